@@ -0,0 +1,149 @@
+use std::collections::VecDeque;
+
+use futures_util::stream::{ self, Stream };
+use tokio::io::{ AsyncBufReadExt, AsyncRead, BufReader, Lines };
+
+use crate::core::*;
+use crate::parsing::{ BaseParser, Parsed, ParserOptions };
+use crate::parsing::parallel::StatementScanner;
+
+// The number of bytes read before the statements read so far are parsed
+const CHUNK_SIZE: usize = 64 * 1024;
+
+// The state of a triple stream between two polls
+struct Reader<R> {
+    lines: Lines<BufReader<R>>,
+    scanner: StatementScanner,
+    options: ParserOptions,
+    pending: VecDeque<Triple>,
+    chunks: usize,
+    done: bool
+}
+
+impl<R: AsyncRead + Unpin> Reader<R> {
+    fn new(reader: R, options: &ParserOptions) -> Self {
+        Self {
+            lines: BufReader::new(reader).lines(),
+            scanner: StatementScanner::new(),
+            options: options.clone(),
+            pending: VecDeque::new(),
+            chunks: 0,
+            done: false
+        }
+    }
+
+    // Reads and parses the next chunk of whole statements. Returns None at the end of the input.
+    async fn next_chunk<P: BaseParser>(&mut self) -> Parsed<Option<Graph>> {
+        let mut chunk = String::new();
+        let mut lines = 0;
+
+        while let Some(line) = self.lines.next_line().await? {
+            chunk += &line;
+            chunk.push('\n');
+            lines += 1;
+
+            if self.scanner.ends_statement(&line) && chunk.len() >= CHUNK_SIZE {
+                break;
+            }
+        }
+
+        if chunk.is_empty() {
+            return Ok(None);
+        }
+
+        // Only the blank nodes generated after the first chunk need a label unique to their chunk
+        let mut options = self.options.clone();
+        if self.chunks > 0 {
+            options.blank_node_prefix = format!("{}{}_", options.blank_node_prefix, self.chunks + 1);
+        }
+
+        let graph = P::graph_with_options(&chunk, &options)?;
+
+        // The directives of this chunk apply to the following ones
+        self.options.prefixes = graph.prefixes.clone().into();
+        self.options.base = graph.base_prefix.clone();
+        self.options.first_line += lines;
+        self.chunks += 1;
+
+        Ok(Some(graph))
+    }
+}
+
+/// Parses the triples read from `reader` as they arrive, without reading the whole input first.
+///
+/// The input is read line by line and parsed in chunks of whole statements, under the same
+/// conditions as [`BaseParser::graph_parallel()`]: chunks end after a line that no statement
+/// continues past. Directives apply to the statements that follow the chunk they are in. The
+/// stream ends after the first error.
+///
+/// # Examples
+///
+/// ```
+/// # use futures_util::StreamExt;
+/// # use rdf_rs::asynchronous;
+/// # use rdf_rs::parsing::{ TurtleParser, ParserOptions, ParserError };
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), ParserError> {
+/// let input: &[u8] = b"ex:John foaf:knows ex:Jane .\nex:Jane foaf:knows ex:John .\n";
+///
+/// let mut triples = Box::pin(asynchronous::triples::<TurtleParser, _>(input, &ParserOptions::default()));
+/// while let Some(triple) = triples.next().await {
+///     println!("{}", triple?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn triples<P, R>(reader: R, options: &ParserOptions) -> impl Stream<Item = Parsed<Triple>>
+    where P: BaseParser, R: AsyncRead + Unpin {
+
+    let reader = Reader::new(reader, options);
+
+    stream::unfold(reader, |mut reader| async move {
+        loop {
+            if let Some(triple) = reader.pending.pop_front() {
+                return Some((Ok(triple), reader));
+            }
+
+            if reader.done {
+                return None;
+            }
+
+            match reader.next_chunk::<P>().await {
+                Ok(Some(graph)) => reader.pending.extend(graph.triples),
+                Ok(None) => reader.done = true,
+                Err(e) => {
+                    reader.done = true;
+                    return Some((Err(e), reader));
+                }
+            }
+        }
+    })
+}
+
+/// Parses a [`Graph`] from `reader`, one chunk of whole statements at a time like
+/// [`triples()`], so that the input is never held in memory as a whole.
+///
+/// # Errors
+///
+/// Returns a [`ParserError`](crate::parsing::ParserError) if reading fails or the input is not a
+/// valid Graph.
+pub async fn graph<P, R>(reader: R, options: &ParserOptions) -> Parsed<Graph>
+    where P: BaseParser, R: AsyncRead + Unpin {
+
+    let mut reader = Reader::new(reader, options);
+    let mut graph = Graph {
+        base_prefix: options.base.clone(),
+        prefixes: options.prefixes.clone().into(),
+        triples: TripleStore::new(),
+        locations: SourceMap::new()
+    };
+
+    while let Some(chunk) = reader.next_chunk::<P>().await? {
+        graph.base_prefix = chunk.base_prefix;
+        graph.prefixes = chunk.prefixes;
+        graph.triples.extend(chunk.triples);
+        graph.locations.merge(chunk.locations);
+    }
+
+    Ok(graph)
+}
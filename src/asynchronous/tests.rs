@@ -0,0 +1,89 @@
+#![allow(dead_code, unused_imports)]
+use futures_util::stream::{ self, StreamExt };
+use tokio::io::AsyncWriteExt;
+
+use crate::asynchronous;
+use crate::assert_isomorphic;
+use crate::core::*;
+use crate::parsing::{ BaseParser, TurtleParser, ParserOptions };
+use crate::serializing::{ BaseSerializer, TurtleSerializer, NTriplesSerializer, SerializerError };
+
+type TestReturn = Result<(), SerializerError>;
+
+#[tokio::test]
+async fn can_parse_from_async_reader() -> TestReturn {
+    let (mut client, server) = tokio::io::duplex(64);
+    let document = std::fs::read_to_string("./test_data/simple.ttl")?;
+
+    let expected = TurtleParser::graph(&document)?;
+
+    let writer = async move {
+        // Trickle the document through the small duplex buffer
+        for line in document.lines() {
+            client.write_all(format!("{}\n", line).as_bytes()).await?;
+        }
+
+        Ok::<_, std::io::Error>(())
+    };
+
+    let reader = asynchronous::triples::<TurtleParser, _>(server, &ParserOptions::default())
+        .collect::<Vec<_>>();
+
+    let (written, triples) = tokio::join!(writer, reader);
+    written?;
+
+    let triples = triples.into_iter().collect::<Result<Vec<Triple>, _>>()?;
+    assert_eq!(triples, expected.triples);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_serialize_to_async_writer() -> TestReturn {
+    let graph = TurtleParser::from_file("./test_data/simple.ttl")?;
+    let (client, server) = tokio::io::duplex(64);
+    let options = ParserOptions::default();
+
+    let (written, parsed) = tokio::join!(
+        asynchronous::write_graph::<TurtleSerializer, _>(&graph, client),
+        asynchronous::graph::<TurtleParser, _>(server, &options)
+    );
+    written?;
+    assert_eq!(parsed?.triples, graph.triples);
+
+    let mut output = Vec::new();
+    let triples = stream::iter(graph.triples.clone().into_iter().take(1));
+    asynchronous::write_triples::<NTriplesSerializer, _, _>(triples, &graph, &mut output).await?;
+    assert_eq!(String::from_utf8(output)?, "<http://example.com/green-goblin> \
+        <http://www.perceive.net/schemas/relationship/enemyOf> <http://example.com/spiderman> .\n");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn can_parse_graph_in_chunks() -> TestReturn {
+    // Large enough to be parsed in several chunks, with directives before and between them
+    let mut document = String::from("@prefix ex: <http://example.com/> .\n");
+    for i in 0..2000 {
+        if i == 1000 {
+            document += "@prefix other: <http://example.org/> .\n";
+        }
+
+        document += &format!("ex:item{} ex:label \"\"\"Item\n{}\"\"\" ;\n    ex:part [ ex:index {} ] .\n", i, i, i);
+    }
+
+    let options = ParserOptions::default().with_locations(None);
+    let expected = TurtleParser::graph_with_options(&document, &options)?;
+    let graph = asynchronous::graph::<TurtleParser, _>(document.as_bytes(), &options).await?;
+
+    assert_eq!(graph.triples.len(), 6000);
+    assert_eq!(graph.prefixes, expected.prefixes);
+    assert_isomorphic!(graph, expected);
+
+    // Locations count the lines of the previous chunks
+    let last = expected.triples.iter().find(|t| t.subject.0.name == "item1999").unwrap();
+    assert_eq!(graph.locations_of(last), expected.locations_of(last));
+    assert!(!graph.locations_of(last).is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use futures_util::stream::{ Stream, StreamExt };
+use tokio::io::{ AsyncWrite, AsyncWriteExt };
+
+use crate::core::*;
+use crate::serializing::{ BaseSerializer, Serialized };
+
+/// Writes a [`Graph`] to `writer`, one triple at a time.
+///
+/// # Errors
+///
+/// Returns a [`SerializerError`](crate::serializing::SerializerError) if a triple cannot be
+/// represented in the format or if writing fails.
+pub async fn write_graph<S, W>(g: &Graph, mut writer: W) -> Serialized<()>
+    where S: BaseSerializer, W: AsyncWrite + Unpin {
+
+    writer.write_all(S::header(g)?.as_bytes()).await?;
+
+    for t in S::triples(g) {
+        writer.write_all(t?.as_bytes()).await?;
+    }
+
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Writes a stream of triples to `writer` as they arrive. `context` provides the header and the
+/// prefixes and base that the triples are relative to; its own triples are not written.
+///
+/// # Errors
+///
+/// Returns a [`SerializerError`](crate::serializing::SerializerError) if a triple cannot be
+/// represented in the format or if writing fails.
+pub async fn write_triples<S, W, T>(triples: T, context: &Graph, mut writer: W) -> Serialized<()>
+    where S: BaseSerializer, W: AsyncWrite + Unpin, T: Stream<Item = Triple> {
+
+    writer.write_all(S::header(context)?.as_bytes()).await?;
+
+    let mut triples = Box::pin(triples);
+    while let Some(t) = triples.next().await {
+        writer.write_all(S::triple(&t, context)?.as_bytes()).await?;
+    }
+
+    writer.flush().await?;
+    Ok(())
+}
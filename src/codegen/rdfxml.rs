@@ -0,0 +1,584 @@
+use std::collections::HashMap;
+
+use crate::core::{ Graph, Triple, Resource, Relationship, Object, Literal, Uri, SourceMap, uri::UriType };
+use crate::parsing::{ ParserOptions, ParserError, Parsed };
+use crate::vocab::{ rdf, xsd };
+
+const XML: &str = "http://www.w3.org/XML/1998/namespace";
+
+// The attributes that are part of the syntax of RDF/XML rather than properties
+const SYNTAX: &[&str] = &["about", "ID", "nodeID", "resource", "parseType", "datatype", "bagID", "aboutEach", "aboutEachPrefix"];
+
+// What is in scope at an element: the namespaces declared by it and its ancestors, its base IRI
+// and its language
+#[derive(Clone, Default)]
+struct Scope {
+    namespaces: HashMap<String, String>,
+    base: Option<String>,
+    language: Option<String>
+}
+
+// An element with its name and the names of its attributes expanded to IRIs
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Node>,
+    // The source of the content, for rdf:parseType="Literal"
+    inner: String,
+    base: Option<String>,
+    language: Option<String>
+}
+
+enum Node {
+    Element(Element),
+    Text(String)
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter().find(|(n, _)| n == name).map(|(_, value)| value.as_str())
+    }
+
+    fn is_rdf(&self, name: &str) -> bool {
+        self.name.strip_prefix(rdf::NAMESPACE) == Some(name)
+    }
+
+    fn rdf(&self, name: &str) -> Option<&str> {
+        self.attribute(&format!("{}{}", rdf::NAMESPACE, name))
+    }
+
+    // The attributes that are properties of the node
+    fn properties(&self) -> impl Iterator<Item = &(String, String)> {
+        self.attributes.iter().filter(|(name, _)| !name.starts_with(XML) &&
+            !name.strip_prefix(rdf::NAMESPACE).is_some_and(|n| SYNTAX.contains(&n)))
+    }
+
+    fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|c| match c {
+            Node::Element(e) => Some(e),
+            Node::Text(_) => None
+        })
+    }
+
+    fn text(&self) -> String {
+        self.children.iter().filter_map(|c| match c {
+            Node::Text(t) => Some(t.as_str()),
+            Node::Element(_) => None
+        }).collect()
+    }
+}
+
+// Reads the elements of an XML document
+struct Reader<'a> {
+    source: &'a str,
+    position: usize,
+    entities: HashMap<String, String>,
+    // The prefixes declared in the document, in order
+    prefixes: Vec<(String, String)>
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn error<T>(&self, message: &str) -> Parsed<T> {
+        let line = self.source[..self.position].matches('\n').count() + 1;
+        Err(ParserError(format!("Invalid RDF/XML at line {}: {}", line, message)))
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    // Skips past `end`, returning what comes before it
+    fn until(&mut self, end: &str) -> Parsed<&'a str> {
+        match self.rest().find(end) {
+            Some(i) => {
+                let skipped = &self.rest()[..i];
+                self.position += i + end.len();
+                Ok(skipped)
+            },
+            None => self.error(&format!("expected {}", end))
+        }
+    }
+
+    fn name(&mut self) -> &'a str {
+        let rest = self.rest();
+        let length = rest.find(|c: char| c.is_whitespace() || "/>=".contains(c)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    // Replaces the character and entity references of `text`
+    fn decode(&self, text: &str) -> Parsed<String> {
+        let mut decoded = String::new();
+        let mut rest = text;
+
+        while let Some(i) = rest.find('&') {
+            decoded.push_str(&rest[..i]);
+            let end = match rest[i..].find(';') {
+                Some(end) => i + end,
+                None => return self.error("unterminated reference")
+            };
+            let reference = &rest[i + 1..end];
+
+            let character = if let Some(hex) = reference.strip_prefix("#x") {
+                u32::from_str_radix(hex, 16).ok()
+            } else if let Some(decimal) = reference.strip_prefix('#') {
+                decimal.parse().ok()
+            } else {
+                None
+            };
+
+            match (reference, character) {
+                (_, Some(c)) => match char::from_u32(c) {
+                    Some(c) => decoded.push(c),
+                    None => return self.error(&format!("invalid character reference &{};", reference))
+                },
+                ("lt", _) => decoded.push('<'),
+                ("gt", _) => decoded.push('>'),
+                ("amp", _) => decoded.push('&'),
+                ("quot", _) => decoded.push('"'),
+                ("apos", _) => decoded.push('\''),
+                _ => match self.entities.get(reference) {
+                    Some(value) => decoded.push_str(value),
+                    None => return self.error(&format!("undeclared entity &{};", reference))
+                }
+            }
+            rest = &rest[end + 1..];
+        }
+
+        decoded.push_str(rest);
+        Ok(decoded)
+    }
+
+    // Reads the entities declared by the internal subset of a document type declaration
+    fn doctype(&mut self) -> Parsed<()> {
+        let declaration = self.rest();
+        let subset = match (declaration.find('['), declaration.find('>')) {
+            (Some(open), Some(close)) if open < close => {
+                self.position += open + 1;
+                self.until("]")?
+            },
+            _ => ""
+        };
+        self.until(">")?;
+
+        let mut rest = subset;
+        while let Some(i) = rest.find("<!ENTITY") {
+            rest = rest[i + "<!ENTITY".len()..].trim_start();
+            let name_length = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            let name = &rest[..name_length];
+            rest = rest[name_length..].trim_start();
+
+            let quote = match rest.chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => continue
+            };
+            if let Some(end) = rest[1..].find(quote) {
+                let value = self.decode(&rest[1..=end])?;
+                self.entities.insert(name.to_string(), value);
+                rest = &rest[end + 2..];
+            }
+        }
+
+        Ok(())
+    }
+
+    // Skips comments, processing instructions and document type declarations
+    fn skip_markup(&mut self) -> Parsed<bool> {
+        if self.rest().starts_with("<!--") {
+            self.until("-->")?;
+        } else if self.rest().starts_with("<?") {
+            self.until("?>")?;
+        } else if self.rest().starts_with("<!DOCTYPE") {
+            self.doctype()?;
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    fn document(&mut self, base: Option<String>) -> Parsed<Element> {
+        loop {
+            self.skip_whitespace();
+            if !self.skip_markup()? {
+                break;
+            }
+        }
+
+        if !self.rest().starts_with('<') {
+            return self.error("expected an element");
+        }
+
+        let root = self.element(&Scope { base, ..Scope::default() })?;
+        loop {
+            self.skip_whitespace();
+            if !self.skip_markup()? {
+                break;
+            }
+        }
+
+        match self.rest().is_empty() {
+            true => Ok(root),
+            false => self.error("content after the root element")
+        }
+    }
+
+    // Expands a qualified name to an IRI. Attributes without a prefix are in no namespace.
+    fn expand(&self, name: &str, scope: &Scope, attribute: bool) -> Parsed<Option<String>> {
+        let (prefix, local) = match name.split_once(':') {
+            Some(("xml", local)) => return Ok(Some(format!("{}{}", XML, local))),
+            Some((prefix, local)) => (prefix, local),
+            None if attribute => return Ok(None),
+            None => ("", name)
+        };
+
+        match scope.namespaces.get(prefix) {
+            Some(namespace) => Ok(Some(format!("{}{}", namespace, local))),
+            None => self.error(&format!("undeclared prefix {}", prefix))
+        }
+    }
+
+    fn element(&mut self, parent: &Scope) -> Parsed<Element> {
+        self.position += 1;
+        let tag = self.name();
+        let mut raw: Vec<(&str, String)> = Vec::new();
+
+        let empty = loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.position += 2;
+                break true;
+            } else if self.rest().starts_with('>') {
+                self.position += 1;
+                break false;
+            }
+
+            let name = self.name();
+            self.skip_whitespace();
+            if name.is_empty() || !self.rest().starts_with('=') {
+                return self.error(&format!("expected an attribute of <{}>", tag));
+            }
+            self.position += 1;
+            self.skip_whitespace();
+
+            let quote = match self.rest().chars().next() {
+                Some(quote @ ('"' | '\'')) => quote,
+                _ => return self.error(&format!("expected the value of {}", name))
+            };
+            self.position += 1;
+            let value = self.until(&quote.to_string())?;
+            raw.push((name, self.decode(value)?));
+        };
+
+        let mut scope = parent.clone();
+        for (name, value) in &raw {
+            if *name == "xmlns" {
+                scope.namespaces.insert(String::new(), value.clone());
+            } else if let Some(prefix) = name.strip_prefix("xmlns:") {
+                scope.namespaces.insert(prefix.to_string(), value.clone());
+                self.prefixes.push((prefix.to_string(), value.clone()));
+            } else if *name == "xml:base" {
+                scope.base = Some(resolve(parent.base.as_deref(), value)?);
+            } else if *name == "xml:lang" {
+                scope.language = Some(value.clone()).filter(|l| !l.is_empty());
+            }
+        }
+
+        let mut attributes = Vec::new();
+        for (name, value) in raw {
+            if name == "xmlns" || name.starts_with("xmlns:") {
+                continue;
+            }
+            if let Some(name) = self.expand(name, &scope, true)? {
+                attributes.push((name, value));
+            }
+        }
+
+        let name = match self.expand(tag, &scope, false)? {
+            Some(name) => name,
+            None => return self.error(&format!("invalid element name {}", tag))
+        };
+
+        let mut element = Element {
+            name,
+            attributes,
+            children: Vec::new(),
+            inner: String::new(),
+            base: scope.base.clone(),
+            language: scope.language.clone()
+        };
+        if empty {
+            return Ok(element);
+        }
+
+        let start = self.position;
+        loop {
+            let rest = self.rest();
+
+            if rest.is_empty() {
+                return self.error(&format!("unclosed element <{}>", tag));
+            } else if rest.starts_with("</") {
+                element.inner = self.source[start..self.position].to_string();
+                self.position += 2;
+                let close = self.name();
+                self.skip_whitespace();
+                if close != tag || !self.rest().starts_with('>') {
+                    return self.error(&format!("expected </{}>", tag));
+                }
+                self.position += 1;
+                return Ok(element);
+            } else if rest.starts_with("<![CDATA[") {
+                self.position += "<![CDATA[".len();
+                let text = self.until("]]>")?;
+                element.children.push(Node::Text(text.to_string()));
+            } else if self.skip_markup()? {
+                continue;
+            } else if rest.starts_with('<') {
+                let child = self.element(&scope)?;
+                element.children.push(Node::Element(child));
+            } else {
+                let length = rest.find('<').unwrap_or(rest.len());
+                self.position += length;
+                element.children.push(Node::Text(self.decode(&rest[..length])?));
+            }
+        }
+    }
+}
+
+// Whether the IRI reference starts with a scheme
+fn is_absolute(iri: &str) -> bool {
+    match iri.find(':') {
+        Some(i) => iri[..i].chars().next().is_some_and(|c| c.is_ascii_alphabetic()) &&
+            iri[..i].chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)),
+        None => false
+    }
+}
+
+// Resolves an IRI reference against a base IRI, without removing dot segments
+fn resolve(base: Option<&str>, reference: &str) -> Parsed<String> {
+    if is_absolute(reference) {
+        return Ok(reference.to_string());
+    }
+
+    let base = base.ok_or_else(|| ParserError(format!("The relative IRI <{}> has no base; set one with ParserOptions::with_base", reference)))?;
+    let document = &base[..base.find('#').unwrap_or(base.len())];
+    let scheme = &base[..base.find(':').map_or(0, |i| i + 1)];
+    let authority = match base.strip_prefix(scheme).and_then(|rest| rest.strip_prefix("//")) {
+        Some(rest) => &base[..scheme.len() + 2 + rest.find(['/', '?', '#']).unwrap_or(rest.len())],
+        None => scheme
+    };
+
+    Ok(if reference.is_empty() || reference.starts_with('#') {
+        format!("{}{}", document, reference)
+    } else if reference.starts_with("//") {
+        format!("{}{}", scheme, reference)
+    } else if reference.starts_with('/') {
+        format!("{}{}", authority, reference)
+    } else {
+        let path = &document[..document.find('?').unwrap_or(document.len())];
+        let directory = match path.rfind('/') {
+            Some(i) if i >= authority.len() => &path[..=i],
+            _ => return Ok(format!("{}/{}", authority, reference))
+        };
+        format!("{}{}", directory, reference)
+    })
+}
+
+// An absolute IRI, split after its last '#' or '/'
+fn iri(iri: &str) -> Uri {
+    let split = iri.rfind(['#', '/']).map_or(0, |i| i + 1);
+    Uri::new(&iri[..split], &iri[split..], UriType::Full)
+}
+
+// Turns the elements of an RDF/XML document into triples
+struct Triples<'a> {
+    triples: Vec<Triple>,
+    blank_node_prefix: &'a str,
+    blank_nodes: usize
+}
+
+impl Triples<'_> {
+    fn push(&mut self, subject: &Uri, predicate: Uri, object: Object) {
+        self.triples.push((Resource(subject.clone()), Relationship(predicate), object).into());
+    }
+
+    fn blank_node(&mut self, label: Option<&str>) -> Uri {
+        match label {
+            Some(label) => Uri::new("_:", label, UriType::Prefixed),
+            None => {
+                self.blank_nodes += 1;
+                Uri::new("_:", &format!("{}{}", self.blank_node_prefix, self.blank_nodes), UriType::Prefixed)
+            }
+        }
+    }
+
+    fn resource(&self, element: &Element, reference: &str) -> Parsed<Uri> {
+        resolve(element.base.as_deref(), reference).map(|i| iri(&i))
+    }
+
+    // A literal, keeping the quotes of its value like the Turtle parser does
+    fn literal(text: &str, datatype: Option<Uri>, language: Option<&str>) -> Object {
+        let quote = if text.contains('\n') { "\"\"\"" } else { "\"" };
+        let escaped = text.replace('\\', "\\\\").replace('"', "\\\"");
+
+        Object::Literal(Literal {
+            value: format!("{}{}{}", quote, escaped, quote),
+            datatype: datatype.unwrap_or_else(|| match language {
+                Some(_) => rdf::LANG_STRING.prefixed(),
+                None => xsd::STRING.prefixed()
+            }),
+            language: language.map(|l| l.to_string())
+        })
+    }
+
+    // The triples of the property attributes of an element about `subject`
+    fn property_attributes(&mut self, subject: &Uri, element: &Element) -> Parsed<()> {
+        for (name, value) in element.properties() {
+            let object = if rdf::TYPE.iri() == *name {
+                Object::Resource(self.resource(element, value)?)
+            } else {
+                Self::literal(value, None, element.language.as_deref())
+            };
+
+            self.push(subject, iri(name), object);
+        }
+
+        Ok(())
+    }
+
+    // The triples of a node element, returning its subject
+    fn node(&mut self, element: &Element) -> Parsed<Uri> {
+        let subject = if let Some(about) = element.rdf("about") {
+            self.resource(element, about)?
+        } else if let Some(id) = element.rdf("ID") {
+            self.resource(element, &format!("#{}", id))?
+        } else {
+            let label = element.rdf("nodeID");
+            self.blank_node(label)
+        };
+
+        if !element.is_rdf("Description") {
+            self.push(&subject, rdf::TYPE.uri(), Object::Resource(iri(&element.name)));
+        }
+        self.property_attributes(&subject, element)?;
+
+        if !element.text().trim().is_empty() {
+            return Err(ParserError(format!("Invalid RDF/XML: unexpected text in <{}>", element.name)));
+        }
+
+        let mut items = 0;
+        for property in element.elements() {
+            self.property(&subject, property, &mut items)?;
+        }
+
+        Ok(subject)
+    }
+
+    // The triples of a property element of `subject`
+    fn property(&mut self, subject: &Uri, element: &Element, items: &mut usize) -> Parsed<()> {
+        let predicate = if element.is_rdf("li") {
+            *items += 1;
+            Uri::new(rdf::NAMESPACE, &format!("_{}", items), UriType::Full)
+        } else {
+            iri(&element.name)
+        };
+
+        let object = match element.rdf("parseType") {
+            Some("Resource") => {
+                let object = self.blank_node(None);
+                let mut items = 0;
+                for property in element.elements() {
+                    self.property(&object, property, &mut items)?;
+                }
+
+                Object::Resource(object)
+            },
+            Some("Collection") => {
+                let mut members = Vec::new();
+                for node in element.elements() {
+                    members.push(self.node(node)?);
+                }
+
+                let mut list = rdf::NIL.uri();
+                for member in members.into_iter().rev() {
+                    let cell = self.blank_node(None);
+                    self.push(&cell, rdf::FIRST.uri(), Object::Resource(member));
+                    self.push(&cell, rdf::REST.uri(), Object::Resource(list));
+                    list = cell;
+                }
+
+                Object::Resource(list)
+            },
+            Some(_) => Self::literal(&element.inner, Some(rdf::XML_LITERAL.uri()), None),
+            None => {
+                let nodes: Vec<&Element> = element.elements().collect();
+
+                match nodes.as_slice() {
+                    [node] => Object::Resource(self.node(node)?),
+                    [_, _, ..] => return Err(ParserError(format!("Invalid RDF/XML: <{}> has several node elements", element.name))),
+                    [] => {
+                        let resource = match (element.rdf("resource"), element.rdf("nodeID")) {
+                            (Some(reference), _) => Some(self.resource(element, reference)?),
+                            (None, Some(label)) => Some(self.blank_node(Some(label))),
+                            (None, None) if element.properties().next().is_some() => Some(self.blank_node(None)),
+                            (None, None) => None
+                        };
+
+                        match resource {
+                            Some(resource) => {
+                                self.property_attributes(&resource, element)?;
+                                Object::Resource(resource)
+                            },
+                            None => {
+                                let datatype = match element.rdf("datatype") {
+                                    Some(datatype) => Some(self.resource(element, datatype)?),
+                                    None => None
+                                };
+                                let language = if datatype.is_some() { None } else { element.language.as_deref() };
+
+                                Self::literal(&element.text(), datatype, language)
+                            }
+                        }
+                    }
+                }
+            }
+        };
+
+        self.push(subject, predicate, object);
+        Ok(())
+    }
+}
+
+// Parses an RDF/XML document. Only what ontologies use is supported: reification with rdf:ID on
+// property elements and the deprecated attributes without a namespace are ignored.
+pub(crate) fn graph(source: &str, options: &ParserOptions) -> Parsed<Graph> {
+    let mut reader = Reader { source, position: 0, entities: HashMap::new(), prefixes: Vec::new() };
+    let root = reader.document(options.base.clone())?;
+
+    // The first declaration of a prefix wins, like the first namespace of a label in Turtle
+    let mut prefixes = options.prefixes.clone();
+    for (label, namespace) in reader.prefixes.into_iter().rev() {
+        prefixes.insert(format!("{}:", label), namespace);
+    }
+
+    let mut triples = Triples { triples: Vec::new(), blank_node_prefix: &options.blank_node_prefix, blank_nodes: 0 };
+    if root.is_rdf("RDF") {
+        for node in root.elements() {
+            triples.node(node)?;
+        }
+    } else {
+        triples.node(&root)?;
+    }
+
+    Ok(Graph {
+        base_prefix: options.base.clone(),
+        prefixes: prefixes.into(),
+        triples: triples.triples.into(),
+        locations: SourceMap::new()
+    })
+}
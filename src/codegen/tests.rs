@@ -0,0 +1,130 @@
+#![allow(dead_code, unused_imports)]
+use crate::codegen::*;
+use crate::parsing::{ BaseParser, TurtleParser, ParserError };
+
+type TestReturn = Result<(), ParserError>;
+
+#[test]
+fn can_name_constants() {
+    assert_eq!(constant_name("subClassOf"), "SUB_CLASS_OF");
+    assert_eq!(constant_name("XMLLiteral"), "XML_LITERAL");
+    assert_eq!(constant_name("based_near"), "BASED_NEAR");
+    assert_eq!(constant_name("IRI"), "IRI");
+    assert_eq!(constant_name("hasISBN13"), "HAS_ISBN13");
+    assert_eq!(constant_name("part-of"), "PART_OF");
+    assert_eq!(constant_name("3DModel"), "_3D_MODEL");
+}
+
+#[test]
+fn can_generate_vocabularies() -> TestReturn {
+    let ontology = r#"
+        @prefix pets: <http://example.com/ns/pets#> .
+        @prefix other: <http://example.com/ns/other#> .
+
+        pets:Pet a owl:Class ;
+            rdfs:label "Haustier"@de , "Pet"@en ;
+            rdfs:comment "An animal kept for company." .
+        pets:Dog a rdfs:Class ; rdfs:subClassOf pets:Pet .
+        pets:name a owl:DatatypeProperty .
+        pets:Name a rdfs:Class .
+        pets:undefined rdfs:label "Not a class" .
+        other:Thing a rdfs:Class .
+    "#;
+
+    // The ontology defines terms in two namespaces
+    assert!(generate(ontology, &CodegenOptions::default()).is_err());
+
+    let source = generate(ontology, &CodegenOptions::default().with_namespace("http://example.com/ns/pets#"))?;
+
+    assert!(source.contains("pub const NAMESPACE: &str = \"http://example.com/ns/pets#\";"));
+    assert!(source.contains("pub const PREFIX: &str = \"pets:\";"));
+    assert!(source.contains("/// `pets:Pet`, a class: Pet\n///\n/// An animal kept for company.\npub const PET: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"Pet\");"));
+    assert!(source.contains("/// `pets:Dog`, a class\npub const DOG: VocabTerm"));
+    assert!(source.contains("pub const NAME: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"Name\");"));
+    assert!(source.contains("/// `pets:name`, a property\npub const NAME_2: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"name\");"));
+    assert!(source.contains("pub const TERMS: &[VocabTerm] = &[DOG, NAME, PET, NAME_2];"));
+    assert!(!source.contains("undefined"));
+    assert!(!source.contains("Thing"));
+
+    let source = generate(ontology, &CodegenOptions::default().with_namespace("http://example.com/ns/other#").with_prefix("o"))?;
+    assert!(source.contains("pub const PREFIX: &str = \"o:\";"));
+    assert!(source.contains("pub const TERMS: &[VocabTerm] = &[THING];"));
+
+    Ok(())
+}
+
+#[test]
+fn can_keep_constant_names_apart() -> TestReturn {
+    let source = generate(r#"
+        @prefix ex: <http://example.com/ns#> .
+
+        ex:Cat a rdfs:Class .
+        ex:Cat_2 a rdfs:Class .
+        ex:cat a rdf:Property .
+        ex:namespace a rdf:Property .
+        ex:namespaceTerm a rdf:Property .
+        ex:prefix a rdf:Property .
+        ex:Terms a rdfs:Class .
+    "#, &CodegenOptions::default())?;
+
+    assert!(source.contains("pub const NAMESPACE: &str = \"http://example.com/ns#\";"));
+    assert!(source.contains("pub const CAT_3: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"cat\");"));
+    assert!(source.contains("pub const NAMESPACE_TERM: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"namespace\");"));
+    assert!(source.contains("pub const NAMESPACE_TERM_2: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"namespaceTerm\");"));
+    assert!(source.contains("pub const PREFIX_TERM: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"prefix\");"));
+    assert!(source.contains("pub const TERMS: &[VocabTerm] = &[CAT, CAT_2, TERMS_TERM, CAT_3, NAMESPACE_TERM, NAMESPACE_TERM_2, PREFIX_TERM];"));
+
+    Ok(())
+}
+
+#[test]
+fn can_generate_vocabularies_from_rdf_xml() -> TestReturn {
+    let ontology = r##"<?xml version="1.0" encoding="utf-8"?>
+        <!DOCTYPE rdf:RDF [
+            <!ENTITY pets "http://example.com/ns/pets#">
+        ]>
+        <!-- Pets and their owners -->
+        <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                 xmlns:rdfs="http://www.w3.org/2000/01/rdf-schema#"
+                 xmlns:owl="http://www.w3.org/2002/07/owl#"
+                 xmlns:pets="&pets;"
+                 xml:base="http://example.com/ns/pets">
+            <owl:Class rdf:about="&pets;Pet">
+                <rdfs:label xml:lang="de">Haustier</rdfs:label>
+                <rdfs:label xml:lang="en">Pet</rdfs:label>
+                <rdfs:comment>An animal kept for company &amp; joy.</rdfs:comment>
+            </owl:Class>
+            <rdfs:Class rdf:ID="Dog" rdfs:label="Dog">
+                <rdfs:subClassOf rdf:resource="#Pet"/>
+                <owl:disjointWith>
+                    <owl:Class rdf:about="http://example.com/ns/pets#Cat"/>
+                </owl:disjointWith>
+            </rdfs:Class>
+            <rdf:Description rdf:about="http://example.com/ns/pets#hasOwner">
+                <rdf:type rdf:resource="http://www.w3.org/2002/07/owl#ObjectProperty"/>
+                <rdfs:domain>
+                    <owl:Class>
+                        <owl:unionOf rdf:parseType="Collection">
+                            <rdf:Description rdf:about="#Dog"/>
+                            <rdf:Description rdf:about="#Cat"/>
+                        </owl:unionOf>
+                    </owl:Class>
+                </rdfs:domain>
+                <rdfs:comment><![CDATA[The <person> who owns the pet.]]></rdfs:comment>
+            </rdf:Description>
+        </rdf:RDF>
+    "##;
+
+    let source = generate(ontology, &CodegenOptions::default())?;
+
+    assert!(source.contains("pub const PREFIX: &str = \"pets:\";"));
+    assert!(source.contains("/// `pets:Pet`, a class: Pet\n///\n/// An animal kept for company & joy.\npub const PET: VocabTerm"));
+    assert!(source.contains("/// `pets:Dog`, a class: Dog\npub const DOG: VocabTerm"));
+    assert!(source.contains("/// `pets:hasOwner`, a property\n///\n/// The <person> who owns the pet.\npub const HAS_OWNER: VocabTerm"));
+    assert!(source.contains("pub const TERMS: &[VocabTerm] = &[CAT, DOG, PET, HAS_OWNER];"));
+
+    assert!(generate("<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"><rdf:Description>", &CodegenOptions::default()).is_err());
+    assert!(generate("<rdf:RDF xmlns:rdfs=\"http://www.w3.org/2000/01/rdf-schema#\"></rdf:RDF>", &CodegenOptions::default()).is_err());
+
+    Ok(())
+}
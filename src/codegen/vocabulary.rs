@@ -0,0 +1,351 @@
+use std::collections::{ BTreeMap, HashSet };
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::core::{ Graph, Object, Uri, uri::UriType };
+use crate::parsing::{ TurtleParser, BaseParser, ParserOptions, ParserError, Parsed };
+use crate::vocab::{ rdf, rdfs, owl, VocabTerm };
+
+// The types that make a term a class or a property
+const CLASSES: &[VocabTerm] = &[rdfs::CLASS, owl::CLASS, rdfs::DATATYPE];
+const PROPERTIES: &[VocabTerm] = &[rdf::PROPERTY, owl::OBJECT_PROPERTY, owl::DATATYPE_PROPERTY, owl::ANNOTATION_PROPERTY];
+
+// The constants every generated module declares besides those of the terms
+const RESERVED: &[&str] = &["NAMESPACE", "PREFIX", "TERMS"];
+
+/// Configuration of the vocabulary module generated by [`generate()`].
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::codegen::CodegenOptions;
+/// let options = CodegenOptions::default()
+///     .with_namespace("http://example.com/ns/pets#")
+///     .with_prefix("pets");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodegenOptions {
+    /// The namespace of the generated terms. Classes and properties of other namespaces are
+    /// skipped. Defaults to the namespace of the terms the ontology defines, if they share one.
+    pub namespace: Option<String>,
+    /// The label of the namespace, including the colon (`"ex:"`). Defaults to the label the
+    /// ontology declares for the namespace.
+    pub prefix: Option<String>,
+    /// The options the ontology is parsed with. The default ones predefine the well-known
+    /// prefixes.
+    pub parser_options: ParserOptions
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            namespace: None,
+            prefix: None,
+            parser_options: ParserOptions::default().with_well_known_prefixes()
+        }
+    }
+}
+
+impl CodegenOptions {
+    /// Only generates the terms of `namespace`.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Sets the label of the namespace. The trailing colon is optional.
+    pub fn with_prefix(mut self, label: &str) -> Self {
+        self.prefix = Some(if label.ends_with(':') { label.to_string() } else { format!("{}:", label) });
+        self
+    }
+
+    /// Parses the ontology with `options`.
+    pub fn with_parser_options(mut self, options: ParserOptions) -> Self {
+        self.parser_options = options;
+        self
+    }
+}
+
+#[derive(Default)]
+struct Definition {
+    class: bool,
+    property: bool,
+    labels: Vec<(Option<String>, String)>,
+    comments: Vec<(Option<String>, String)>
+}
+
+impl Definition {
+    fn kind(&self) -> &'static str {
+        match (self.class, self.property) {
+            (true, true) => "class and property",
+            (true, false) => "class",
+            _ => "property"
+        }
+    }
+
+    // The English text if there is one, then the one without a language, then the first one
+    fn text(texts: &[(Option<String>, String)]) -> Option<&str> {
+        let english = |language: &Option<String>| language.as_deref().is_some_and(|l| l.eq_ignore_ascii_case("en") || l.to_ascii_lowercase().starts_with("en-"));
+
+        texts.iter().find(|(language, _)| english(language))
+            .or_else(|| texts.iter().find(|(language, _)| language.is_none()))
+            .or_else(|| texts.first())
+            .map(|(_, text)| text.as_str())
+    }
+}
+
+// The namespace of an IRI, up to its last '#' or '/'
+fn namespace_of(iri: &str) -> &str {
+    match iri.rfind(['#', '/']) {
+        Some(i) => &iri[..=i],
+        None => iri
+    }
+}
+
+/// Returns the name of the constant for the local name `name`: `subClassOf` becomes
+/// `SUB_CLASS_OF` and `XMLLiteral` becomes `XML_LITERAL`.
+pub fn constant_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut constant = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            constant.push('_');
+            continue;
+        }
+
+        if i > 0 && c.is_uppercase() {
+            let previous = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+
+            if previous.is_lowercase() || (previous.is_uppercase() && next_is_lower) {
+                constant.push('_');
+            }
+        }
+
+        constant.extend(c.to_uppercase());
+    }
+
+    let constant = constant.split('_').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("_");
+    match constant.chars().next() {
+        None => "_".into(),
+        Some(c) if c.is_numeric() => format!("_{}", constant),
+        _ => constant
+    }
+}
+
+// The lines of a doc comment, without trailing whitespace
+fn doc_lines(out: &mut String, text: &str) {
+    for line in text.trim().lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            out.push_str("///\n");
+        } else {
+            let _ = writeln!(out, "/// {}", line.trim_start());
+        }
+    }
+}
+
+/// Generates the vocabulary module of an ontology that is already parsed. See [`generate()`].
+///
+/// # Errors
+///
+/// Returns a [`ParserError`] if the namespace or its label are not given and cannot be told
+/// from the ontology.
+pub fn generate_from_graph(graph: &Graph, options: &CodegenOptions) -> Parsed<String> {
+    let mut definitions: BTreeMap<String, Definition> = BTreeMap::new();
+    let iri = |u: &Uri| if u.uri_type == UriType::Full { Some(u.to_string()) } else { None };
+
+    for t in graph.triples.iter().map(|t| graph.expanded(t)) {
+        let subject = match iri(&t.subject.0) {
+            Some(subject) => subject,
+            None => continue
+        };
+        let predicate = &t.predicate.0;
+
+        if rdf::TYPE.matches(predicate) {
+            let class = CLASSES.iter().any(|c| c.matches_object(&t.object));
+            let property = PROPERTIES.iter().any(|p| p.matches_object(&t.object));
+
+            if class || property {
+                let definition = definitions.entry(subject).or_default();
+                definition.class |= class;
+                definition.property |= property;
+            }
+        } else if let Object::Literal(l) = &t.object {
+            let text = (l.language.clone(), l.lexical_form().to_string());
+
+            if rdfs::LABEL.matches(predicate) {
+                definitions.entry(subject).or_default().labels.push(text);
+            } else if rdfs::COMMENT.matches(predicate) {
+                definitions.entry(subject).or_default().comments.push(text);
+            }
+        }
+    }
+    definitions.retain(|_, d| d.class || d.property);
+
+    let namespace = match &options.namespace {
+        Some(namespace) => namespace.clone(),
+        None => {
+            let mut namespaces: Vec<&str> = definitions.keys().map(|iri| namespace_of(iri)).collect();
+            namespaces.sort_unstable();
+            namespaces.dedup();
+
+            match namespaces.as_slice() {
+                [namespace] => namespace.to_string(),
+                [] => return Err(ParserError("The ontology defines no class or property".into())),
+                _ => return Err(ParserError(format!("The ontology defines terms in several namespaces ({}); choose one with CodegenOptions::with_namespace", namespaces.join(", "))))
+            }
+        }
+    };
+
+    let prefix = match &options.prefix {
+        Some(prefix) => prefix.clone(),
+        None => {
+            let mut labels: Vec<&String> = graph.prefixes.iter()
+                .filter(|(_, n)| **n == namespace)
+                .map(|(label, _)| label)
+                .collect();
+            labels.sort();
+
+            labels.first()
+                .map(|label| label.to_string())
+                .ok_or_else(|| ParserError(format!("The ontology declares no prefix for {}; set one with CodegenOptions::with_prefix", namespace)))?
+        }
+    };
+
+    // Terms named like the constants of the module get a `_TERM` suffix, and names that map to a
+    // constant already used are told apart by the first free number, in IRI order
+    let mut used: HashSet<String> = RESERVED.iter().map(|name| name.to_string()).collect();
+    let terms: Vec<(String, &str, &Definition)> = definitions.iter()
+        .filter_map(|(iri, d)| iri.strip_prefix(namespace.as_str()).filter(|name| !name.is_empty()).map(|name| (name, d)))
+        .map(|(name, d)| {
+            let mut base = constant_name(name);
+            if RESERVED.contains(&base.as_str()) {
+                base.push_str("_TERM");
+            }
+
+            let mut constant = base.clone();
+            let mut count = 1;
+            while !used.insert(constant.clone()) {
+                count += 1;
+                constant = format!("{}_{}", base, count);
+            }
+
+            (constant, name, d)
+        })
+        .collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "// Generated by rdf_rs::codegen. Do not edit.");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "#[allow(unused_imports)]");
+    let _ = writeln!(out, "use rdf_rs::vocab::VocabTerm;");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "/// The namespace IRI.");
+    let _ = writeln!(out, "pub const NAMESPACE: &str = {:?};", namespace);
+    let _ = writeln!(out, "/// The usual label of the namespace, including the colon.");
+    let _ = writeln!(out, "pub const PREFIX: &str = {:?};", prefix);
+
+    for (constant, name, definition) in &terms {
+        let _ = writeln!(out);
+
+        let label = Definition::text(&definition.labels);
+        let comment = Definition::text(&definition.comments);
+        doc_lines(&mut out, &format!("`{}{}`, a {}{}", prefix, name, definition.kind(), label.map(|l| format!(": {}", l)).unwrap_or_default()));
+        if let Some(comment) = comment {
+            out.push_str("///\n");
+            doc_lines(&mut out, comment);
+        }
+
+        let _ = writeln!(out, "pub const {}: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, {:?});", constant, name);
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "/// Every class and property of the vocabulary.");
+    let _ = writeln!(out, "pub const TERMS: &[VocabTerm] = &[{}];", terms.iter().map(|(c, _, _)| c.as_str()).collect::<Vec<_>>().join(", "));
+
+    Ok(out)
+}
+
+/// Parses a Turtle or RDF/XML ontology and generates the source of a Rust module with a
+/// [`VocabTerm`] constant for each class (`rdfs:Class`, `owl:Class` or `rdfs:Datatype`)
+/// and property (`rdf:Property`, `owl:ObjectProperty`, `owl:DatatypeProperty` or
+/// `owl:AnnotationProperty`) of its namespace, like those of [`crate::vocab`].
+///
+/// The constants are named after the local names of the terms (`hasOwner` becomes `HAS_OWNER`)
+/// and documented with their `rdfs:label` and `rdfs:comment`, in English if they have several.
+/// The module also contains `NAMESPACE`, `PREFIX` and `TERMS`; the constants of terms with these
+/// names get a `_TERM` suffix (`NAMESPACE_TERM`).
+///
+/// Ontologies that start with an XML declaration, a document type declaration or an element with
+/// attributes are read as RDF/XML, with the namespaces they declare as prefixes. Reification
+/// with `rdf:ID` on property elements is not supported and ignored.
+///
+/// # Errors
+///
+/// Returns a [`ParserError`] if the ontology is not valid Turtle or RDF/XML, or if the namespace
+/// or its label cannot be told from the ontology.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::parsing::ParserError;
+/// # use rdf_rs::codegen::{ generate, CodegenOptions };
+/// # fn main() -> Result<(), ParserError> {
+/// let source = generate(r#"
+///     @prefix pets: <http://example.com/ns/pets#> .
+///
+///     pets:Cat a rdfs:Class ; rdfs:label "Cat" ; rdfs:comment "A small feline." .
+///     pets:hasOwner a rdf:Property .
+/// "#, &CodegenOptions::default())?;
+///
+/// assert!(source.contains("/// `pets:Cat`, a class: Cat\n///\n/// A small feline.\npub const CAT: VocabTerm"));
+/// assert!(source.contains("pub const HAS_OWNER: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, \"hasOwner\");"));
+/// # Ok(())
+/// # }
+/// ```
+pub fn generate(ontology: &str, options: &CodegenOptions) -> Parsed<String> {
+    // A Turtle document can only start with '<' for an IRI, which has no whitespace
+    let start = ontology.trim_start();
+    let xml = start.starts_with("<?") || start.starts_with("<!") ||
+        (start.starts_with('<') && start[..start.find('>').unwrap_or(start.len())].contains(char::is_whitespace));
+
+    let graph = if xml {
+        super::rdfxml::graph(ontology, &options.parser_options)?
+    } else {
+        TurtleParser::graph_with_options(ontology, &options.parser_options)?
+    };
+    generate_from_graph(&graph, options)
+}
+
+/// Generates the vocabulary module of the ontology at `input` (see [`generate()`]) and writes
+/// it to `output`, for use from a build script:
+///
+/// ```no_run
+/// // build.rs
+/// # use rdf_rs::codegen::{ generate_file, CodegenOptions };
+/// fn main() {
+///     let output = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("pets.rs");
+///     generate_file("ontologies/pets.ttl", &output, &CodegenOptions::default()).unwrap();
+///     println!("cargo:rerun-if-changed=ontologies/pets.ttl");
+/// }
+/// ```
+///
+/// The module is then included with
+/// `pub mod pets { include!(concat!(env!("OUT_DIR"), "/pets.rs")); }`.
+///
+/// # Errors
+///
+/// Returns a [`ParserError`] if the ontology cannot be read or generated from, or if the
+/// module cannot be written.
+pub fn generate_file<P: AsRef<Path>>(input: &str, output: P, options: &CodegenOptions) -> Parsed<()> {
+    let ontology = crate::compression::read_to_string(input)?;
+    let options = CodegenOptions {
+        parser_options: options.parser_options.for_file(input),
+        ..options.clone()
+    };
+
+    std::fs::write(output, generate(&ontology, &options)?)?;
+    Ok(())
+}
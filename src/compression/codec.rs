@@ -0,0 +1,185 @@
+use std::io::{ self, BufRead, BufReader, Read, Write };
+
+/// A compression format that documents can be read from and written to.
+///
+/// Every format other than [`Compression::None`] requires the cargo feature of the same name
+/// (`gzip`, `bzip2` or `zstd`). Reading or writing a format whose feature is disabled results in
+/// an [`io::ErrorKind::Unsupported`] error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    None,
+    Gzip,
+    Bzip2,
+    Zstd
+}
+
+impl Compression {
+    const GZIP_MAGIC: &'static [u8] = &[0x1f, 0x8b];
+    const BZIP2_MAGIC: &'static [u8] = b"BZh";
+    const ZSTD_MAGIC: &'static [u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+    /// Detects the compression of a document from its first bytes.
+    pub fn from_magic(bytes: &[u8]) -> Self {
+        if bytes.starts_with(Self::GZIP_MAGIC) {
+            Compression::Gzip
+        } else if bytes.starts_with(Self::BZIP2_MAGIC) {
+            Compression::Bzip2
+        } else if bytes.starts_with(Self::ZSTD_MAGIC) {
+            Compression::Zstd
+        } else {
+            Compression::None
+        }
+    }
+
+    /// Detects the compression of a file from its extension (`.gz`, `.bz2` or `.zst`).
+    pub fn from_path(path: &str) -> Self {
+        match path.rsplit('.').next() {
+            Some("gz") => Compression::Gzip,
+            Some("bz2") => Compression::Bzip2,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None
+        }
+    }
+
+    /// The name of the cargo feature that enables the format.
+    fn feature(self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => "gzip",
+            Compression::Bzip2 => "bzip2",
+            Compression::Zstd => "zstd"
+        }
+    }
+
+    #[allow(dead_code)]
+    fn unsupported(self) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{:?} compression requires the `{}` feature of rdf-rs", self, self.feature())
+        )
+    }
+
+    /// Wraps `reader` in a decoder for this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the feature for the format is disabled, or if the decoder cannot be
+    /// created.
+    pub fn decoder<'a, R: Read + 'a>(self, reader: R) -> io::Result<Box<dyn Read + 'a>> {
+        match self {
+            Compression::None => Ok(Box::new(reader)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Box::new(flate2::read::MultiGzDecoder::new(reader))),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Ok(Box::new(bzip2::read::MultiBzDecoder::new(reader))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Ok(Box::new(zstd::stream::read::Decoder::new(reader)?)),
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unsupported())
+        }
+    }
+
+    /// Wraps `writer` in an [`Encoder`] for this format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the feature for the format is disabled, or if the encoder cannot be
+    /// created.
+    pub fn encoder<W: Write>(self, writer: W) -> io::Result<Encoder<W>> {
+        let inner = match self {
+            Compression::None => Inner::None(writer),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Inner::Gzip(flate2::write::GzEncoder::new(writer, flate2::Compression::default())),
+            #[cfg(feature = "bzip2")]
+            Compression::Bzip2 => Inner::Bzip2(bzip2::write::BzEncoder::new(writer, bzip2::Compression::default())),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Inner::Zstd(zstd::stream::write::Encoder::new(writer, 0)?),
+            #[allow(unreachable_patterns)]
+            _ => return Err(self.unsupported())
+        };
+
+        Ok(Encoder(inner))
+    }
+}
+
+enum Inner<W: Write> {
+    None(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "bzip2")]
+    Bzip2(bzip2::write::BzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::stream::write::Encoder<'static, W>)
+}
+
+/// A writer that compresses what is written to it, created with [`Compression::encoder()`].
+///
+/// [`Encoder::finish()`] must be called once everything is written, so that the end of the
+/// compressed stream is written as well.
+pub struct Encoder<W: Write>(Inner<W>);
+
+impl<W: Write> Encoder<W> {
+    /// Writes the end of the compressed stream and returns the underlying writer.
+    pub fn finish(self) -> io::Result<W> {
+        match self.0 {
+            Inner::None(mut w) => {
+                w.flush()?;
+                Ok(w)
+            },
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(e) => e.finish(),
+            #[cfg(feature = "bzip2")]
+            Inner::Bzip2(e) => e.finish(),
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(e) => e.finish()
+        }
+    }
+
+    fn get(&mut self) -> &mut dyn Write {
+        match &mut self.0 {
+            Inner::None(w) => w,
+            #[cfg(feature = "gzip")]
+            Inner::Gzip(e) => e,
+            #[cfg(feature = "bzip2")]
+            Inner::Bzip2(e) => e,
+            #[cfg(feature = "zstd")]
+            Inner::Zstd(e) => e
+        }
+    }
+}
+
+impl<W: Write> Write for Encoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.get().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.get().flush()
+    }
+}
+
+/// Wraps `reader` in the decoder for the compression detected from its first bytes.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read, or if it is compressed with a format whose
+/// feature is disabled.
+pub fn decompress<'a, R: Read + 'a>(reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    let mut reader = BufReader::new(reader);
+    let compression = Compression::from_magic(reader.fill_buf()?);
+
+    compression.decoder(reader)
+}
+
+/// Reads a whole file to a string, decompressing it if its first bytes are those of a gzip,
+/// bzip2 or zstd stream.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or decompressed, or if it is not UTF-8.
+pub fn read_to_string(path: &str) -> io::Result<String> {
+    let mut s = String::new();
+    decompress(std::fs::File::open(path)?)?.read_to_string(&mut s)?;
+
+    Ok(s)
+}
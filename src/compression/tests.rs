@@ -0,0 +1,82 @@
+#![allow(dead_code, unused_imports)]
+use std::io::Read;
+
+use crate::compression::{ self, Compression };
+use crate::core::*;
+use crate::parsing::{ BaseParser, TurtleParser };
+use crate::serializing::{ BaseSerializer, TurtleSerializer, NTriplesSerializer, SerializerError };
+
+type TestReturn = Result<(), SerializerError>;
+
+// Compresses the graph, then detects the format of the output and parses it back
+fn round_trip(compression: Compression) -> TestReturn {
+    let graph = TurtleParser::from_file("./test_data/simple.ttl")?;
+
+    let mut compressed = Vec::new();
+    TurtleSerializer::write_compressed(&graph, &mut compressed, compression)?;
+    assert_eq!(Compression::from_magic(&compressed), compression);
+
+    let mut turtle = String::new();
+    compression::decompress(compressed.as_slice())?.read_to_string(&mut turtle)?;
+    assert_eq!(TurtleParser::graph(&turtle)?.triples, graph.triples);
+
+    Ok(())
+}
+
+#[test]
+fn can_detect_compression() {
+    assert_eq!(Compression::from_path("dump.ttl.gz"), Compression::Gzip);
+    assert_eq!(Compression::from_path("dump.nt.bz2"), Compression::Bzip2);
+    assert_eq!(Compression::from_path("dump.nt.zst"), Compression::Zstd);
+    assert_eq!(Compression::from_path("dump.ttl"), Compression::None);
+
+    assert_eq!(Compression::from_magic(&[0x1f, 0x8b, 0x08]), Compression::Gzip);
+    assert_eq!(Compression::from_magic(b"BZh91AY"), Compression::Bzip2);
+    assert_eq!(Compression::from_magic(&[0x28, 0xb5, 0x2f, 0xfd]), Compression::Zstd);
+    assert_eq!(Compression::from_magic(b"@prefix"), Compression::None);
+}
+
+#[test]
+fn can_read_and_write_uncompressed() -> TestReturn {
+    round_trip(Compression::None)
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn can_read_and_write_gzip() -> TestReturn {
+    round_trip(Compression::Gzip)?;
+
+    // Files are compressed according to their extension and detected by their magic bytes
+    let path = std::env::temp_dir().join(format!("rdf-rs-{}.ttl.gz", std::process::id()));
+    let path = path.to_str().unwrap_or_default();
+
+    let graph = TurtleParser::from_file("./test_data/simple.ttl")?;
+    TurtleSerializer::to_file(&graph, path)?;
+    let parsed = TurtleParser::from_file(path);
+    std::fs::remove_file(path)?;
+
+    assert_eq!(parsed?.triples, graph.triples);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "bzip2")]
+fn can_read_and_write_bzip2() -> TestReturn {
+    round_trip(Compression::Bzip2)
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn can_read_and_write_zstd() -> TestReturn {
+    round_trip(Compression::Zstd)
+}
+
+#[test]
+#[cfg(not(feature = "gzip"))]
+fn cannot_read_disabled_compression() {
+    let gzip: &[u8] = &[0x1f, 0x8b, 0x08, 0x00];
+
+    assert!(compression::decompress(gzip).is_err());
+    assert!(Compression::Gzip.encoder(Vec::new()).is_err());
+}
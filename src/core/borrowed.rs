@@ -0,0 +1,139 @@
+use crate::core::{ Uri, Literal, Object, Resource, Relationship, Triple, language, uri::UriType };
+use crate::vocab::rdf;
+
+/// A [`Uri`] that borrows its parts from the parsed input instead of owning them.
+///
+/// Obtained from the [streaming parser](crate::parsing::TurtleParser::stream) or from
+/// [`Uri::as_uri_ref()`], and turned back into a [`Uri`] with `.into()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UriRef<'a> {
+    pub prefix: &'a str,
+    pub name: &'a str,
+    pub uri_type: UriType
+}
+
+impl<'a> UriRef<'a> {
+    pub fn new(prefix: &'a str, name: &'a str, uri_type: UriType) -> Self {
+        Self { prefix, name, uri_type }
+    }
+
+    /// Splits a Turtle IRI, prefixed name or blank node label into its parts without copying
+    /// them. Returns [`None`] if `u` is not a URI.
+    pub fn parse(u: &'a str) -> Option<Self> {
+        use crate::core::uri::matches;
+
+        let full_url = &matches::FULL_URL;
+        let relative_url = &matches::RELATIVE_URL;
+        let prefixed = &matches::PREFIXED;
+        let empty_prefix = &matches::EMPTY_PREFIX;
+
+        // If u is in the form <http://[valid url]/foo> or <http://[valid url]#foo>
+        let uri = if let Some(caps) = full_url.captures(u) {
+            Self::new(caps.get(1)?.as_str(), caps.get(2)?.as_str(), UriType::Full)
+        // If u is in the form <#foo>
+        } else if let Some(caps) = relative_url.captures(u) {
+            Self::new("", caps.get(1)?.as_str(), UriType::Relative)
+        // If u is in the form prefix:foo
+        } else if let Some(caps) = prefixed.captures(u) {
+            Self::new(caps.get(1)?.as_str(), caps.get(2)?.as_str(), UriType::Prefixed)
+        // If u is in the form :foo
+        } else if let Some(caps) = empty_prefix.captures(u) {
+            Self::new("", caps.get(1)?.as_str(), UriType::PrefixedWithBase)
+        // If u is the identity relationship
+        } else if u == "a" {
+            Self::new(rdf::TYPE.prefix, rdf::TYPE.name, UriType::Prefixed)
+        } else {
+            return None;
+        };
+
+        Some(uri)
+    }
+}
+
+impl std::fmt::Display for UriRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.prefix, self.name)
+    }
+}
+
+impl From<UriRef<'_>> for Uri {
+    fn from(u: UriRef<'_>) -> Self {
+        Uri::new(u.prefix, u.name, u.uri_type)
+    }
+}
+
+/// A [`Literal`] that borrows its lexical form, datatype and language from the parsed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LiteralRef<'a> {
+    pub value: &'a str,
+    pub datatype: UriRef<'a>,
+    /// The language tag as written. Converting to a [`Literal`] normalizes its case like the
+    /// regular parser does.
+    pub language: Option<&'a str>
+}
+
+impl std::fmt::Display for LiteralRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(language) = &self.language {
+            write!(f, "{}^^{}@{}", self.value, self.datatype, language)
+        } else {
+            write!(f, "{}^^{}", self.value, self.datatype)
+        }
+    }
+}
+
+impl From<LiteralRef<'_>> for Literal {
+    fn from(l: LiteralRef<'_>) -> Self {
+        Literal {
+            value: l.value.into(),
+            datatype: l.datatype.into(),
+            language: l.language.map(language::normalize)
+        }
+    }
+}
+
+/// An [`Object`] that borrows from the parsed input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ObjectRef<'a> {
+    Literal(LiteralRef<'a>),
+    Resource(UriRef<'a>)
+}
+
+impl std::fmt::Display for ObjectRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            ObjectRef::Literal(literal) => write!(f, "{}", literal),
+            ObjectRef::Resource(resource) => write!(f, "{}", resource)
+        }
+    }
+}
+
+impl From<ObjectRef<'_>> for Object {
+    fn from(o: ObjectRef<'_>) -> Self {
+        match o {
+            ObjectRef::Literal(literal) => Object::Literal(literal.into()),
+            ObjectRef::Resource(resource) => Object::Resource(resource.into())
+        }
+    }
+}
+
+/// A [`Triple`] whose terms borrow from the parsed input. Cheap to copy, and converted into an
+/// owned [`Triple`] with `.into()` once it needs to outlive the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TripleRef<'a> {
+    pub subject: UriRef<'a>,
+    pub predicate: UriRef<'a>,
+    pub object: ObjectRef<'a>
+}
+
+impl std::fmt::Display for TripleRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} .", self.subject, self.predicate, self.object)
+    }
+}
+
+impl From<TripleRef<'_>> for Triple {
+    fn from(t: TripleRef<'_>) -> Self {
+        (Resource(t.subject.into()), Relationship(t.predicate.into()), Object::from(t.object)).into()
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use crate::core::{ Graph, Triple, Resource, Relationship, Object, Uri, uri::UriType };
+use crate::core::merge::blank_labels;
+use crate::vocab::{ rdf, VocabTerm };
+
+/// The classes of RDF containers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContainerKind {
+    /// `rdf:Bag`, whose members are unordered.
+    Bag,
+    /// `rdf:Seq`, whose members are ordered.
+    Seq,
+    /// `rdf:Alt`, whose members are alternatives, the first one being the default.
+    Alt
+}
+
+impl ContainerKind {
+    /// The local name of the class in the rdf namespace.
+    pub fn name(&self) -> &'static str {
+        self.term().name
+    }
+
+    /// The class, such as `rdf:Bag`.
+    pub fn term(&self) -> VocabTerm {
+        match self {
+            ContainerKind::Bag => rdf::BAG,
+            ContainerKind::Seq => rdf::SEQ,
+            ContainerKind::Alt => rdf::ALT
+        }
+    }
+}
+
+/// Returns `n` if `u` is the container membership property `rdf:_n`, written with the `rdf`
+/// prefix or in full.
+pub(crate) fn membership_index(u: &Uri) -> Option<usize> {
+    let namespace = match u.uri_type {
+        UriType::Full => rdf::NAMESPACE,
+        UriType::Prefixed => rdf::PREFIX,
+        _ => return None
+    };
+
+    // The parser may split an IRI anywhere, so the parts are read as a whole
+    format!("{}{}", u.prefix, u.name).strip_prefix(namespace)?
+        .strip_prefix('_')
+        .filter(|n| !n.starts_with('0'))
+        .and_then(|n| n.parse().ok())
+}
+
+impl Graph {
+    // The membership triples of a container, ordered by their index, and then as inserted
+    fn memberships(&self, container: &Uri) -> Vec<(usize, Triple)> {
+        let container = self.expanded_uri(container);
+
+        let mut memberships: Vec<(usize, Triple)> = self.triples.iter()
+            .filter(|t| self.expanded_uri(&t.subject.0) == container)
+            .filter_map(|t| membership_index(&self.expanded_uri(&t.predicate.0)).map(|n| (n, t.clone())))
+            .collect();
+        memberships.sort_by_key(|(n, _)| *n);
+
+        memberships
+    }
+
+    // Replaces the membership triples of a container by `rdf:_1` to `rdf:_n` for the members,
+    // keeping the locations of the members that were already in the container
+    fn set_members(&mut self, container: &Uri, old: Vec<Triple>, members: Vec<(Object, Option<Triple>)>) {
+        for t in &old {
+            self.triples.remove(t);
+        }
+
+        let mut moved = (Vec::new(), Vec::new());
+        for (i, (member, previous)) in members.into_iter().enumerate() {
+            let t: Triple = (Resource(container.clone()), Relationship(self.rdf(&format!("_{}", i + 1))), member).into();
+
+            if let Some(previous) = previous {
+                moved.0.push(previous);
+                moved.1.push(t.clone());
+            }
+            self.triples.insert(t);
+        }
+
+        for t in old.iter().filter(|t| !moved.0.contains(t)) {
+            self.locations.remove(t);
+        }
+        self.locations.rekey(&moved.0, &moved.1);
+    }
+
+    /// Returns the class of the container `container`, if it has the type `rdf:Bag`, `rdf:Seq`
+    /// or `rdf:Alt`.
+    pub fn container_kind(&self, container: &Uri) -> Option<ContainerKind> {
+        let container = self.expanded_uri(container);
+        self.triples.iter()
+            .map(|t| self.expanded(t))
+            .filter(|t| t.subject.0 == container && rdf::TYPE.matches(&t.predicate.0))
+            .find_map(|t| {
+                [ContainerKind::Bag, ContainerKind::Seq, ContainerKind::Alt].iter().copied().find(|k| k.term().matches_object(&t.object))
+            })
+    }
+
+    /// Returns the members of a container (the objects of its `rdf:_1`, `rdf:_2`... properties)
+    /// ordered by their index. Missing indices are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # use rdf_rs::core::ContainerKind;
+    /// # fn main() -> Result<(), ParserError> {
+    /// let mut graph = TurtleParser::graph(r#"
+    ///     ex:steps a rdf:Seq ; rdf:_2 "mix" ; rdf:_1 "pour" ; rdf:_4 "bake" .
+    /// "#)?;
+    /// let steps = graph.triples[0].subject.0.clone();
+    /// assert_eq!(graph.container_kind(&steps), Some(ContainerKind::Seq));
+    ///
+    /// graph.insert_container_member(&steps, 3, TurtleParser::object("\"wait\"")?);
+    /// let members: Vec<String> = graph.container_members(&steps).iter()
+    ///     .map(|m| m.literal().unwrap().lexical_form().to_string())
+    ///     .collect();
+    /// assert_eq!(members, vec!["pour", "mix", "wait", "bake"]);
+    ///
+    /// // The members are renumbered without gaps
+    /// assert!(graph.triples.iter().any(|t| t.to_string() == "ex:steps rdf:_4 \"bake\"^^xsd:string ."));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn container_members(&self, container: &Uri) -> Vec<Object> {
+        self.memberships(container).into_iter().map(|(_, t)| t.object).collect()
+    }
+
+    /// Adds a new blank node of class `kind` to the graph, with `members` as its members, and
+    /// returns it.
+    pub fn insert_container<I: IntoIterator<Item = Object>>(&mut self, kind: ContainerKind, members: I) -> Uri {
+        let mut used = HashSet::new();
+        self.triples.iter().for_each(|t| blank_labels(t, &mut used));
+
+        let label = (1..).map(|n| format!("container{}", n)).find(|l| !used.contains(l.as_str())).unwrap();
+        let container = Uri::new("_:", &label, UriType::Prefixed);
+
+        let class = self.rdf(kind.name());
+        self.triples.insert((Resource(container.clone()), Relationship(self.rdf("type")), Object::Resource(class)).into());
+        self.set_members(&container, Vec::new(), members.into_iter().map(|m| (m, None)).collect());
+
+        container
+    }
+
+    /// Inserts `member` at the 1-based position `index` of a container (or at its end, if
+    /// `index` is past it), and renumbers the members after it.
+    pub fn insert_container_member(&mut self, container: &Uri, index: usize, member: Object) {
+        let old: Vec<Triple> = self.memberships(container).into_iter().map(|(_, t)| t).collect();
+
+        let mut members: Vec<(Object, Option<Triple>)> = old.iter().map(|t| (t.object.clone(), Some(t.clone()))).collect();
+        members.insert(index.clamp(1, members.len() + 1) - 1, (member, None));
+
+        self.set_members(container, old, members);
+    }
+
+    /// Adds `member` at the end of a container.
+    pub fn push_container_member(&mut self, container: &Uri, member: Object) {
+        self.insert_container_member(container, usize::MAX, member);
+    }
+
+    /// Removes the member at the 1-based position `index` of a container, renumbers the members
+    /// after it, and returns it.
+    pub fn remove_container_member(&mut self, container: &Uri, index: usize) -> Option<Object> {
+        let old: Vec<Triple> = self.memberships(container).into_iter().map(|(_, t)| t).collect();
+        if index == 0 || index > old.len() {
+            return None;
+        }
+
+        let mut members: Vec<(Object, Option<Triple>)> = old.iter().map(|t| (t.object.clone(), Some(t.clone()))).collect();
+        let (removed, _) = members.remove(index - 1);
+
+        self.set_members(container, old, members);
+        Some(removed)
+    }
+
+    /// Renumbers the members of a container from `rdf:_1`, without gaps, keeping their order.
+    pub fn renumber_container(&mut self, container: &Uri) {
+        let old: Vec<Triple> = self.memberships(container).into_iter().map(|(_, t)| t).collect();
+        let members = old.iter().map(|t| (t.object.clone(), Some(t.clone()))).collect();
+
+        self.set_members(container, old, members);
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::{ Arc, RwLock };
+
+use crate::core::{ Term, TermTriple, Subject, Graph };
+use crate::parsing::{ Parsed, ParserError };
+
+/// The compact identifier of a [`Term`] within a [`TermDictionary`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TermId(pub u32);
+
+/// A dictionary that stores each distinct [`Term`] once and maps it to a [`TermId`].
+///
+/// Ids are handed out in insertion order and never change, so a dictionary can be shared by
+/// several graphs (see [`SharedDictionary`]) whose id triples can then be compared directly.
+#[derive(Debug, Default)]
+pub struct TermDictionary {
+    terms: Vec<Arc<Term>>,
+    ids: HashMap<Arc<Term>, TermId>
+}
+
+/// A [`TermDictionary`] that can be shared across graphs and threads.
+pub type SharedDictionary = Arc<RwLock<TermDictionary>>;
+
+impl TermDictionary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty dictionary that can be shared across graphs.
+    pub fn shared() -> SharedDictionary {
+        Arc::new(RwLock::new(Self::new()))
+    }
+
+    /// Returns the id of `term`, adding it to the dictionary if it is not already in it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dictionary already holds `u32::MAX` terms.
+    pub fn intern(&mut self, term: &Term) -> TermId {
+        if let Some(id) = self.ids.get(term) {
+            return *id;
+        }
+
+        let id = TermId(u32::try_from(self.terms.len()).expect("Term dictionary is full"));
+        let term = Arc::new(term.clone());
+
+        self.terms.push(term.clone());
+        self.ids.insert(term, id);
+
+        id
+    }
+
+    /// Returns the id of `term` without adding it to the dictionary.
+    pub fn id(&self, term: &Term) -> Option<TermId> {
+        self.ids.get(term).copied()
+    }
+
+    /// Returns the term with the given id.
+    pub fn term(&self, id: TermId) -> Option<&Arc<Term>> {
+        self.terms.get(id.0 as usize)
+    }
+
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+/// A triple of [`TermId`]s, resolved against the dictionary of its graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IdTriple {
+    pub subject: TermId,
+    pub predicate: TermId,
+    pub object: TermId
+}
+
+/// A graph that stores its triples as [`IdTriple`]s, with each distinct term stored once in a
+/// (possibly shared) [`TermDictionary`].
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::{ InternedGraph, TermDictionary };
+/// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+/// # fn main() -> Result<(), ParserError> {
+/// let dictionary = TermDictionary::shared();
+///
+/// let a = TurtleParser::graph("@prefix ex: <http://example.com/> . ex:John a ex:Person .")?;
+/// let b = TurtleParser::graph("@prefix ex: <http://example.com/> . ex:Jane a ex:Person .")?;
+/// let a = InternedGraph::from_graph(&a, &dictionary)?;
+/// let b = InternedGraph::from_graph(&b, &dictionary)?;
+///
+/// // Both graphs share the ids of `rdf:type` and `ex:Person`
+/// assert_eq!(a.triples[0].object, b.triples[0].object);
+/// assert_eq!(dictionary.read().unwrap().len(), 4);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct InternedGraph {
+    pub dictionary: SharedDictionary,
+    pub triples: Vec<IdTriple>
+}
+
+impl InternedGraph {
+    /// Creates an empty graph whose terms are interned in `dictionary`.
+    pub fn new(dictionary: &SharedDictionary) -> Self {
+        Self {
+            dictionary: dictionary.clone(),
+            triples: Vec::new()
+        }
+    }
+
+    /// Interns the triples of `g`, after resolving them with [`Graph::term_triples()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the triples of `g` cannot be resolved, or if the dictionary
+    /// is poisoned.
+    pub fn from_graph(g: &Graph, dictionary: &SharedDictionary) -> Parsed<Self> {
+        let mut graph = Self::new(dictionary);
+        graph.extend(g.term_triples()?)?;
+
+        Ok(graph)
+    }
+
+    /// Interns and appends the given triples.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the dictionary is poisoned.
+    pub fn extend<I: IntoIterator<Item = TermTriple>>(&mut self, triples: I) -> Parsed<()> {
+        let mut dictionary = self.dictionary.write()
+            .map_err(|_| ParserError("The term dictionary is poisoned".into()))?;
+
+        self.triples.extend(triples.into_iter().map(|t| IdTriple {
+            subject: dictionary.intern(&t.subject.into()),
+            predicate: dictionary.intern(&t.predicate.into()),
+            object: dictionary.intern(&t.object)
+        }));
+
+        Ok(())
+    }
+
+    /// Returns the term with the given id. Only clones the pointer to the term.
+    pub fn term(&self, id: TermId) -> Option<Arc<Term>> {
+        self.dictionary.read().ok()?.term(id).cloned()
+    }
+
+    /// Resolves the triples of the graph back to [`TermTriple`]s.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the dictionary is poisoned, or if an id does not belong to
+    /// it or is not allowed in its position.
+    pub fn term_triples(&self) -> Parsed<Vec<TermTriple>> {
+        let dictionary = self.dictionary.read()
+            .map_err(|_| ParserError("The term dictionary is poisoned".into()))?;
+
+        let term = |id: TermId| dictionary.term(id)
+            .map(|t| t.as_ref().clone())
+            .ok_or_else(|| ParserError(format!("Unknown term id: {}", id.0)));
+
+        self.triples.iter()
+            .map(|t| {
+                let subject = match term(t.subject)? {
+                    Term::NamedNode(node) => Subject::NamedNode(node),
+                    Term::BlankNode(node) => Subject::BlankNode(node),
+                    other => return Err(ParserError(format!("Invalid subject: {}", other)))
+                };
+                let predicate = match term(t.predicate)? {
+                    Term::NamedNode(node) => node,
+                    other => return Err(ParserError(format!("Invalid predicate: {}", other)))
+                };
+
+                Ok(TermTriple { subject, predicate, object: term(t.object)? })
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.triples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triples.is_empty()
+    }
+}
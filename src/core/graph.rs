@@ -1,106 +1,115 @@
-use std::collections::HashMap;
-use std::iter::FromIterator;
+use std::cell::RefCell;
 
-use crate::core::{ Resource, Relationship, Object, Triple, Uri, uri::UriType };
-use crate::querying::QueryBuilder;
-use crate::reasoning::{ RDFSReasoner, BaseReasoner };
+use crate::core::{ Resource, Relationship, Object, Triple, TripleStore, Uri, PrefixMap, SourceLocation, SourceMap, uri::UriType };
+use crate::parsing::{ ParserError, Parsed };
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Graph {
     pub base_prefix: Option<String>,
-    pub prefixes: HashMap<String, String>,
-    pub triples: Vec<Triple>
+    pub prefixes: PrefixMap,
+    /// The triples of the graph, indexed for pattern matching. This was a `Vec<Triple>` before the
+    /// [`TripleStore`] was introduced; see its documentation for how to migrate.
+    pub triples: TripleStore,
+    /// Where each triple was parsed from, if the parser was asked to track locations
+    /// (see [`ParserOptions::track_locations`](crate::parsing::ParserOptions::track_locations)).
+    pub locations: SourceMap
 }
 
 impl Graph {
-    /// Expands all the URIs to have full paths for each resource.
-    pub fn apply_metadata(&mut self) {
-        let base = self.base_prefix.clone().unwrap_or(String::new());
-        let prefixes = self.prefixes.clone();
+    /// Returns the statements `triple` was parsed from.
+    pub fn locations_of(&self, triple: &Triple) -> &[SourceLocation] {
+        self.locations.get(triple)
+    }
 
-        self.triples.iter_mut()
-            .for_each(|t| {
-                t.apply_graph_prefixes(&base, &prefixes);
-            });
+    /// Returns the triples that match a pattern, using the indexes of the graph's
+    /// [`TripleStore`], in the order of the index. Each term that is [`None`] matches anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     ex:John ex:knows ex:Jane , ex:Jim .
+    ///     ex:Jane ex:knows ex:Jim .
+    /// "#)?;
+    /// let jim = TurtleParser::object("ex:Jim")?;
+    ///
+    /// // ?s ex:knows ex:Jim
+    /// let knows_jim = graph.triples_matching(None, Some(&graph.triples[0].predicate), Some(&jim));
+    /// assert_eq!(knows_jim.count(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn triples_matching(&self, s: Option<&Resource>, p: Option<&Relationship>, o: Option<&Object>) -> impl Iterator<Item = &Triple> {
+        self.triples.matching(s, p, o)
     }
 
-    pub fn apply_prefixes(&mut self) {
-        let base = self.base_prefix.clone().unwrap_or(String::new());
+    /// Expands all the URIs to have full paths for each resource: prefixed names with
+    /// [`Graph::prefixes`] and relative IRIs with the base. Prefixed blank nodes become
+    /// [`UriType::BlankNode`]s. The datatypes of literals are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] listing the prefixes that are used without being defined, in
+    /// which case the graph is left unchanged.
+    pub fn apply_metadata(&mut self) -> Parsed<()> {
+        let base = self.base_prefix.clone().unwrap_or_default();
+        let undefined = RefCell::new(Vec::new());
 
-        let prefixes: Vec<String> = self.prefixes.keys().cloned().collect();
-        let expanded_prefixes: Vec<String> = self.prefixes.values().cloned().collect();
+        let expanded: Vec<Triple> = self.triples.iter()
+            .map(|t| map_resources(t, &|u: &Uri| match u.uri_type {
+                UriType::PrefixedWithBase | UriType::Relative => Uri::new(&base, &u.name, UriType::Full),
+                UriType::Prefixed if u.prefix.starts_with('_') => Uri::new(&u.prefix, &u.name, UriType::BlankNode),
+                _ => self.prefixes.expand_uri(u).unwrap_or_else(|_| {
+                    undefined.borrow_mut().push(u.prefix.clone());
+                    u.clone()
+                })
+            }))
+            .collect();
 
-        // reverse prefix keys/values for later use
-        let prefix_lookup: HashMap<String, String> = HashMap::from_iter(expanded_prefixes.iter().cloned().zip(prefixes).into_iter());
+        let mut undefined = undefined.into_inner();
+        if !undefined.is_empty() {
+            undefined.sort();
+            undefined.dedup();
+            return Err(ParserError(format!("Use of prefix without first defining it: {}", undefined.join(", "))));
+        }
 
-        self.triples.iter_mut()
-            .for_each(|t| {
-                let Resource(subject) = &t.subject;
-                if subject.uri_type != UriType::Prefixed {
-                    if expanded_prefixes.contains(&subject.prefix) {
-                        t.subject = Resource(Uri {
-                            prefix: prefix_lookup.get(&subject.prefix).unwrap().to_string(),
-                            name: subject.name.clone(),
-                            uri_type: UriType::Prefixed
-                        })
-                    } else if subject.uri_type == UriType::PrefixedWithBase {
-                        t.subject = Resource(Uri {
-                            prefix: base.clone(),
-                            name: subject.name.clone(),
-                            uri_type: UriType::Prefixed
-                        })
-                    }
-                }
-                
-                let Relationship(predicate) = &t.predicate;
-                if predicate.uri_type != UriType::Prefixed {
-                    if expanded_prefixes.contains(&predicate.prefix) {
-                        t.predicate = Relationship(Uri {
-                            prefix: prefix_lookup.get(&predicate.prefix).unwrap().to_string(),
-                            name: predicate.name.clone(),
-                            uri_type: UriType::Prefixed
-                        })
-                    } else if predicate.uri_type == UriType::PrefixedWithBase {
-                        t.predicate = Relationship(Uri {
-                            prefix: base.clone(),
-                            name: predicate.name.clone(),
-                            uri_type: UriType::Prefixed
-                        })
-                    }
-                }
+        // Triples that became equal once expanded are merged, along with their locations
+        self.locations.rekey(&self.triples, &expanded);
+        self.triples = expanded.into();
 
-                if t.object.is_resource() {
-                    let object = t.object.resource().unwrap();
-                    
-                    if object.uri_type != UriType::Prefixed {
-                        if expanded_prefixes.contains(&object.prefix) {
-                            t.object = Object::Resource(Uri {
-                                prefix: prefix_lookup.get(&object.prefix).unwrap().to_string(),
-                                name: object.name.clone(),
-                                uri_type: UriType::Prefixed
-                            })
-                        } else if object.uri_type == UriType::PrefixedWithBase {
-                            t.object = Object::Resource(Uri {
-                                prefix: base.clone(),
-                                name: object.name.clone(),
-                                uri_type: UriType::Prefixed
-                            })
-                        }
-                    }
-                }
-            });
+        Ok(())
     }
 
-    pub fn start_query(&self, inferrence_depth: usize) -> QueryBuilder {
-        // Normalize triple uri format to UriType::Prefixed
-        let mut graph = self.clone();
-        graph.apply_prefixes();
+    /// Compacts the full IRIs of the graph into prefixed names, using the longest namespace of
+    /// [`Graph::prefixes`] that leaves a valid local name (see [`PrefixMap::compact()`]), and
+    /// prefixes the names relative to the base with it.
+    pub fn apply_prefixes(&mut self) {
+        let base = self.base_prefix.clone().unwrap_or_default();
 
-        // Add inferred triples
-        let mut new_triples = RDFSReasoner::get_inferred_triples(graph.triples.clone(), inferrence_depth);
-        graph.triples.append(&mut new_triples);
+        let compacted: Vec<Triple> = self.triples.iter()
+            .map(|t| map_resources(t, &|u: &Uri| match u.uri_type {
+                UriType::Full => self.prefixes.compact_uri(u),
+                UriType::PrefixedWithBase => Uri::new(&base, &u.name, UriType::Prefixed),
+                _ => u.clone()
+            }))
+            .collect();
 
-        QueryBuilder::start(graph.triples)
+        // Triples that became equal once compacted are merged, along with their locations
+        self.locations.rekey(&self.triples, &compacted);
+        self.triples = compacted.into();
     }
 }
 
+// Rewrites the subject, predicate and resource object of a triple, and those of the triple
+// terms within it
+fn map_resources<F: Fn(&Uri) -> Uri>(t: &Triple, f: &F) -> Triple {
+    let object = match &t.object {
+        Object::Resource(u) => Object::Resource(f(u)),
+        Object::Triple(t) => Object::Triple(Box::new(map_resources(t, f))),
+        literal => literal.clone()
+    };
+
+    (Resource(f(&t.subject.0)), Relationship(f(&t.predicate.0)), object).into()
+}
@@ -0,0 +1,426 @@
+use std::collections::{ HashMap, HashSet };
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{ Hash, Hasher };
+
+use crate::core::{ Graph, Triple, Uri, uri::UriType };
+use crate::core::merge::{ is_blank_node, map_uris, blank_labels };
+
+// The triples of a graph, and for each of its blank nodes the triples it appears in
+struct BlankNodes {
+    triples: Vec<Triple>,
+    labels: Vec<String>,
+    index: HashMap<String, usize>,
+    adjacent: Vec<Vec<usize>>
+}
+
+impl BlankNodes {
+    fn new(graph: &Graph) -> Self {
+        let mut seen = HashSet::new();
+        let triples: Vec<Triple> = graph.triples.iter()
+            .map(|t| graph.expanded(t))
+            .filter(|t| seen.insert(t.clone()))
+            .collect();
+
+        let mut labels = Vec::new();
+        let mut index = HashMap::new();
+        let mut adjacent: Vec<Vec<usize>> = Vec::new();
+
+        for (i, t) in triples.iter().enumerate() {
+            let mut found = HashSet::new();
+            blank_labels(t, &mut found);
+
+            for label in found {
+                let node = *index.entry(label.to_string()).or_insert_with(|| {
+                    labels.push(label.to_string());
+                    adjacent.push(Vec::new());
+                    labels.len() - 1
+                });
+                adjacent[node].push(i);
+            }
+        }
+
+        Self { triples, labels, index, adjacent }
+    }
+
+    fn is_ground(&self, t: &Triple) -> bool {
+        let mut found = HashSet::new();
+        blank_labels(t, &mut found);
+        found.is_empty()
+    }
+
+    // The hash of a triple with the blank node `me` replaced by a marker, and every other blank
+    // node by its color
+    fn signature(&self, t: &Triple, me: &str, colors: &[u64]) -> u64 {
+        let t = map_uris(t, &|u: &Uri| if is_blank_node(u) {
+            let name = if u.name == me { "self".to_string() } else { format!("{:x}", colors[self.index[&u.name]]) };
+            Uri::new("_:", &name, UriType::BlankNode)
+        } else {
+            u.clone()
+        });
+
+        let mut hasher = DefaultHasher::new();
+        t.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn refine(&self, colors: &[u64]) -> Vec<u64> {
+        self.labels.iter()
+            .enumerate()
+            .map(|(node, label)| {
+                let mut signatures: Vec<u64> = self.adjacent[node].iter()
+                    .map(|&i| self.signature(&self.triples[i], label, colors))
+                    .collect();
+                signatures.sort_unstable();
+
+                let mut hasher = DefaultHasher::new();
+                (colors[node], signatures).hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect()
+    }
+
+    // The triple with the blank nodes of this graph renamed to those of `other`, if they are all
+    // mapped
+    fn mapped(&self, t: &Triple, mapping: &[Option<usize>], other: &BlankNodes) -> Option<Triple> {
+        let mut found = HashSet::new();
+        blank_labels(t, &mut found);
+        if found.iter().any(|label| mapping[self.index[*label]].is_none()) {
+            return None;
+        }
+
+        Some(map_uris(t, &|u: &Uri| if is_blank_node(u) {
+            let label = &other.labels[mapping[self.index[&u.name]].unwrap()];
+            Uri::new(&u.prefix, label, u.uri_type)
+        } else {
+            u.clone()
+        }))
+    }
+}
+
+// Colors the blank nodes of both graphs by the triples around them, round after round, until
+// the colors stop telling more nodes apart. Nodes that a bijection could map onto each other
+// share a color; each round is finer than the previous one.
+fn color_rounds(a: &BlankNodes, b: &BlankNodes) -> Vec<(Vec<u64>, Vec<u64>)> {
+    let mut rounds = vec![(vec![0; a.labels.len()], vec![0; b.labels.len()])];
+    let mut distinct = 1;
+
+    loop {
+        let last = rounds.last().unwrap();
+        let refined = (a.refine(&last.0), b.refine(&last.1));
+        let count = refined.0.iter().chain(refined.1.iter()).collect::<HashSet<_>>().len();
+
+        rounds.push(refined);
+        if count <= distinct {
+            return rounds;
+        }
+        distinct = count;
+    }
+}
+
+fn colors(a: &BlankNodes, b: &BlankNodes) -> (Vec<u64>, Vec<u64>) {
+    color_rounds(a, b).pop().unwrap()
+}
+
+// The number of elements two sorted lists have in common
+fn shared(a: &[u64], b: &[u64]) -> usize {
+    let (mut i, mut j, mut count) = (0, 0, 0);
+
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+
+    count
+}
+
+// Maps the blank nodes of `a` in `order` onto those of `b` with the same color, backtracking
+// whenever a mapped triple is not in `b`. The search keeps a stack with, for each node mapped so
+// far, the position of the next candidate to try among the unused nodes of its color; taking a
+// candidate swaps the last unused node into its place, and backtracking swaps it back.
+fn search(a: &BlankNodes, b: &BlankNodes, colors: &(Vec<u64>, Vec<u64>), triples: &HashSet<&Triple>, order: &[usize], mapping: &mut [Option<usize>]) -> bool {
+    let mut unused: HashMap<u64, Vec<usize>> = HashMap::new();
+    for (node, color) in colors.1.iter().enumerate() {
+        unused.entry(*color).or_default().push(node);
+    }
+
+    let mut frames: Vec<usize> = vec![0];
+    while let Some(&position) = frames.last() {
+        let node = match order.get(frames.len() - 1) {
+            Some(&node) => node,
+            None => return true
+        };
+
+        let candidates = match unused.get_mut(&colors.0[node]) {
+            Some(candidates) => candidates,
+            None => return false
+        };
+
+        // Put the candidate tried last back where it was taken from
+        if let Some(previous) = mapping[node].take() {
+            candidates.push(previous);
+            let last = candidates.len() - 1;
+            candidates.swap(position - 1, last);
+        }
+
+        if position >= candidates.len() {
+            frames.pop();
+            continue;
+        }
+
+        let candidate = candidates.swap_remove(position);
+        mapping[node] = Some(candidate);
+        *frames.last_mut().unwrap() = position + 1;
+
+        let consistent = a.adjacent[node].iter()
+            .filter_map(|&i| a.mapped(&a.triples[i], mapping, b))
+            .all(|t| triples.contains(&t));
+
+        if consistent {
+            frames.push(0);
+        }
+    }
+
+    false
+}
+
+impl Graph {
+    /// Returns a bijection between the blank nodes of this graph and those of `other` that makes
+    /// both graphs contain the same triples, or [`None`] if there is none.
+    ///
+    /// Triples are compared once their prefixed names and relative IRIs are expanded, as in
+    /// [`Graph::union()`]. The keys of the map are the labels of the blank nodes of this graph.
+    pub fn isomorphism(&self, other: &Graph) -> Option<HashMap<String, String>> {
+        let (a, b) = (BlankNodes::new(self), BlankNodes::new(other));
+        if a.triples.len() != b.triples.len() || a.labels.len() != b.labels.len() {
+            return None;
+        }
+
+        let triples: HashSet<&Triple> = b.triples.iter().collect();
+        if !a.triples.iter().filter(|t| a.is_ground(t)).all(|t| triples.contains(t)) {
+            return None;
+        }
+
+        let colors = colors(&a, &b);
+        let (mut ours, mut theirs) = colors.clone();
+        ours.sort_unstable();
+        theirs.sort_unstable();
+        if ours != theirs {
+            return None;
+        }
+
+        let mut sizes: HashMap<u64, usize> = HashMap::new();
+        colors.0.iter().for_each(|c| *sizes.entry(*c).or_default() += 1);
+        let mut mapping = vec![None; a.labels.len()];
+
+        if sizes.values().all(|&size| size == 1) {
+            // Every node has a color of its own, so there is only one mapping to check
+            let theirs: HashMap<u64, usize> = colors.1.iter().enumerate().map(|(node, color)| (*color, node)).collect();
+            for (node, color) in colors.0.iter().enumerate() {
+                mapping[node] = theirs.get(color).copied();
+            }
+
+            if !a.triples.iter().all(|t| a.mapped(t, &mapping, &b).is_some_and(|t| triples.contains(&t))) {
+                return None;
+            }
+        } else {
+            // Nodes with rarer colors have fewer candidates, so they are mapped first
+            let mut order: Vec<usize> = (0..a.labels.len()).collect();
+            order.sort_by_key(|&node| (sizes[&colors.0[node]], colors.0[node]));
+
+            if !search(&a, &b, &colors, &triples, &order, &mut mapping) {
+                return None;
+            }
+        }
+
+        Some(a.labels.iter()
+            .zip(mapping)
+            .map(|(label, node)| (label.clone(), b.labels[node.unwrap()].clone()))
+            .collect())
+    }
+
+    // The expanded triples of `other`, with its blank nodes relabelled after the blank nodes of
+    // this graph they correspond to. If the graphs are isomorphic, that is the bijection between
+    // them. Otherwise nodes are paired by their colors, from the finest round of coloring that
+    // pairs them to the coarsest, then by the most triples in common, then by their labels; the
+    // nodes left get labels this graph does not use.
+    pub(crate) fn aligned(&self, other: &Graph) -> Vec<Triple> {
+        let (a, b) = (BlankNodes::new(self), BlankNodes::new(other));
+
+        let renamed: HashMap<String, String> = match self.isomorphism(other) {
+            Some(mapping) => mapping.into_iter().map(|(ours, theirs)| (theirs, ours)).collect(),
+            None => {
+                let mut pairs: Vec<Option<usize>> = vec![None; b.labels.len()];
+                let mut paired = vec![false; a.labels.len()];
+
+                for (ours, theirs) in color_rounds(&a, &b).iter().skip(1).rev() {
+                    let mut unpaired: HashMap<u64, Vec<usize>> = HashMap::new();
+                    for node in (0..b.labels.len()).rev().filter(|&node| pairs[node].is_none()) {
+                        unpaired.entry(theirs[node]).or_default().push(node);
+                    }
+
+                    for node in 0..a.labels.len() {
+                        if paired[node] {
+                            continue;
+                        }
+
+                        if let Some(candidate) = unpaired.get_mut(&ours[node]).and_then(|nodes| nodes.pop()) {
+                            pairs[candidate] = Some(node);
+                            paired[node] = true;
+                        }
+                    }
+                }
+
+                // Then by the triples they share, regardless of the blank nodes around them
+                let signatures = |nodes: &BlankNodes, node: usize| {
+                    let colors = vec![0; nodes.labels.len()];
+                    let mut signatures: Vec<u64> = nodes.adjacent[node].iter()
+                        .map(|&i| nodes.signature(&nodes.triples[i], &nodes.labels[node], &colors))
+                        .collect();
+                    signatures.sort_unstable();
+                    signatures
+                };
+
+                let ours: Vec<(usize, Vec<u64>)> = (0..a.labels.len()).filter(|&node| !paired[node]).map(|node| (node, signatures(&a, node))).collect();
+                let theirs: Vec<(usize, Vec<u64>)> = (0..b.labels.len()).filter(|&node| pairs[node].is_none()).map(|node| (node, signatures(&b, node))).collect();
+
+                let mut candidates: Vec<(usize, usize, usize)> = ours.iter()
+                    .flat_map(|(node, ours)| theirs.iter().map(move |(candidate, theirs)| (shared(ours, theirs), *node, *candidate)))
+                    .filter(|&(shared, _, _)| shared > 0)
+                    .collect();
+                candidates.sort_by(|x, y| y.0.cmp(&x.0).then((x.1, x.2).cmp(&(y.1, y.2))));
+
+                for (_, node, candidate) in candidates {
+                    if !paired[node] && pairs[candidate].is_none() {
+                        pairs[candidate] = Some(node);
+                        paired[node] = true;
+                    }
+                }
+
+                // Then by their labels
+                for (node, label) in b.labels.iter().enumerate() {
+                    if pairs[node].is_some() {
+                        continue;
+                    }
+
+                    if let Some(&same) = a.index.get(label).filter(|&&same| !paired[same]) {
+                        pairs[node] = Some(same);
+                        paired[same] = true;
+                    }
+                }
+
+                let mut taken: HashSet<String> = a.labels.iter().chain(b.labels.iter()).cloned().collect();
+                b.labels.iter()
+                    .zip(pairs)
+                    .map(|(label, pair)| match pair {
+                        Some(node) => (label.clone(), a.labels[node].clone()),
+                        None if !a.index.contains_key(label) => (label.clone(), label.clone()),
+                        None => {
+                            let fresh = (1..).map(|n| format!("{}_{}", label, n)).find(|l| !taken.contains(l)).unwrap();
+                            taken.insert(fresh.clone());
+
+                            (label.clone(), fresh)
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        b.triples.iter()
+            .map(|t| map_uris(t, &|u: &Uri| match renamed.get(&u.name) {
+                Some(label) if is_blank_node(u) => Uri::new(&u.prefix, label, u.uri_type),
+                _ => u.clone()
+            }))
+            .collect()
+    }
+
+    /// Returns whether this graph and `other` are the same up to the labels of their blank nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let a = TurtleParser::graph("_:a foaf:knows _:b . _:b foaf:name 'Bob' .")?;
+    /// let b = TurtleParser::graph("_:y foaf:name 'Bob' . _:x foaf:knows _:y .")?;
+    /// let c = TurtleParser::graph("_:y foaf:name 'Bob' . _:y foaf:knows _:x .")?;
+    ///
+    /// assert!(a.is_isomorphic(&b));
+    /// assert!(!a.is_isomorphic(&c));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_isomorphic(&self, other: &Graph) -> bool {
+        self.isomorphism(other).is_some()
+    }
+
+    /// Returns [`None`] if this graph is isomorphic to `other`, and otherwise lists the triples
+    /// only this graph has (`-`) and those only `other` has (`+`), with the blank nodes of
+    /// `other` aligned as in [`Graph::diff()`]. Used by [`assert_isomorphic!`](crate::assert_isomorphic).
+    pub fn isomorphism_diff(&self, other: &Graph) -> Option<String> {
+        if self.is_isomorphic(other) {
+            return None;
+        }
+
+        let a = BlankNodes::new(self);
+        let aligned = self.aligned(other);
+
+        let mut lines: Vec<String> = a.triples.iter()
+            .filter(|t| !aligned.contains(t))
+            .map(|t| format!("- {}", t))
+            .collect();
+        lines.extend(aligned.iter()
+            .filter(|t| !a.triples.contains(t))
+            .map(|t| format!("+ {}", t)));
+
+        if lines.is_empty() {
+            lines.push("(the graphs differ only in how their blank nodes are connected)".into());
+        }
+
+        Some(lines.join("\n"))
+    }
+}
+
+/// Asserts that two [`Graph`]s are isomorphic (see [`Graph::is_isomorphic()`]), and otherwise
+/// panics with the triples that differ between them.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::assert_isomorphic;
+/// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+/// # fn main() -> Result<(), ParserError> {
+/// let a = TurtleParser::graph("ex:John foaf:knows [ foaf:name 'Jane' ] .")?;
+/// let b = TurtleParser::graph("ex:John foaf:knows _:jane . _:jane foaf:name 'Jane' .")?;
+///
+/// assert_isomorphic!(a, b);
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_isomorphic {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if let Some(diff) = $crate::core::Graph::isomorphism_diff(left, right) {
+                    panic!("assertion failed: `left` is isomorphic to `right`\n{}", diff);
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if let Some(diff) = $crate::core::Graph::isomorphism_diff(left, right) {
+                    panic!("assertion failed: `left` is isomorphic to `right`: {}\n{}", format_args!($($arg)+), diff);
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,358 @@
+use std::str::FromStr;
+
+use crate::core::Literal;
+use crate::parsing::{ Parsed, ParserError };
+
+// The grandfathered tags of BCP 47, which do not follow its grammar but are well-formed
+const GRANDFATHERED: &[&str] = &[
+    "en-gb-oed", "i-ami", "i-bnn", "i-default", "i-enochian", "i-hak", "i-klingon", "i-lux",
+    "i-mingo", "i-navajo", "i-pwn", "i-tao", "i-tay", "i-tsu", "sgn-be-fr", "sgn-be-nl",
+    "sgn-ch-de", "art-lojban", "cel-gaulish", "no-bok", "no-nyn", "zh-guoyu", "zh-hakka",
+    "zh-min", "zh-min-nan", "zh-xiang"
+];
+
+fn is_alpha(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_digit(s: &str) -> bool {
+    s.bytes().all(|b| b.is_ascii_digit())
+}
+
+// Whether lower case subtags follow the `langtag` or `privateuse` production of RFC 5646, without
+// repeated variants or extension singletons
+fn is_well_formed(subtags: &[&str]) -> bool {
+    let private_use = |rest: &[&str]| !rest.is_empty() && rest.iter().all(|s| !s.is_empty() && s.len() <= 8);
+
+    if subtags.iter().any(|s| s.is_empty() || s.len() > 8 || !s.bytes().all(|b| b.is_ascii_alphanumeric())) {
+        return false;
+    }
+
+    if subtags[0] == "x" {
+        return private_use(&subtags[1..]);
+    }
+
+    // Language, with up to three extended language subtags after a short one
+    let mut i = 1;
+    match subtags[0].len() {
+        2 | 3 if is_alpha(subtags[0]) => {
+            while i < subtags.len() && i <= 3 && subtags[i].len() == 3 && is_alpha(subtags[i]) {
+                i += 1;
+            }
+        },
+        4..=8 if is_alpha(subtags[0]) => (),
+        _ => return false
+    }
+
+    let next = |i: usize| subtags.get(i).copied().unwrap_or("");
+
+    // Script
+    if next(i).len() == 4 && is_alpha(next(i)) {
+        i += 1;
+    }
+
+    // Region
+    if (next(i).len() == 2 && is_alpha(next(i))) || (next(i).len() == 3 && is_digit(next(i))) {
+        i += 1;
+    }
+
+    // Variants
+    let mut variants = Vec::new();
+    while next(i).len() >= 5 || (next(i).len() == 4 && next(i).as_bytes()[0].is_ascii_digit()) {
+        if variants.contains(&next(i)) {
+            return false;
+        }
+
+        variants.push(next(i));
+        i += 1;
+    }
+
+    // Extensions, each a singleton followed by at least one subtag of two to eight characters
+    let mut singletons = Vec::new();
+    while next(i).len() == 1 && next(i) != "x" {
+        if singletons.contains(&next(i)) {
+            return false;
+        }
+
+        singletons.push(next(i));
+        i += 1;
+
+        let start = i;
+        while next(i).len() >= 2 {
+            i += 1;
+        }
+
+        if i == start {
+            return false;
+        }
+    }
+
+    if next(i) == "x" {
+        return private_use(&subtags[i + 1..]);
+    }
+
+    i == subtags.len()
+}
+
+/// A well-formed BCP 47 language tag, such as `en`, `zh-Hant-TW` or `en-US-x-twain`.
+///
+/// Language tags are case-insensitive. A [`LanguageTag`] is kept in the case BCP 47 recommends:
+/// lower case, except for scripts in title case and regions in upper case.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::LanguageTag;
+/// # use rdf_rs::parsing::ParserError;
+/// # fn main() -> Result<(), ParserError> {
+/// let tag: LanguageTag = "EN-latn-us-X-TWAIN".parse()?;
+///
+/// assert_eq!(tag.as_str(), "en-Latn-US-x-twain");
+/// assert_eq!(tag.primary_language(), "en");
+/// assert!("en-".parse::<LanguageTag>().is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LanguageTag {
+    tag: String
+}
+
+impl LanguageTag {
+    pub fn as_str(&self) -> &str {
+        &self.tag
+    }
+
+    /// The first subtag of the tag, which is `x` for a private use tag.
+    pub fn primary_language(&self) -> &str {
+        self.tag.split('-').next().unwrap_or("")
+    }
+
+    /// Returns true if the tag matches a basic language range, see [`basic_filter()`].
+    pub fn matches_basic(&self, range: &str) -> bool {
+        basic_filter(range, &self.tag)
+    }
+
+    /// Returns true if the tag matches an extended language range, see [`extended_filter()`].
+    pub fn matches(&self, range: &str) -> bool {
+        extended_filter(range, &self.tag)
+    }
+}
+
+impl FromStr for LanguageTag {
+    type Err = ParserError;
+
+    /// Parses a language tag and normalizes its case.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the tag is not well-formed according to RFC 5646, or if it
+    /// repeats a variant or an extension.
+    fn from_str(s: &str) -> Parsed<Self> {
+        let lower = s.to_ascii_lowercase();
+        let subtags: Vec<&str> = lower.split('-').collect();
+
+        if !GRANDFATHERED.contains(&lower.as_str()) && !is_well_formed(&subtags) {
+            return Err(ParserError(format!("Invalid language tag: {}", s)));
+        }
+
+        // Scripts and regions only follow the primary language, never a singleton
+        let mut after_singleton = false;
+        let normalized: Vec<String> = subtags.iter().enumerate()
+            .map(|(i, subtag)| {
+                let subtag = match subtag.len() {
+                    2 if i > 0 && !after_singleton => subtag.to_ascii_uppercase(),
+                    4 if i > 0 && !after_singleton && is_alpha(subtag) => subtag[..1].to_ascii_uppercase() + &subtag[1..],
+                    _ => subtag.to_string()
+                };
+
+                after_singleton = after_singleton || subtag.len() == 1;
+                subtag
+            })
+            .collect();
+
+        Ok(Self { tag: normalized.join("-") })
+    }
+}
+
+impl std::fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tag)
+    }
+}
+
+// The tag in normal case, or as it is written if it is malformed, for strict parsing to reject
+pub(crate) fn normalize(tag: &str) -> String {
+    tag.parse::<LanguageTag>().map(|t| t.to_string()).unwrap_or_else(|_| tag.to_string())
+}
+
+/// Basic filtering (RFC 4647, section 3.3.1): returns true if `tag` equals the basic language
+/// `range`, or starts with it followed by a `-`. The range `*` matches any tag. Comparisons are
+/// case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::basic_filter;
+/// assert!(basic_filter("de-de", "de-DE-1996"));
+/// assert!(!basic_filter("de-DE", "de-Latn-DE"));
+/// assert!(!basic_filter("de", "den"));
+/// assert!(!basic_filter("e", "éa"));
+/// ```
+pub fn basic_filter(range: &str, tag: &str) -> bool {
+    if range == "*" {
+        return true;
+    }
+
+    // Compared as bytes, as the range may not end on a character boundary of the tag
+    let (tag, range) = (tag.as_bytes(), range.as_bytes());
+
+    tag.get(..range.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(range)) &&
+        (tag.len() == range.len() || tag[range.len()] == b'-')
+}
+
+/// Extended filtering (RFC 4647, section 3.3.2): returns true if `tag` matches the extended
+/// language `range`. A `*` subtag in the range matches any number of subtags, and subtags of the
+/// tag may be skipped between those of the range, except for singletons. Comparisons are
+/// case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::extended_filter;
+/// assert!(extended_filter("de-*-DE", "de-Latn-DE"));
+/// assert!(extended_filter("de-DE", "de-Latn-DE-1996"));
+/// assert!(extended_filter("*-DE", "de-DE"));
+/// assert!(!extended_filter("de-DE", "de-x-DE"));
+/// ```
+pub fn extended_filter(range: &str, tag: &str) -> bool {
+    let range: Vec<String> = range.split('-').map(|s| s.to_ascii_lowercase()).collect();
+    let tag: Vec<String> = tag.split('-').map(|s| s.to_ascii_lowercase()).collect();
+
+    if range[0] != "*" && range[0] != tag[0] {
+        return false;
+    }
+
+    let (mut r, mut t) = (1, 1);
+    while r < range.len() {
+        if range[r] == "*" {
+            r += 1;
+        } else if t >= tag.len() || tag[t].len() == 1 && range[r] != tag[t] {
+            return false;
+        } else if range[r] == tag[t] {
+            r += 1;
+            t += 1;
+        } else {
+            t += 1;
+        }
+    }
+
+    true
+}
+
+/// Lookup (RFC 4647, section 3.4): returns the tag that best matches the language ranges, which
+/// are tried in order of preference. Each range is made less specific one subtag at a time until
+/// it equals one of the tags. `*` ranges are ignored.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::lookup;
+/// let tags = ["en", "fr-CA", "zh-Hant"];
+///
+/// assert_eq!(lookup(&["zh-Hant-CN-x-private"], tags.iter().copied()), Some("zh-Hant"));
+/// assert_eq!(lookup(&["de", "fr-CA-u-ca-gregory"], tags.iter().copied()), Some("fr-CA"));
+/// assert_eq!(lookup(&["de"], tags.iter().copied()), None);
+/// ```
+pub fn lookup<'a, I>(ranges: &[&str], tags: I) -> Option<&'a str>
+    where I: IntoIterator<Item = &'a str> {
+
+    let tags: Vec<&str> = tags.into_iter().collect();
+
+    for range in ranges.iter().filter(|r| **r != "*") {
+        let mut range = *range;
+
+        loop {
+            if let Some(tag) = tags.iter().find(|t| t.eq_ignore_ascii_case(range)) {
+                return Some(*tag);
+            }
+
+            range = match range.rfind('-') {
+                Some(i) => &range[..i],
+                None => break
+            };
+
+            // A range never ends with a singleton
+            if range.len() >= 2 && range.as_bytes()[range.len() - 2] == b'-' {
+                range = &range[..range.len() - 2];
+            }
+        }
+    }
+
+    None
+}
+
+impl Literal {
+    /// The language tag of the literal.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the literal has a language tag that is not well-formed.
+    pub fn language_tag(&self) -> Option<Parsed<LanguageTag>> {
+        self.language.as_ref().map(|l| l.parse())
+    }
+
+    /// Returns true if the literal has a language tag that matches an extended language range,
+    /// such as `en`, `en-*` or `*-CH`, see [`extended_filter()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::{ Object, Literal };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let name = TurtleParser::object(r#""Colour"@en-GB"#)?;
+    /// let name = name.literal().unwrap();
+    ///
+    /// assert!(name.matches_language("en-*"));
+    /// assert!(name.matches_language("EN"));
+    /// assert!(!name.matches_language("en-US"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn matches_language(&self, range: &str) -> bool {
+        self.language.as_ref().map(|l| extended_filter(range, l)).unwrap_or(false)
+    }
+
+    /// Returns true if the literal has a language tag that matches a basic language range, see
+    /// [`basic_filter()`].
+    pub fn matches_language_basic(&self, range: &str) -> bool {
+        self.language.as_ref().map(|l| basic_filter(range, l)).unwrap_or(false)
+    }
+
+    /// Returns the literal whose language tag best matches the language ranges, in order of
+    /// preference, see [`lookup()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::Literal;
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"ex:Cat rdfs:label "Cat"@en, "Chat"@fr, "Katze"@de ."#)?;
+    /// let labels: Vec<&Literal> = graph.triples.iter().filter_map(|t| t.object.literal()).collect();
+    ///
+    /// let label = Literal::lookup_language(labels, &["fr-CH", "en"]);
+    /// assert_eq!(label.map(|l| l.lexical_form()), Some("Chat"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lookup_language<'a, I>(literals: I, ranges: &[&str]) -> Option<&'a Literal>
+        where I: IntoIterator<Item = &'a Literal> {
+
+        let literals: Vec<&Literal> = literals.into_iter().filter(|l| l.language.is_some()).collect();
+        let tag = lookup(ranges, literals.iter().filter_map(|l| l.language.as_deref()))?;
+
+        literals.into_iter().find(|l| l.language.as_deref() == Some(tag))
+    }
+}
@@ -0,0 +1,385 @@
+use std::cmp::Reverse;
+use std::collections::{ BTreeSet, HashMap, HashSet };
+
+use crate::core::{ Graph, Triple, Resource, Relationship, Object, Uri };
+use crate::core::merge::{ is_blank_node, blank_labels };
+
+// A mapping of blank node labels to the terms they stand for, which remembers the order of its
+// bindings so that they can be undone
+#[derive(Default)]
+struct Bindings {
+    terms: HashMap<String, Object>,
+    trail: Vec<String>
+}
+
+impl Bindings {
+    fn bind(&mut self, label: &str, term: &Object) -> bool {
+        match self.terms.get(label) {
+            Some(bound) => bound == term,
+            None => {
+                self.terms.insert(label.to_string(), term.clone());
+                self.trail.push(label.to_string());
+                true
+            }
+        }
+    }
+
+    fn undo(&mut self, length: usize) {
+        for label in self.trail.drain(length..) {
+            self.terms.remove(&label);
+        }
+    }
+
+    fn uri(&mut self, pattern: &Uri, target: &Uri, variable: &dyn Fn(&Uri) -> bool) -> bool {
+        if variable(pattern) {
+            self.bind(&pattern.name, &Object::Resource(target.clone()))
+        } else {
+            pattern == target
+        }
+    }
+
+    fn object(&mut self, pattern: &Object, target: &Object, variable: &dyn Fn(&Uri) -> bool) -> bool {
+        match (pattern, target) {
+            (Object::Resource(u), _) if variable(u) => self.bind(&u.name, target),
+            (Object::Triple(pattern), Object::Triple(target)) => self.triple(pattern, target, variable),
+            (pattern, target) => pattern == target
+        }
+    }
+
+    // Extends the bindings so that `pattern` becomes `target`, leaving them as they were if it
+    // cannot
+    fn triple(&mut self, pattern: &Triple, target: &Triple, variable: &dyn Fn(&Uri) -> bool) -> bool {
+        let length = self.trail.len();
+
+        let unified = self.uri(&pattern.subject.0, &target.subject.0, variable) &&
+            self.uri(&pattern.predicate.0, &target.predicate.0, variable) &&
+            self.object(&pattern.object, &target.object, variable);
+
+        if !unified {
+            self.undo(length);
+        }
+        unified
+    }
+
+    fn apply(&self, t: &Triple) -> Triple {
+        let uri = |u: &Uri| match self.terms.get(&u.name) {
+            Some(Object::Resource(term)) if is_blank_node(u) => term.clone(),
+            _ => u.clone()
+        };
+
+        // A blank node in object position can stand for a literal or a triple term as well
+        let object = match &t.object {
+            Object::Resource(u) if is_blank_node(u) => self.terms.get(&u.name).cloned().unwrap_or_else(|| t.object.clone()),
+            Object::Triple(inner) => Object::Triple(Box::new(self.apply(inner))),
+            object => object.clone()
+        };
+
+        (Resource(uri(&t.subject.0)), Relationship(uri(&t.predicate.0)), object).into()
+    }
+}
+
+// The resource a URI of a pattern stands for: itself if it is not a variable, or what it is bound
+// to if it is
+fn known<'t>(u: &'t Uri, bindings: &'t Bindings, variable: &dyn Fn(&Uri) -> bool) -> Option<&'t Uri> {
+    if variable(u) {
+        bindings.terms.get(&u.name).and_then(|o| o.resource())
+    } else {
+        Some(u)
+    }
+}
+
+// The triples a pattern can be mapped onto, by subject, predicate and object
+struct Candidates<'a> {
+    all: Vec<&'a Triple>,
+    by_subject: HashMap<&'a Uri, Vec<&'a Triple>>,
+    by_predicate: HashMap<&'a Uri, Vec<&'a Triple>>,
+    by_object: HashMap<&'a Object, Vec<&'a Triple>>
+}
+
+impl<'a> Candidates<'a> {
+    fn new(triples: impl Iterator<Item = &'a Triple>) -> Self {
+        let all: Vec<&Triple> = triples.collect();
+        let mut by_subject: HashMap<&Uri, Vec<&Triple>> = HashMap::new();
+        let mut by_predicate: HashMap<&Uri, Vec<&Triple>> = HashMap::new();
+        let mut by_object: HashMap<&Object, Vec<&Triple>> = HashMap::new();
+        for t in &all {
+            by_subject.entry(&t.subject.0).or_default().push(t);
+            by_predicate.entry(&t.predicate.0).or_default().push(t);
+            by_object.entry(&t.object).or_default().push(t);
+        }
+
+        Self { all, by_subject, by_predicate, by_object }
+    }
+
+    // The triples that share the most selective term of `pattern` that is already known, either
+    // because it is not a variable or because it is bound
+    fn of(&self, pattern: &Triple, bindings: &Bindings, variable: &dyn Fn(&Uri) -> bool) -> &[&'a Triple] {
+        let object = match &pattern.object {
+            Object::Resource(u) if variable(u) => bindings.terms.get(&u.name),
+            // A triple term may contain variables
+            Object::Triple(_) => None,
+            o => Some(o)
+        };
+
+        let lists = [
+            known(&pattern.subject.0, bindings, variable).map(|s| self.by_subject.get(s)),
+            known(&pattern.predicate.0, bindings, variable).map(|p| self.by_predicate.get(p)),
+            object.map(|o| self.by_object.get(o))
+        ];
+
+        lists.iter().flatten()
+            .map(|list| list.map(|l| l.as_slice()).unwrap_or(&[]))
+            .min_by_key(|list| list.len())
+            .unwrap_or(&self.all)
+    }
+}
+
+fn labels(t: &Triple) -> Vec<String> {
+    let mut labels = HashSet::new();
+    blank_labels(t, &mut labels);
+    labels.into_iter().map(String::from).collect()
+}
+
+// Orders the triples with blank nodes so that each one shares as many blank nodes as possible
+// with those before it, starting with `first`, which keeps the search for a homomorphism from
+// binding unrelated nodes before it fails
+fn order<'a>(first: &'a Triple, triples: &'a [Triple]) -> Vec<&'a Triple> {
+    let of: Vec<Vec<String>> = triples.iter().map(labels).collect();
+    let mut by_label: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, labels) in of.iter().enumerate() {
+        for label in labels {
+            by_label.entry(label).or_default().push(i);
+        }
+    }
+
+    // The remaining triples by the number of their blank nodes already seen, whether that is
+    // all of them, and then by their position
+    let key = |i: usize, shared: usize| (shared, shared == of[i].len(), Reverse(i));
+    let mut shared = vec![0; triples.len()];
+    let mut remaining: BTreeSet<(usize, bool, Reverse<usize>)> = (0..triples.len())
+        .filter(|&i| triples[i] != *first && !of[i].is_empty())
+        .map(|i| key(i, 0))
+        .collect();
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut ordered = vec![first];
+    let mut next = labels(first);
+
+    loop {
+        for label in &next {
+            if !seen.insert(label.clone()) {
+                continue;
+            }
+
+            for &i in by_label.get::<str>(label).into_iter().flatten() {
+                if remaining.remove(&key(i, shared[i])) {
+                    shared[i] += 1;
+                    remaining.insert(key(i, shared[i]));
+                }
+            }
+        }
+
+        let (_, _, Reverse(i)) = match remaining.pop_last() {
+            Some(last) => last,
+            None => break
+        };
+
+        ordered.push(&triples[i]);
+        next = of[i].clone();
+    }
+
+    ordered
+}
+
+// Searches for a mapping of blank nodes that turns each of `patterns` into a triple of `target`,
+// backtracking with an explicit stack so that long patterns cannot overflow the call stack
+fn homomorphism(patterns: &[&Triple], target: &Candidates, bindings: &mut Bindings) -> bool {
+    // For each pattern matched so far, the next candidate to try for it and the length of the
+    // trail before it was matched
+    let mut matched: Vec<(usize, usize)> = Vec::new();
+    let mut next = 0;
+
+    while let Some(pattern) = patterns.get(matched.len()) {
+        let candidates = target.of(pattern, bindings, &is_blank_node);
+        let length = bindings.trail.len();
+
+        match (next..candidates.len()).find(|&i| bindings.triple(pattern, candidates[i], &is_blank_node)) {
+            Some(i) => {
+                matched.push((i + 1, length));
+                next = 0;
+            },
+            None => match matched.pop() {
+                Some((candidate, length)) => {
+                    bindings.undo(length);
+                    next = candidate;
+                },
+                None => return false
+            }
+        }
+    }
+
+    true
+}
+
+// The triples with the bindings applied, without duplicates
+fn mapped(triples: &[Triple], bindings: &Bindings) -> Vec<Triple> {
+    let mut seen = HashSet::new();
+    triples.iter()
+        .map(|t| bindings.apply(t))
+        .filter(|t| seen.insert(t.clone()))
+        .collect()
+}
+
+impl Graph {
+    // The expanded triples of the graph, without duplicates
+    fn expanded_triples(&self) -> Vec<Triple> {
+        let mut seen = HashSet::new();
+        self.triples.iter()
+            .map(|t| self.expanded(t))
+            .filter(|t| seen.insert(t.clone()))
+            .collect()
+    }
+
+    // This graph with only the stored triples of `kept`, in their original order
+    fn keeping(&self, kept: &HashSet<Triple>) -> Graph {
+        let mut graph = self.clone();
+        graph.triples.retain(|t| kept.contains(&self.expanded(t)));
+
+        let removed: Vec<Triple> = self.triples.iter().filter(|t| !graph.triples.contains(t)).cloned().collect();
+        for t in &removed {
+            graph.locations.remove(t);
+        }
+
+        graph
+    }
+
+    /// Returns the lean core of this graph, as defined by
+    /// [RDF 1.1 Semantics](https://www.w3.org/TR/rdf11-mt/#dfn-lean): the smallest subgraph that
+    /// an instance of the graph maps onto, where blank nodes are replaced by other terms of the
+    /// graph. Its triples say everything the graph does, without the redundant blank nodes.
+    ///
+    /// The triples are compared once their prefixed names and relative IRIs are expanded, but
+    /// those kept are returned as they were written. Finding the core can take exponential time
+    /// in the number of blank nodes; [`Graph::approximate_lean()`] is cheaper.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     ex:John foaf:knows ex:Jane , _:someone .
+    ///     ex:Jane foaf:name "Jane" .
+    ///     _:someone foaf:name _:name .
+    ///     _:x foaf:knows _:y .
+    /// "#)?;
+    ///
+    /// // _:someone can be ex:Jane, _:name "Jane", and _:x foaf:knows _:y says no more than
+    /// // ex:John foaf:knows ex:Jane
+    /// let lean = graph.lean();
+    /// assert_eq!(lean.triples.len(), 2);
+    /// assert!(lean.is_lean());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lean(&self) -> Graph {
+        let mut triples = self.expanded_triples();
+
+        'reduce: loop {
+            for (i, removed) in triples.iter().enumerate() {
+                if labels(removed).is_empty() {
+                    continue;
+                }
+
+                let target = Candidates::new(triples.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, t)| t));
+                let mut bindings = Bindings::default();
+
+                if homomorphism(&order(removed, &triples), &target, &mut bindings) {
+                    triples = mapped(&triples, &bindings);
+                    continue 'reduce;
+                }
+            }
+
+            break;
+        }
+
+        self.keeping(&triples.into_iter().collect())
+    }
+
+    /// Returns whether no triple of this graph is redundant, that is whether it is its own
+    /// [`Graph::lean()`] core.
+    pub fn is_lean(&self) -> bool {
+        self.lean().triples.len() == self.expanded_triples().len()
+    }
+
+    /// Returns this graph without the blank nodes that can be replaced, one at a time, by
+    /// another term of the graph: a blank node is removed when replacing it by some term in all
+    /// its triples gives triples the graph already contains.
+    ///
+    /// This takes polynomial time and removes the redundant structure reasoners add, such as the
+    /// blank nodes that stand for the same literal, but unlike [`Graph::lean()`] it keeps groups
+    /// of blank nodes that are only redundant together, such as `_:x ex:p _:y . _:y ex:p _:x .`
+    /// next to `ex:a ex:p ex:a .`.
+    pub fn approximate_lean(&self) -> Graph {
+        let triples = self.expanded_triples();
+        let target = Candidates::new(triples.iter());
+
+        // The triples of each blank node, with the nodes in the order they first appear
+        let mut nodes: Vec<String> = Vec::new();
+        let mut own: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, t) in triples.iter().enumerate() {
+            let mut labels = labels(t);
+            labels.sort_unstable();
+
+            for label in labels {
+                own.entry(label.clone()).or_insert_with(|| {
+                    nodes.push(label);
+                    Vec::new()
+                }).push(i);
+            }
+        }
+
+        // Replacing a node gives triples the graph contains, so it only removes the node's own
+        let mut existing: HashSet<&Triple> = triples.iter().collect();
+        let mut removed = vec![false; triples.len()];
+
+        loop {
+            let mut changed = false;
+
+            for node in &nodes {
+                let indexes: Vec<usize> = own[node].iter().copied().filter(|&i| !removed[i]).collect();
+                let first = match indexes.first() {
+                    Some(&first) => &triples[first],
+                    None => continue
+                };
+
+                let itself = Object::Resource(Uri::new("_:", node, crate::core::uri::UriType::BlankNode));
+                let variable = |u: &Uri| is_blank_node(u) && &u.name == node;
+
+                let replaceable = target.of(first, &Bindings::default(), &variable).iter().any(|candidate| {
+                    let mut bindings = Bindings::default();
+
+                    bindings.triple(first, candidate, &variable) &&
+                        bindings.terms.get(node).is_some_and(|term| *term != itself) &&
+                        indexes.iter().all(|&i| existing.contains(&bindings.apply(&triples[i])))
+                });
+
+                if replaceable {
+                    for i in indexes {
+                        removed[i] = true;
+                        existing.remove(&triples[i]);
+                    }
+
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        let kept = triples.iter().zip(&removed).filter(|(_, removed)| !**removed).map(|(t, _)| t.clone());
+        self.keeping(&kept.collect())
+    }
+}
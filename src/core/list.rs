@@ -0,0 +1,171 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::core::{ Graph, Triple, Resource, Relationship, Object, Uri, uri::UriType };
+use crate::core::merge::blank_labels;
+use crate::parsing::{ ParserError, Parsed };
+use crate::vocab::rdf;
+
+// The rdf:first and rdf:rest triples of a list node: the expanded object, used to follow the
+// list, and the stored triple
+#[derive(Default)]
+struct ListNode<'a> {
+    first: Vec<(Object, &'a Triple)>,
+    rest: Vec<(Object, &'a Triple)>
+}
+
+impl Graph {
+    // An rdf: term, prefixed if the graph defines the rdf prefix
+    pub(crate) fn rdf(&self, name: &str) -> Uri {
+        match self.prefixes.get(rdf::PREFIX) {
+            Some(namespace) if namespace == rdf::NAMESPACE => Uri::new(rdf::PREFIX, name, UriType::Prefixed),
+            _ => Uri::new(rdf::NAMESPACE, name, UriType::Full)
+        }
+    }
+
+    // The list nodes of the graph, by their expanded IRI or blank node label
+    fn list_nodes(&self) -> HashMap<Uri, ListNode<'_>> {
+        let mut nodes: HashMap<Uri, ListNode> = HashMap::new();
+
+        for t in self.triples.iter() {
+            let expanded = self.expanded(t);
+            let predicate = &expanded.predicate.0;
+            if rdf::FIRST.matches(predicate) {
+                nodes.entry(expanded.subject.0).or_default().first.push((expanded.object, t));
+            } else if rdf::REST.matches(predicate) {
+                nodes.entry(expanded.subject.0).or_default().rest.push((expanded.object, t));
+            }
+        }
+
+        nodes
+    }
+
+    // The nodes of the list starting at `head`, after checking that it is well formed
+    fn list_walk<'a>(&self, head: &Uri, nodes: &'a HashMap<Uri, ListNode<'a>>) -> Parsed<Vec<&'a ListNode<'a>>> {
+        let nil = rdf::NIL.uri();
+        let mut node = self.expanded_uri(head);
+        let mut visited = HashSet::new();
+        let mut walked = Vec::new();
+
+        while node != nil {
+            if !visited.insert(node.clone()) {
+                return Err(ParserError(format!("The list at {} is cyclic: it reaches {} twice", head, node)));
+            }
+
+            let list_node = nodes.get(&node)
+                .ok_or_else(|| ParserError(format!("The list at {} does not end with rdf:nil: {} is not a list node", head, node)))?;
+
+            match (list_node.first.len(), list_node.rest.len()) {
+                (1, 1) => (),
+                (0, _) => return Err(ParserError(format!("The list node {} has no rdf:first", node))),
+                (_, 0) => return Err(ParserError(format!("The list at {} does not end with rdf:nil: {} has no rdf:rest", head, node))),
+                (first, rest) => return Err(ParserError(format!("The list node {} branches: it has {} rdf:first and {} rdf:rest", node, first, rest)))
+            }
+
+            node = match &list_node.rest[0].0 {
+                Object::Resource(next) => next.clone(),
+                other => return Err(ParserError(format!("The rdf:rest of the list node {} is not a list node: {}", node, other)))
+            };
+            walked.push(list_node);
+        }
+
+        Ok(walked)
+    }
+
+    /// Reads the items of the RDF collection (`rdf:List`) starting at `head`, in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the list is not well formed: if a node of the list has no
+    /// `rdf:first`, has more than one `rdf:first` or `rdf:rest` (a branch), if the list comes back
+    /// to one of its nodes (a cycle), or if it does not end with `rdf:nil`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"ex:basket ex:contains ( "apple" "banana" ) ."#)?;
+    /// let head = graph.triples.last().unwrap().object.resource().unwrap();
+    ///
+    /// let items = graph.list(head)?;
+    /// assert_eq!(items.len(), 2);
+    /// assert_eq!(items[1].literal().unwrap().lexical_form(), "banana");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list(&self, head: &Uri) -> Parsed<Vec<Object>> {
+        let nodes = self.list_nodes();
+
+        Ok(self.list_walk(head, &nodes)?
+            .into_iter()
+            .map(|node| node.first[0].1.object.clone())
+            .collect())
+    }
+
+    /// Adds the items to the graph as an RDF collection, and returns its head: a new blank node,
+    /// or `rdf:nil` if there are no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # use rdf_rs::core::Object;
+    /// # fn main() -> Result<(), ParserError> {
+    /// let mut graph = TurtleParser::graph("")?;
+    /// let fruits = vec![TurtleParser::object("ex:apple")?, TurtleParser::object("ex:banana")?];
+    ///
+    /// let head = graph.insert_list(fruits.clone());
+    /// graph.triples.insert((TurtleParser::resource("ex:basket")?, TurtleParser::relationship("ex:contains")?, Object::Resource(head.clone())).into());
+    ///
+    /// assert_eq!(graph.triples.len(), 5);
+    /// assert_eq!(graph.list(&head)?, fruits);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert_list<I: IntoIterator<Item = Object>>(&mut self, items: I) -> Uri {
+        let mut used = HashSet::new();
+        self.triples.iter().for_each(|t| blank_labels(t, &mut used));
+
+        let items: Vec<Object> = items.into_iter().collect();
+        let nodes: Vec<Uri> = (1..)
+            .map(|n| format!("list{}", n))
+            .filter(|l| !used.contains(l.as_str()))
+            .take(items.len())
+            .map(|l| Uri::new("_:", &l, UriType::Prefixed))
+            .collect();
+        let (first, rest, nil) = (self.rdf("first"), self.rdf("rest"), self.rdf("nil"));
+
+        for (i, item) in items.into_iter().enumerate() {
+            let next = nodes.get(i + 1).unwrap_or(&nil);
+
+            self.triples.insert((Resource(nodes[i].clone()), Relationship(first.clone()), item).into());
+            self.triples.insert((Resource(nodes[i].clone()), Relationship(rest.clone()), Object::Resource(next.clone())).into());
+        }
+
+        nodes.into_iter().next().unwrap_or(nil)
+    }
+
+    /// Removes the `rdf:first` and `rdf:rest` triples of the RDF collection starting at `head`,
+    /// and returns its items. Triples that refer to the list or to its items are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the list is not well formed (see [`Graph::list()`]), in which
+    /// case the graph is left unchanged.
+    pub fn remove_list(&mut self, head: &Uri) -> Parsed<Vec<Object>> {
+        let nodes = self.list_nodes();
+        let walked = self.list_walk(head, &nodes)?;
+
+        let items: Vec<Object> = walked.iter().map(|node| node.first[0].1.object.clone()).collect();
+        let removed: Vec<Triple> = walked.iter()
+            .flat_map(|node| vec![node.first[0].1.clone(), node.rest[0].1.clone()])
+            .collect();
+
+        for t in &removed {
+            self.triples.remove(t);
+            self.locations.remove(t);
+        }
+
+        Ok(items)
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use crate::core::Triple;
+
+/// A 1-based line and column within a source document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize
+}
+
+impl Position {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+}
+
+impl Default for Position {
+    fn default() -> Self {
+        Self::new(1, 1)
+    }
+}
+
+/// The statement a [`Triple`] was parsed from: the name of its source (typically a file path)
+/// and the range from its first to its last character, both inclusive.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceLocation {
+    pub source: Option<String>,
+    pub start: Position,
+    pub end: Position
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}-{}:{}",
+            self.source.as_deref().unwrap_or("<input>"),
+            self.start.line, self.start.column,
+            self.end.line, self.end.column
+        )
+    }
+}
+
+/// A side table from the triples of a [`Graph`](crate::core::Graph) to the statements they were
+/// parsed from.
+///
+/// A triple can be stated more than once, possibly in different sources, so every triple maps
+/// to all of its locations. Merging two maps keeps the locations of both.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceMap(HashMap<Triple, Vec<SourceLocation>>);
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns every recorded location of `triple`, or an empty slice.
+    pub fn get(&self, triple: &Triple) -> &[SourceLocation] {
+        self.0.get(triple).map(|l| l.as_slice()).unwrap_or(&[])
+    }
+
+    /// Records that `triple` was parsed from `location`.
+    pub fn insert(&mut self, triple: Triple, location: SourceLocation) {
+        let locations = self.0.entry(triple).or_default();
+
+        if !locations.contains(&location) {
+            locations.push(location);
+        }
+    }
+
+    /// Forgets the locations of `triple`, returning them.
+    pub fn remove(&mut self, triple: &Triple) -> Vec<SourceLocation> {
+        self.0.remove(triple).unwrap_or_default()
+    }
+
+    /// Adds all the locations of `other` to this map.
+    pub fn merge(&mut self, other: SourceMap) {
+        for (triple, locations) in other.0 {
+            for location in locations {
+                self.insert(triple.clone(), location);
+            }
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Triple, &Vec<SourceLocation>)> {
+        self.0.iter()
+    }
+
+    // Moves the locations of `old[i]` to `new[i]` after the triples of a graph were rewritten
+    pub(crate) fn rekey(&mut self, old: &[Triple], new: &[Triple]) {
+        if self.is_empty() {
+            return;
+        }
+
+        let mut rekeyed = SourceMap::new();
+        for (old, new) in old.iter().zip(new) {
+            if let Some(locations) = self.0.remove(old) {
+                for location in locations {
+                    rekeyed.insert(new.clone(), location);
+                }
+            }
+        }
+
+        self.merge(rekeyed);
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::{ HashMap, HashSet };
+
+use crate::core::{ Graph, Triple, Resource, Relationship, Object, Literal, Uri, PrefixMap, SourceMap, TripleStore, uri::UriType };
+
+/// A prefix label that two graphs map to different namespaces, as found by
+/// [`Graph::prefix_conflicts()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixConflict {
+    /// The label, including its trailing colon (`"ex:"`).
+    pub prefix: String,
+    /// The namespace of the label in the graph being combined into.
+    pub namespace: String,
+    /// The namespace of the label in the other graph.
+    pub other_namespace: String,
+    /// The label that stands for `other_namespace` in the combined graph.
+    pub renamed_to: String
+}
+
+impl std::fmt::Display for PrefixConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is both <{}> and <{}>, renamed the latter to {}", self.prefix, self.namespace, self.other_namespace, self.renamed_to)
+    }
+}
+
+pub(crate) fn is_blank_node(u: &Uri) -> bool {
+    u.uri_type == UriType::BlankNode || (u.uri_type == UriType::Prefixed && u.prefix == "_:")
+}
+
+// Rewrites every URI of a triple, including the datatypes of literals and the URIs of triple
+// terms
+pub(crate) fn map_uris<F: Fn(&Uri) -> Uri>(t: &Triple, f: &F) -> Triple {
+    let object = match &t.object {
+        Object::Resource(u) => Object::Resource(f(u)),
+        Object::Literal(l) => Object::Literal(Literal { datatype: f(&l.datatype), ..l.clone() }),
+        Object::Triple(t) => Object::Triple(Box::new(map_uris(t, f)))
+    };
+
+    (Resource(f(&t.subject.0)), Relationship(f(&t.predicate.0)), object).into()
+}
+
+// The blank node labels of a triple, including those within triple terms
+pub(crate) fn blank_labels<'a>(t: &'a Triple, labels: &mut HashSet<&'a str>) {
+    for u in [&t.subject.0, &t.predicate.0] {
+        if is_blank_node(u) {
+            labels.insert(&u.name);
+        }
+    }
+
+    match &t.object {
+        Object::Resource(u) if is_blank_node(u) => { labels.insert(&u.name); },
+        Object::Triple(t) => blank_labels(t, labels),
+        _ => ()
+    }
+}
+
+impl Graph {
+    // The URI expanded with the prefixes and base of the graph, where it defines them
+    pub(crate) fn expanded_uri(&self, u: &Uri) -> Uri {
+        match u.uri_type {
+            UriType::Prefixed if !is_blank_node(u) => match self.prefixes.get(&u.prefix) {
+                Some(namespace) => Uri::new(namespace, &u.name, UriType::Full),
+                None => u.clone()
+            },
+            UriType::Relative | UriType::PrefixedWithBase => match &self.base_prefix {
+                Some(base) => Uri::new(base, &u.name, UriType::Full),
+                None => u.clone()
+            },
+            _ => u.clone()
+        }
+    }
+
+    // The triple with its prefixed names and relative IRIs expanded, where the graph defines
+    // them. Only used to compare triples across graphs.
+    pub(crate) fn expanded(&self, t: &Triple) -> Triple {
+        map_uris(t, &|u: &Uri| self.expanded_uri(u))
+    }
+
+    /// Returns the prefix labels that this graph and `other` map to different namespaces.
+    ///
+    /// [`Graph::union()`] and [`Graph::merge()`] keep the namespace of this graph for such a
+    /// label, and give the namespace of `other` the label [`PrefixConflict::renamed_to`]: the
+    /// label followed by the smallest number that neither graph uses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let a = TurtleParser::graph("@prefix ex: <http://example.com/a/> . ex:John a ex:Person .")?;
+    /// let b = TurtleParser::graph("@prefix ex: <http://example.com/b/> . ex:Jane a ex:Person .")?;
+    ///
+    /// let conflicts = a.prefix_conflicts(&b);
+    /// assert_eq!(conflicts[0].prefix, "ex:");
+    /// assert_eq!(conflicts[0].renamed_to, "ex1:");
+    ///
+    /// let union = a.union(&b);
+    /// assert_eq!(union.prefixes["ex1:"], "http://example.com/b/");
+    /// assert_eq!(union.triples[1].to_string(), "ex1:Jane rdf:type ex1:Person .");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prefix_conflicts(&self, other: &Graph) -> Vec<PrefixConflict> {
+        let labels = other.prefixes.sorted();
+
+        let mut taken: HashSet<String> = self.prefixes.labels().chain(other.prefixes.labels()).cloned().collect();
+        let mut conflicts = Vec::new();
+
+        for (prefix, other_namespace) in labels {
+            let namespace = match self.prefixes.get(prefix) {
+                Some(namespace) if namespace != other_namespace => namespace,
+                _ => continue
+            };
+
+            let stem = prefix.trim_end_matches(':');
+            let renamed_to = (1..).map(|n| format!("{}{}:", stem, n)).find(|p| !taken.contains(p)).unwrap();
+            taken.insert(renamed_to.clone());
+
+            conflicts.push(PrefixConflict {
+                prefix: prefix.clone(),
+                namespace: namespace.clone(),
+                other_namespace: other_namespace.clone(),
+                renamed_to
+            });
+        }
+
+        conflicts
+    }
+
+    // The prefixes of this graph and `other`, and the triples of `other` rewritten to mean the
+    // same with them and with the base of this graph
+    pub(crate) fn reconcile(&self, other: &Graph) -> (PrefixMap, Vec<Triple>) {
+        let conflicts = self.prefix_conflicts(other);
+        let renamed: HashMap<&str, &str> = conflicts.iter()
+            .map(|c| (c.prefix.as_str(), c.renamed_to.as_str()))
+            .collect();
+
+        let mut prefixes = self.prefixes.clone();
+        for (prefix, namespace) in &other.prefixes {
+            let prefix = renamed.get(prefix.as_str()).copied().unwrap_or(prefix);
+            if !prefixes.contains_key(prefix) {
+                prefixes.insert(prefix, namespace);
+            }
+        }
+
+        let rebase = other.base_prefix.is_some() && other.base_prefix != self.base_prefix;
+        let triples = other.triples.iter()
+            .map(|t| map_uris(t, &|u: &Uri| match u.uri_type {
+                UriType::Prefixed if renamed.contains_key(u.prefix.as_str()) => Uri::new(renamed[u.prefix.as_str()], &u.name, UriType::Prefixed),
+                UriType::Relative | UriType::PrefixedWithBase if rebase => {
+                    Uri::new(other.base_prefix.as_deref().unwrap_or_default(), &u.name, UriType::Full)
+                },
+                _ => u.clone()
+            }))
+            .collect();
+
+        (prefixes, triples)
+    }
+
+    // A graph with the base of this one, the given prefixes and triples, and the locations of
+    // the triples found in `sources`
+    fn combined(&self, prefixes: PrefixMap, triples: Vec<Triple>, sources: &[(&SourceMap, &[Triple], &[Triple])]) -> Graph {
+        let mut locations = SourceMap::new();
+        for (source, old, new) in sources {
+            if source.is_empty() {
+                continue;
+            }
+
+            let mut source = (*source).clone();
+            source.rekey(old, new);
+            locations.merge(source);
+        }
+
+        let mut kept = SourceMap::new();
+        for t in &triples {
+            for location in locations.get(t) {
+                kept.insert(t.clone(), location.clone());
+            }
+        }
+
+        Graph {
+            base_prefix: self.base_prefix.clone(),
+            prefixes,
+            triples: triples.into(),
+            locations: kept
+        }
+    }
+
+    /// Returns the triples of this graph followed by those of `other` that it does not contain.
+    ///
+    /// Blank nodes with the same label are the same node in both graphs; use [`Graph::merge()`]
+    /// if they should be kept apart. Triples are compared once their prefixed names and relative
+    /// IRIs are expanded, so `ex:John` in one graph equals `<http://example.com/John>` in the other.
+    /// See [`Graph::prefix_conflicts()`] for how the prefixes of both graphs are combined.
+    pub fn union(&self, other: &Graph) -> Graph {
+        let (prefixes, reconciled) = self.reconcile(other);
+        let combined = Graph {
+            base_prefix: self.base_prefix.clone(),
+            prefixes: prefixes.clone(),
+            triples: TripleStore::new(),
+            locations: SourceMap::new()
+        };
+
+        let existing: HashSet<Triple> = self.triples.iter().map(|t| combined.expanded(t)).collect();
+        let added: Vec<Triple> = reconciled.iter()
+            .filter(|t| !existing.contains(&combined.expanded(t)))
+            .cloned()
+            .collect();
+
+        let triples = self.triples.iter().cloned().chain(added).collect();
+        self.combined(prefixes, triples, &[(&self.locations, &self.triples, &self.triples), (&other.locations, &other.triples, &reconciled)])
+    }
+
+    /// Returns an RDF merge of this graph and `other`: their union, after renaming the blank
+    /// nodes of `other` whose labels this graph also uses, so that the blank nodes of both graphs
+    /// stay distinct.
+    ///
+    /// A renamed blank node gets its label followed by `_` and the smallest number that neither
+    /// graph uses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let a = TurtleParser::graph("_:someone foaf:name 'Alice' .")?;
+    /// let b = TurtleParser::graph("_:someone foaf:name 'Bob' .")?;
+    ///
+    /// assert_eq!(a.union(&b).triples.len(), 2);
+    /// assert_eq!(a.merge(&b).triples[1].subject.to_string(), "_:someone_1");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn merge(&self, other: &Graph) -> Graph {
+        let mut ours = HashSet::new();
+        self.triples.iter().for_each(|t| blank_labels(t, &mut ours));
+
+        let mut theirs = HashSet::new();
+        other.triples.iter().for_each(|t| blank_labels(t, &mut theirs));
+
+        let mut taken: HashSet<String> = ours.iter().chain(theirs.iter()).map(|l| l.to_string()).collect();
+        let mut labels: Vec<&str> = theirs.into_iter().filter(|l| ours.contains(l)).collect();
+        labels.sort_unstable();
+
+        let renamed: HashMap<&str, String> = labels.into_iter()
+            .map(|label| {
+                let fresh = (1..).map(|n| format!("{}_{}", label, n)).find(|l| !taken.contains(l)).unwrap();
+                taken.insert(fresh.clone());
+
+                (label, fresh)
+            })
+            .collect();
+
+        let triples: Vec<Triple> = other.triples.iter()
+            .map(|t| map_uris(t, &|u: &Uri| match renamed.get(u.name.as_str()) {
+                Some(fresh) if is_blank_node(u) => Uri::new(&u.prefix, fresh, u.uri_type),
+                _ => u.clone()
+            }))
+            .collect();
+
+        let mut locations = other.locations.clone();
+        locations.rekey(&other.triples, &triples);
+
+        let renamed = Graph {
+            triples: triples.into(),
+            locations,
+            ..other.clone()
+        };
+
+        self.union(&renamed)
+    }
+
+    /// Returns the triples of this graph that `other` also contains, compared as in
+    /// [`Graph::union()`]. The result keeps the prefixes and base of this graph.
+    pub fn intersection(&self, other: &Graph) -> Graph {
+        let theirs: HashSet<Triple> = other.triples.iter().map(|t| other.expanded(t)).collect();
+        let triples = self.triples.iter().filter(|t| theirs.contains(&self.expanded(t))).cloned().collect();
+
+        self.combined(self.prefixes.clone(), triples, &[(&self.locations, &self.triples, &self.triples)])
+    }
+
+    /// Returns the triples of this graph that `other` does not contain, compared as in
+    /// [`Graph::union()`]. The result keeps the prefixes and base of this graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let a = TurtleParser::graph("@prefix ex: <http://example.com/> . ex:John ex:knows ex:Jane, ex:Jim .")?;
+    /// let b = TurtleParser::graph("@prefix e: <http://example.com/> . e:John e:knows e:Jim .")?;
+    ///
+    /// assert_eq!(a.difference(&b).triples.len(), 1);
+    /// assert_eq!(a.intersection(&b).triples.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn difference(&self, other: &Graph) -> Graph {
+        let theirs: HashSet<Triple> = other.triples.iter().map(|t| other.expanded(t)).collect();
+        let triples = self.triples.iter().filter(|t| !theirs.contains(&self.expanded(t))).cloned().collect();
+
+        self.combined(self.prefixes.clone(), triples, &[(&self.locations, &self.triples, &self.triples)])
+    }
+}
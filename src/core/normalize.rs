@@ -0,0 +1,225 @@
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::ops::Deref;
+
+use crate::core::{ Graph, Triple, Uri, SourceLocation, uri::UriType };
+use crate::core::merge::{ is_blank_node, map_uris };
+use crate::querying::QueryBuilder;
+use crate::reasoning::{ RDFSReasoner, BaseReasoner };
+
+/// A term of a [`Graph`] that could not be made absolute by [`Graph::normalize()`], along with
+/// the triple it appears in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndefinedPrefix {
+    /// The undefined prefix label, with its colon, or [`None`] for a relative IRI in a graph
+    /// without a base.
+    pub prefix: Option<String>,
+    /// The term as it is written in the graph.
+    pub term: Uri,
+    pub triple: Triple,
+    /// Where the triple was parsed from, if the parser tracked locations.
+    pub locations: Vec<SourceLocation>
+}
+
+impl std::fmt::Display for UndefinedPrefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.prefix {
+            Some(prefix) => write!(f, "Use of prefix without first defining it: {} in `{}`", prefix, self.triple)?,
+            None => write!(f, "Relative IRI without a base: {} in `{}`", self.term.name, self.triple)?
+        }
+
+        for location in &self.locations {
+            write!(f, " at {}", location)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Every term that [`Graph::normalize()`] could not make absolute, in the order of the triples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizationError {
+    pub undefined: Vec<UndefinedPrefix>
+}
+
+impl NormalizationError {
+    /// Returns the undefined prefix labels, sorted and without duplicates.
+    pub fn prefixes(&self) -> Vec<&str> {
+        let mut prefixes: Vec<&str> = self.undefined.iter()
+            .filter_map(|u| u.prefix.as_deref())
+            .collect();
+
+        prefixes.sort_unstable();
+        prefixes.dedup();
+        prefixes
+    }
+}
+
+impl std::fmt::Display for NormalizationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self.undefined.iter().map(|u| u.to_string()).collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl std::error::Error for NormalizationError { }
+
+/// A [`Graph`] whose terms are all in canonical form: IRIs are absolute
+/// ([`UriType::Full`](crate::core::uri::UriType)), including the datatypes of literals, and blank
+/// nodes are labelled `_:` [`UriType::BlankNode`](crate::core::uri::UriType)s.
+///
+/// The only way to get one is [`Graph::normalize()`], and it only gives read access to its
+/// graph, so querying and reasoning, which are only available here, never see a mix of prefixed
+/// and absolute IRIs. The prefixes and base of the graph are kept, for serializers to compact
+/// IRIs with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NormalizedGraph {
+    graph: Graph
+}
+
+impl NormalizedGraph {
+    pub fn as_graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn into_inner(self) -> Graph {
+        self.graph
+    }
+
+    /// Starts a query over the triples of the graph and those that [`RDFSReasoner`] infers from
+    /// them in `inferrence_depth` steps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/ns/> .
+    ///     @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+    ///     ex:Cat rdfs:subClassOf ex:Animal .
+    ///     ex:Tom a ex:Cat .
+    /// "#)?;
+    ///
+    /// let animals = graph.normalize()?.start_query(1)
+    ///     .object(|o| o == "http://example.com/ns/Animal")
+    ///     .predicate(|p| p == "http://www.w3.org/1999/02/22-rdf-syntax-ns#type")
+    ///     .query();
+    /// assert_eq!(animals[0].subject.to_string(), "http://example.com/ns/Tom");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn start_query(&self, inferrence_depth: usize) -> QueryBuilder {
+        let mut triples = self.graph.triples.to_vec();
+        triples.extend(RDFSReasoner::get_inferred_triples(self, inferrence_depth));
+
+        QueryBuilder::start(triples)
+    }
+}
+
+impl Deref for NormalizedGraph {
+    type Target = Graph;
+
+    fn deref(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+impl AsRef<Graph> for NormalizedGraph {
+    fn as_ref(&self) -> &Graph {
+        &self.graph
+    }
+}
+
+impl From<NormalizedGraph> for Graph {
+    fn from(graph: NormalizedGraph) -> Self {
+        graph.graph
+    }
+}
+
+impl TryFrom<Graph> for NormalizedGraph {
+    type Error = NormalizationError;
+
+    fn try_from(graph: Graph) -> Result<Self, NormalizationError> {
+        graph.normalize()
+    }
+}
+
+impl Graph {
+    // The canonical form of a URI, or the prefix it is missing (`None` for the base)
+    fn canonical(&self, u: &Uri) -> Result<Uri, Option<String>> {
+        match u.uri_type {
+            UriType::Full => Ok(u.clone()),
+            _ if is_blank_node(u) => Ok(Uri::new("_:", &u.name, UriType::BlankNode)),
+            UriType::Relative | UriType::PrefixedWithBase => match &self.base_prefix {
+                Some(base) => Ok(Uri::new(base, &u.name, UriType::Full)),
+                None => Err(None)
+            },
+            _ => self.prefixes.expand_uri(u).map_err(|_| Some(u.prefix.clone()))
+        }
+    }
+
+    /// Returns the graph with every term in canonical form (see [`NormalizedGraph`]): prefixed
+    /// names are expanded with [`Graph::prefixes`], relative IRIs are resolved against the base,
+    /// and prefixed blank nodes become [`UriType::BlankNode`](crate::core::uri::UriType)s. Unlike
+    /// [`Graph::apply_metadata()`], the datatypes of literals are expanded as well.
+    ///
+    /// Triples that become equal are merged, along with their locations.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`NormalizationError`] listing every use of an undefined prefix, and of a
+    /// relative IRI if the graph has no base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/ns/> .
+    ///     ex:John ex:age "42"^^xsd:integer ; foaf:knows ex:Jane ; dc:creator ex:Jane .
+    /// "#)?;
+    ///
+    /// let error = graph.normalize().unwrap_err();
+    /// assert_eq!(error.prefixes(), vec!["dc:", "foaf:"]);
+    ///
+    /// let graph = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/ns/> .
+    ///     ex:John ex:age "42"^^xsd:integer .
+    /// "#)?;
+    ///
+    /// let normalized = graph.normalize()?;
+    /// assert_eq!(normalized.triples[0].to_string(),
+    ///     "http://example.com/ns/John http://example.com/ns/age \"42\"^^http://www.w3.org/2001/XMLSchema#integer .");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn normalize(&self) -> Result<NormalizedGraph, NormalizationError> {
+        let undefined = RefCell::new(Vec::new());
+
+        let normalized: Vec<Triple> = self.triples.iter()
+            .map(|t| map_uris(t, &|u: &Uri| self.canonical(u).unwrap_or_else(|prefix| {
+                undefined.borrow_mut().push(UndefinedPrefix {
+                    prefix,
+                    term: u.clone(),
+                    triple: t.clone(),
+                    locations: self.locations_of(t).to_vec()
+                });
+
+                u.clone()
+            })))
+            .collect();
+
+        let undefined = undefined.into_inner();
+        if !undefined.is_empty() {
+            return Err(NormalizationError { undefined });
+        }
+
+        let mut graph = self.clone();
+        graph.locations.rekey(&self.triples, &normalized);
+        graph.triples = normalized.into();
+
+        Ok(NormalizedGraph { graph })
+    }
+}
@@ -1,18 +1,93 @@
-use crate::core::{ Uri, uri::UriType };
+use std::cmp::Ordering;
+use std::hash::{ Hash, Hasher };
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+use crate::core::{ Uri, Triple, uri::{ self, UriType } };
+
+/// An RDF literal.
+///
+/// Two literals are equal if they have the same lexical form (whatever quotes surround it), the
+/// same datatype and the same language tag, compared case-insensitively as BCP 47 requires.
+#[derive(Debug, Clone)]
 pub struct Literal {
     pub value: String,
     pub datatype: Uri,
     pub language: Option<String>,
 }
 
-impl ToString for Literal {
-    fn to_string(&self) -> String {
+impl Literal {
+    /// The value of the literal without its surrounding quotes (`"`, `'`, `"""` or `'''`).
+    pub fn lexical_form(&self) -> &str {
+        for delimiter in &["\"\"\"", "'''", "\"", "'"] {
+            let value = &self.value;
+
+            if value.len() >= 2 * delimiter.len() && value.starts_with(delimiter) && value.ends_with(delimiter) {
+                return &value[delimiter.len()..value.len() - delimiter.len()];
+            }
+        }
+
+        &self.value
+    }
+
+    // The language tag in lower case, which is how it is compared
+    fn language_key(&self) -> Option<impl Iterator<Item = u8> + '_> {
+        self.language.as_ref().map(|l| l.bytes().map(|b| b.to_ascii_lowercase()))
+    }
+
+    /// Returns true if the datatype of the literal is `xsd:string`.
+    pub fn is_string(&self) -> bool {
+        self.datatype.name == "string" &&
+            (self.datatype.prefix == "xsd:" || self.datatype.prefix == "http://www.w3.org/2001/XMLSchema#")
+    }
+}
+
+impl PartialEq for Literal {
+    fn eq(&self, other: &Self) -> bool {
+        self.lexical_form() == other.lexical_form() &&
+            self.datatype == other.datatype &&
+            match (&self.language, &other.language) {
+                (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+                (a, b) => a.is_none() && b.is_none()
+            }
+    }
+}
+
+impl Eq for Literal { }
+
+impl PartialOrd for Literal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Literal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.lexical_form().cmp(other.lexical_form())
+            .then_with(|| self.datatype.cmp(&other.datatype))
+            .then_with(|| match (self.language_key(), other.language_key()) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (a, b) => a.is_some().cmp(&b.is_some())
+            })
+    }
+}
+
+impl Hash for Literal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lexical_form().hash(state);
+        self.datatype.hash(state);
+
+        match self.language_key() {
+            Some(language) => uri::hash_bytes(language, state),
+            None => state.write_u8(0)
+        }
+    }
+}
+
+impl std::fmt::Display for Literal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(language) = &self.language {
-            format!("{}^^{}@{}", self.value, self.datatype.to_string(), language)
+            write!(f, "{}^^{}@{}", self.value, self.datatype, language)
         } else {
-            format!("{}^^{}", self.value, self.datatype.to_string())
+            write!(f, "{}^^{}", self.value, self.datatype)
         }
     }
 }
@@ -31,47 +106,59 @@ impl From<&str> for Literal {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// The object of a [`Triple`].
+///
+/// Besides resources and literals, the object can be a triple term (RDF 1.2, or a quoted triple
+/// in RDF-star), which refers to a triple without asserting it. Triple terms are written
+/// `<<( s p o )>>` in Turtle and N-Triples.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Object {
     Literal(Literal),
-    Resource(Uri)
+    Resource(Uri),
+    Triple(Box<Triple>)
 }
 
 impl Object {
     pub fn is_literal(&self) -> bool {
-        match &self {
-            Object::Literal(_) => true,
-            _ => false
-        }
+        matches!(self, Object::Literal(_))
     }
     
     pub fn is_resource(&self) -> bool {
-        match &self {
-            Object::Resource(_) => true,
-            _ => false
-        }
+        matches!(self, Object::Resource(_))
+    }
+
+    pub fn is_triple(&self) -> bool {
+        matches!(self, Object::Triple(_))
     }
 
     pub fn literal(&self) -> Option<&Literal> {
         match &self {
-            Object::Literal(l) => Some(&l),
+            Object::Literal(l) => Some(l),
             _ => None
         }
     }
     
     pub fn resource(&self) -> Option<&Uri> {
         match &self {
-            Object::Resource(r) => Some(&r),
+            Object::Resource(r) => Some(r),
+            _ => None
+        }
+    }
+
+    pub fn triple(&self) -> Option<&Triple> {
+        match &self {
+            Object::Triple(t) => Some(t),
             _ => None
         }
     }
 }
 
-impl ToString for Object {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match &self {
-            Object::Literal(literal) => literal.to_string(),
-            Object::Resource(resource) => resource.to_string()
+            Object::Literal(literal) => write!(f, "{}", literal),
+            Object::Resource(resource) => write!(f, "{}", resource),
+            Object::Triple(t) => write!(f, "<<( {} {} {} )>>", t.subject, t.predicate, t.object)
         }
     }
 }
@@ -82,6 +169,6 @@ pub mod matches {
 
     lazy_static! {
         pub static ref WITH_DATATYPE: Regex = Regex::new(r"(.+)\^\^(.+)").unwrap();
-        pub static ref WITH_LANG: Regex = Regex::new(r"(.+)@(.{2,5})$").unwrap();
+        pub static ref WITH_LANG: Regex = Regex::new(r#"^(.*["'])@([a-zA-Z]+(?:-[a-zA-Z0-9]+)*)$"#).unwrap();
     }
 }
@@ -0,0 +1,314 @@
+use std::cell::RefCell;
+use std::collections::{ HashMap, HashSet };
+use std::str::FromStr;
+
+use crate::core::{ Graph, Triple, Object, Literal, Uri, uri::UriType };
+use crate::core::merge::{ is_blank_node, map_uris };
+use crate::parsing::{ TurtleParser, BaseParser, ParserOptions, ParserError, Parsed };
+use crate::serializing::NTriplesSerializer;
+
+/// The operation of a row of a [`Patch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `A`: adds a triple.
+    Add,
+    /// `D`: removes a triple.
+    Delete
+}
+
+impl Op {
+    fn code(&self) -> &'static str {
+        match self {
+            Op::Add => "A",
+            Op::Delete => "D"
+        }
+    }
+}
+
+/// The changes that turn one [`Graph`] into another, as computed by [`Graph::diff()`] and applied
+/// by [`Graph::apply_patch()`].
+///
+/// A patch is written in the [RDF Patch](https://afs.github.io/rdf-patch/) format: a `PA` header
+/// for each prefix, then its rows in order, `D` for a removed triple and `A` for an added one.
+///
+/// ```text
+/// PA "ex" "http://example.com/ns/" .
+/// D <http://example.com/ns/John> <http://example.com/ns/age> "41"^^<http://www.w3.org/2001/XMLSchema#integer> .
+/// A <http://example.com/ns/John> <http://example.com/ns/age> "42"^^<http://www.w3.org/2001/XMLSchema#integer> .
+/// ```
+///
+/// Besides those, parsing accepts `PD` rows, which remove a prefix, `H` headers and `TX`/`TC`
+/// transaction rows, which are ignored, and `TA`, which drops the rows of the transaction it
+/// aborts. Terms may be written as prefixed names of the declared prefixes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Patch {
+    /// The prefixes of the patched graph, keyed by their label including the colon (`"ex:"`).
+    pub prefixes: HashMap<String, String>,
+    /// The rows of the patch, in the order they are applied. A triple can be added and removed
+    /// several times.
+    pub rows: Vec<(Op, Triple)>
+}
+
+impl Patch {
+    /// Returns true if the patch neither removes nor adds triples.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The triples of the `D` rows, in order.
+    pub fn removed(&self) -> impl Iterator<Item = &Triple> {
+        self.rows.iter().filter(|(op, _)| *op == Op::Delete).map(|(_, t)| t)
+    }
+
+    /// The triples of the `A` rows, in order.
+    pub fn added(&self) -> impl Iterator<Item = &Triple> {
+        self.rows.iter().filter(|(op, _)| *op == Op::Add).map(|(_, t)| t)
+    }
+
+    fn uri(u: &Uri) -> String {
+        match u.uri_type {
+            UriType::Full => format!("<{}{}>", u.prefix, u.name),
+            UriType::BlankNode => format!("_:{}", u.name),
+            UriType::Prefixed => u.to_string(),
+            UriType::Relative | UriType::PrefixedWithBase => format!("<{}>", u.name)
+        }
+    }
+
+    fn object(o: &Object) -> String {
+        match o {
+            Object::Resource(u) => Self::uri(u),
+            Object::Literal(l) => Self::literal(l),
+            Object::Triple(t) => format!("<<( {} {} {} )>>", Self::uri(&t.subject.0), Self::uri(&t.predicate.0), Self::object(&t.object))
+        }
+    }
+
+    fn literal(l: &Literal) -> String {
+        let value = format!("\"{}\"", NTriplesSerializer::escape(l.lexical_form()));
+
+        if let Some(language) = &l.language {
+            format!("{}@{}", value, language)
+        } else if l.is_string() {
+            value
+        } else {
+            format!("{}^^{}", value, Self::uri(&l.datatype))
+        }
+    }
+
+    fn row(op: Op, t: &Triple) -> String {
+        format!("{} {} {} {} .", op.code(), Self::uri(&t.subject.0), Self::uri(&t.predicate.0), Self::object(&t.object))
+    }
+
+    // Strips the quotes or angle brackets around a term of a prefix row
+    fn unquote(term: &str) -> &str {
+        term.trim_start_matches(['"', '<']).trim_end_matches(['"', '>'])
+    }
+
+    // Expands the prefixed names of a triple read from a row with the declared prefixes, or
+    // those the parser predefines
+    fn expand(&self, t: &Triple) -> Parsed<Triple> {
+        let defaults = ParserOptions::default().prefixes;
+        let undefined = RefCell::new(None);
+
+        let expanded = map_uris(t, &|u: &Uri| match u.uri_type {
+            UriType::Prefixed if !is_blank_node(u) => {
+                match self.prefixes.get(&u.prefix).or_else(|| defaults.get(&u.prefix)) {
+                    Some(namespace) => Uri::new(namespace, &u.name, UriType::Full),
+                    None => {
+                        undefined.replace(Some(u.prefix.clone()));
+                        u.clone()
+                    }
+                }
+            },
+            _ => u.clone()
+        });
+
+        match undefined.into_inner() {
+            Some(prefix) => Err(ParserError(format!("Use of prefix without first defining it: {}", prefix))),
+            None => Ok(expanded)
+        }
+    }
+}
+
+impl std::fmt::Display for Patch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut prefixes: Vec<(&String, &String)> = self.prefixes.iter().collect();
+        prefixes.sort();
+
+        for (label, namespace) in prefixes {
+            writeln!(f, "PA \"{}\" \"{}\" .", label.trim_end_matches(':'), namespace)?;
+        }
+
+        for (op, t) in &self.rows {
+            writeln!(f, "{}", Self::row(*op, t))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Patch {
+    type Err = ParserError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut patch = Patch::default();
+        // The number of rows when the current transaction started
+        let mut transaction = 0;
+
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |e: &str| ParserError(format!("Line {}: {}", number + 1, e));
+
+            let (op, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+            if !rest.ends_with('.') {
+                return Err(error("Expected a row to end with '.'"));
+            }
+
+            match op {
+                "H" | "TC" => (),
+                "TX" => transaction = patch.rows.len(),
+                "TA" => patch.rows.truncate(transaction),
+                "PA" => {
+                    let terms: Vec<&str> = rest.trim_end_matches('.').split_whitespace().collect();
+                    if terms.len() != 2 {
+                        return Err(error("Expected a prefix and a namespace"));
+                    }
+
+                    let label = format!("{}:", Self::unquote(terms[0]).trim_end_matches(':'));
+                    patch.prefixes.insert(label, Self::unquote(terms[1]).to_string());
+                },
+                "PD" => {
+                    let label = format!("{}:", Self::unquote(rest.trim_end_matches('.').trim()).trim_end_matches(':'));
+                    patch.prefixes.remove(&label);
+                },
+                "A" | "D" => {
+                    let triples = TurtleParser::triple(rest).map_err(|e| error(&e.0))?;
+                    if triples.len() != 1 {
+                        return Err(error("Expected a single triple"));
+                    }
+
+                    let t = patch.expand(&triples[0]).map_err(|e| error(&e.0))?;
+                    patch.rows.push((if op == "A" { Op::Add } else { Op::Delete }, t));
+                },
+                op => return Err(error(&format!("Unknown operation: {}", op)))
+            }
+        }
+
+        Ok(patch)
+    }
+}
+
+impl Graph {
+    /// Returns the [`Patch`] that turns this graph into `other`.
+    ///
+    /// Triples are compared once their prefixed names and relative IRIs are expanded, and the
+    /// patch only contains expanded triples. Blank nodes of `other` are written with the label of
+    /// the blank node of this graph they correspond to: if the graphs are isomorphic, the patch is
+    /// empty; otherwise nodes are paired by the triples around them, and then by their labels.
+    /// The prefixes of the patch are those of both graphs, combined as in [`Graph::union()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # use rdf_rs::core::Patch;
+    /// # fn main() -> Result<(), ParserError> {
+    /// let before = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/ns/> .
+    ///     ex:John ex:address [ ex:city "Paris" ; ex:street "Rue de Rivoli" ] .
+    /// "#)?;
+    /// let after = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/ns/> .
+    ///     ex:John ex:address [ ex:city "Lyon" ; ex:street "Rue de Rivoli" ] .
+    /// "#)?;
+    ///
+    /// let patch = before.diff(&after);
+    /// assert_eq!(patch.removed().count(), 1);
+    /// assert!(patch.to_string().contains("A _:blank1 <http://example.com/ns/city> \"Lyon\" .\n"));
+    ///
+    /// let mut patched = before.clone();
+    /// patched.apply_patch(&patch.to_string().parse::<Patch>()?)?;
+    /// assert!(patched.is_isomorphic(&after));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff(&self, other: &Graph) -> Patch {
+        let mut seen = HashSet::new();
+        let ours: Vec<Triple> = self.triples.iter()
+            .map(|t| self.expanded(t))
+            .filter(|t| seen.insert(t.clone()))
+            .collect();
+
+        let mut seen = HashSet::new();
+        let theirs: Vec<Triple> = self.aligned(other).into_iter()
+            .filter(|t| seen.insert(t.clone()))
+            .collect();
+
+        let (ours_set, theirs_set): (HashSet<&Triple>, HashSet<&Triple>) = (ours.iter().collect(), theirs.iter().collect());
+
+        let removed = ours.iter().filter(|t| !theirs_set.contains(t)).map(|t| (Op::Delete, t.clone()));
+        let added = theirs.iter().filter(|t| !ours_set.contains(t)).map(|t| (Op::Add, t.clone()));
+
+        Patch {
+            prefixes: self.reconcile(other).0.into(),
+            rows: removed.chain(added).collect()
+        }
+    }
+
+    /// Applies a [`Patch`], one row after the other: a `D` row removes its triple, and an `A` row
+    /// adds its triple unless the graph already contains it. Then defines the prefixes of the
+    /// patch whose labels the graph does not use yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] listing the `D` rows of the patch whose triple the graph does not
+    /// contain by the time the row is reached. The graph is left unchanged in that case.
+    pub fn apply_patch(&mut self, patch: &Patch) -> Parsed<()> {
+        // The triples of the graph as they are stored, by their expanded form
+        let mut stored: HashMap<Triple, Triple> = self.triples.iter()
+            .map(|t| (self.expanded(t), t.clone()))
+            .collect();
+
+        // Check every row before changing the graph
+        let mut present: HashSet<&Triple> = stored.keys().collect();
+        let mut missing = Vec::new();
+
+        for (op, t) in &patch.rows {
+            match op {
+                Op::Add => { present.insert(t); },
+                Op::Delete => if !present.remove(t) {
+                    missing.push(format!("  {}", t));
+                }
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(ParserError(format!("Cannot remove triples the graph does not contain:\n{}", missing.join("\n"))));
+        }
+
+        for (op, t) in &patch.rows {
+            match op {
+                Op::Add => if !stored.contains_key(t) {
+                    self.triples.insert(t.clone());
+                    stored.insert(t.clone(), t.clone());
+                },
+                Op::Delete => if let Some(t) = stored.remove(t) {
+                    self.triples.remove(&t);
+                    self.locations.remove(&t);
+                }
+            }
+        }
+
+        for (label, namespace) in &patch.prefixes {
+            if !self.prefixes.contains_key(label) {
+                self.prefixes.insert(label, namespace);
+            }
+        }
+
+        Ok(())
+    }
+}
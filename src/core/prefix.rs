@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::iter::FromIterator;
+
+use crate::core::{ Uri, uri::UriType };
+use crate::parsing::{ ParserError, Parsed };
+
+// The characters a local name can escape with a backslash
+const LOCAL_ESCAPES: &str = "_~.-!$&'()*+,;=/?#@%";
+
+fn is_pn_chars_base(c: char) -> bool {
+    matches!(c,
+        'A'..='Z' | 'a'..='z' |
+        '\u{C0}'..='\u{D6}' | '\u{D8}'..='\u{F6}' | '\u{F8}'..='\u{2FF}' |
+        '\u{370}'..='\u{37D}' | '\u{37F}'..='\u{1FFF}' | '\u{200C}'..='\u{200D}' |
+        '\u{2070}'..='\u{218F}' | '\u{2C00}'..='\u{2FEF}' | '\u{3001}'..='\u{D7FF}' |
+        '\u{F900}'..='\u{FDCF}' | '\u{FDF0}'..='\u{FFFD}' | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+fn is_pn_chars_u(c: char) -> bool {
+    is_pn_chars_base(c) || c == '_'
+}
+
+fn is_pn_chars(c: char) -> bool {
+    is_pn_chars_u(c) || c == '-' || c.is_ascii_digit() || c == '\u{B7}' ||
+        ('\u{300}'..='\u{36F}').contains(&c) || ('\u{203F}'..='\u{2040}').contains(&c)
+}
+
+/// Returns true if `label` is a valid prefix label (`PN_PREFIX` in the
+/// [Turtle grammar](https://www.w3.org/TR/turtle/#grammar-production-PN_PREFIX)), without its
+/// colon. The empty label is valid.
+pub fn is_pn_prefix(label: &str) -> bool {
+    let mut chars = label.chars();
+
+    match chars.next() {
+        None => true,
+        Some(first) => {
+            let rest: Vec<char> = chars.collect();
+
+            is_pn_chars_base(first) &&
+                rest.iter().all(|c| is_pn_chars(*c) || *c == '.') &&
+                rest.last().is_none_or(|c| is_pn_chars(*c))
+        }
+    }
+}
+
+/// Returns true if `name` is a valid local name (`PN_LOCAL` in the
+/// [Turtle grammar](https://www.w3.org/TR/turtle/#grammar-production-PN_LOCAL)), as written in
+/// a prefixed name: with its percent-encoded and backslash-escaped characters. The empty name is
+/// valid.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::is_pn_local;
+/// assert!(is_pn_local("John"));
+/// assert!(is_pn_local("2024-01"));
+/// assert!(is_pn_local("a.b:c%20d\\/e"));
+/// assert!(!is_pn_local("a/b"));
+/// assert!(!is_pn_local("ends."));
+/// ```
+pub fn is_pn_local(name: &str) -> bool {
+    let chars: Vec<char> = name.chars().collect();
+    let mut i = 0;
+    let mut last_dot = false;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let valid = match c {
+            '%' => {
+                let hex = chars.get(i + 1..i + 3).is_some_and(|h| h.iter().all(|c| c.is_ascii_hexdigit()));
+                i += 2;
+                hex
+            },
+            '\\' => {
+                i += 1;
+                chars.get(i).is_some_and(|e| LOCAL_ESCAPES.contains(*e))
+            },
+            ':' => true,
+            '.' => i > 0,
+            c if i == 0 => is_pn_chars_u(c) || c.is_ascii_digit(),
+            c => is_pn_chars(c)
+        };
+
+        if !valid {
+            return false;
+        }
+
+        last_dot = c == '.';
+        i += 1;
+    }
+
+    !last_dot
+}
+
+// Removes the backslashes of the escaped characters of a local name
+fn unescape_local(name: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = name.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c)
+        }
+    }
+
+    unescaped
+}
+
+// Adds the colon to a prefix label without one
+fn label(label: &str) -> String {
+    if label.ends_with(':') { label.to_string() } else { format!("{}:", label) }
+}
+
+/// The prefixes of a [`Graph`](crate::core::Graph): labels (including their colon, `"ex:"`) and
+/// the namespace IRIs they stand for.
+///
+/// Besides looking namespaces up like a [`HashMap`], a `PrefixMap` expands prefixed names
+/// ([`PrefixMap::expand()`]) and compacts IRIs into prefixed names, using the longest namespace
+/// that leaves a valid local name ([`PrefixMap::compact()`]).
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::PrefixMap;
+/// # use rdf_rs::parsing::ParserError;
+/// # fn main() -> Result<(), ParserError> {
+/// let mut prefixes = PrefixMap::new();
+/// prefixes.insert("ex", "http://example.com/");
+/// prefixes.insert("people", "http://example.com/people/");
+///
+/// assert_eq!(prefixes.expand("people:John")?, "http://example.com/people/John");
+/// assert_eq!(prefixes.compact("http://example.com/people/John").as_deref(), Some("people:John"));
+/// assert_eq!(prefixes.compact("http://example.com/places/Paris"), None);
+///
+/// assert!(prefixes.expand("foaf:name").is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrefixMap {
+    namespaces: HashMap<String, String>
+}
+
+impl PrefixMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.namespaces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+
+    /// Returns the namespace of the prefix `label`. The trailing colon of the label is optional.
+    pub fn get(&self, label: &str) -> Option<&String> {
+        if label.ends_with(':') {
+            self.namespaces.get(label)
+        } else {
+            self.namespaces.get(&format!("{}:", label))
+        }
+    }
+
+    pub fn contains_key(&self, label: &str) -> bool {
+        self.get(label).is_some()
+    }
+
+    /// Defines (or redefines) the prefix `label`, and returns the namespace it stood for. The
+    /// trailing colon of the label is optional.
+    pub fn insert(&mut self, label: &str, namespace: &str) -> Option<String> {
+        self.namespaces.insert(self::label(label), namespace.to_string())
+    }
+
+    /// Removes the prefix `label`, and returns its namespace.
+    pub fn remove(&mut self, label: &str) -> Option<String> {
+        self.namespaces.remove(&self::label(label))
+    }
+
+    /// Iterates over the labels and their namespaces, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.namespaces.iter()
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &String> {
+        self.namespaces.keys()
+    }
+
+    pub fn namespaces(&self) -> impl Iterator<Item = &String> {
+        self.namespaces.values()
+    }
+
+    /// Returns the labels and their namespaces sorted by label, as serializers write them.
+    pub fn sorted(&self) -> Vec<(&String, &String)> {
+        let mut prefixes: Vec<(&String, &String)> = self.namespaces.iter().collect();
+        prefixes.sort();
+        prefixes
+    }
+
+    /// Expands the prefixed name `curie` (`ex:John`, with the local name as written in Turtle)
+    /// into an absolute IRI.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if `curie` is not a prefixed name, if its prefix is not defined,
+    /// or if its local name is not valid.
+    pub fn expand(&self, curie: &str) -> Parsed<String> {
+        let (label, name) = curie.split_once(':')
+            .ok_or_else(|| ParserError(format!("Not a prefixed name: {}", curie)))?;
+
+        if label == "_" {
+            return Err(ParserError(format!("A blank node cannot be expanded: {}", curie)));
+        }
+        if !is_pn_prefix(label) {
+            return Err(ParserError(format!("Invalid prefix label: {}", curie)));
+        }
+        if !is_pn_local(name) {
+            return Err(ParserError(format!("Invalid local name: {}", curie)));
+        }
+
+        let namespace = self.get(label)
+            .ok_or_else(|| ParserError(format!("Use of prefix without first defining it: {}:", label)))?;
+
+        Ok(format!("{}{}", namespace, unescape_local(name)))
+    }
+
+    /// Expands a prefixed [`Uri`] into a full one. Other URIs, including blank nodes, are
+    /// returned as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the prefix of `u` is not defined.
+    pub fn expand_uri(&self, u: &Uri) -> Parsed<Uri> {
+        match u.uri_type {
+            UriType::Prefixed if u.prefix != "_:" => self.get(&u.prefix)
+                .map(|namespace| Uri::new(namespace, &u.name, UriType::Full))
+                .ok_or_else(|| ParserError(format!("Use of prefix without first defining it: {}", u.prefix))),
+            _ => Ok(u.clone())
+        }
+    }
+
+    // The label and local name of the longest namespace `iri` starts with, such that the rest is
+    // a valid local name. Ties are broken by label, so that the result does not depend on the
+    // order of the map.
+    fn split<'a>(&'a self, iri: &'a str) -> Option<(&'a String, &'a str)> {
+        self.namespaces.iter()
+            .filter(|(_, namespace)| !namespace.is_empty())
+            .filter_map(|(label, namespace)| iri.strip_prefix(namespace.as_str()).map(|name| (label, namespace, name)))
+            .filter(|(_, _, name)| is_pn_local(name) && !name.contains('\\'))
+            .max_by(|a, b| a.1.len().cmp(&b.1.len()).then_with(|| b.0.cmp(a.0)))
+            .map(|(label, _, name)| (label, name))
+    }
+
+    /// Compacts the absolute IRI `iri` into a prefixed name, using the longest namespace it
+    /// starts with whose remainder is a valid local name. Returns [`None`] if there is none.
+    pub fn compact(&self, iri: &str) -> Option<String> {
+        self.split(iri).map(|(label, name)| format!("{}{}", label, name))
+    }
+
+    /// Compacts a full [`Uri`] into a prefixed one as [`PrefixMap::compact()`] does. Other URIs,
+    /// and IRIs that cannot be compacted, are returned as they are.
+    pub fn compact_uri(&self, u: &Uri) -> Uri {
+        if u.uri_type != UriType::Full {
+            return u.clone();
+        }
+
+        let iri = u.to_string();
+        match self.split(&iri) {
+            Some((label, name)) => Uri::new(label, name, UriType::Prefixed),
+            None => u.clone()
+        }
+    }
+}
+
+impl std::ops::Index<&str> for PrefixMap {
+    type Output = String;
+
+    fn index(&self, label: &str) -> &String {
+        self.get(label).unwrap_or_else(|| panic!("Undefined prefix: {}", label))
+    }
+}
+
+impl From<HashMap<String, String>> for PrefixMap {
+    fn from(prefixes: HashMap<String, String>) -> Self {
+        prefixes.into_iter().collect()
+    }
+}
+
+impl From<PrefixMap> for HashMap<String, String> {
+    fn from(prefixes: PrefixMap) -> Self {
+        prefixes.namespaces
+    }
+}
+
+impl<L: AsRef<str>, N: AsRef<str>> FromIterator<(L, N)> for PrefixMap {
+    fn from_iter<I: IntoIterator<Item = (L, N)>>(iter: I) -> Self {
+        let mut prefixes = PrefixMap::new();
+        for (label, namespace) in iter {
+            prefixes.insert(label.as_ref(), namespace.as_ref());
+        }
+
+        prefixes
+    }
+}
+
+impl<L: AsRef<str>, N: AsRef<str>> Extend<(L, N)> for PrefixMap {
+    fn extend<I: IntoIterator<Item = (L, N)>>(&mut self, iter: I) {
+        for (label, namespace) in iter {
+            self.insert(label.as_ref(), namespace.as_ref());
+        }
+    }
+}
+
+impl IntoIterator for PrefixMap {
+    type Item = (String, String);
+    type IntoIter = std::collections::hash_map::IntoIter<String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.namespaces.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PrefixMap {
+    type Item = (&'a String, &'a String);
+    type IntoIter = std::collections::hash_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.namespaces.iter()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::core::Uri;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Relationship(pub Uri);
 
 impl From<Uri> for Relationship {
@@ -9,9 +9,9 @@ impl From<Uri> for Relationship {
     }
 }
 
-impl ToString for Relationship {
-    fn to_string(&self) -> String {
-        format!("{}", self.0.to_string())
+impl std::fmt::Display for Relationship {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
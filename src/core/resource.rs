@@ -1,6 +1,6 @@
 use crate::core::Uri;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Resource(pub Uri);
 
 impl From<Uri> for Resource {
@@ -9,9 +9,9 @@ impl From<Uri> for Resource {
     }
 }
 
-impl ToString for Resource {
-    fn to_string(&self) -> String {
-        format!("{}", self.0.to_string())
+impl std::fmt::Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
@@ -0,0 +1,210 @@
+use std::cell::RefCell;
+use std::collections::{ HashMap, HashSet };
+
+use crate::core::{ Graph, Triple, Uri, PrefixMap, uri::UriType };
+use crate::core::merge::{ is_blank_node, map_uris };
+
+const GENID: &str = "/.well-known/genid/";
+
+/// The blank nodes a graph had and the IRIs that replaced them, as returned by
+/// [`Graph::skolemize()`] and [`Graph::deskolemize()`].
+///
+/// Each blank node keeps the form it was written in, so that de-skolemizing a skolemized graph
+/// with its table gives back the exact same graph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SkolemTable {
+    blank_nodes: HashMap<String, Uri>,
+    iris: HashMap<String, String>
+}
+
+impl SkolemTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.blank_nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blank_nodes.is_empty()
+    }
+
+    /// Returns the IRI that stands for the blank node labelled `label`.
+    pub fn iri(&self, label: &str) -> Option<&str> {
+        self.iris.get(label).map(|iri| iri.as_str())
+    }
+
+    /// Returns the blank node that `iri` stands for.
+    pub fn blank_node(&self, iri: &str) -> Option<&Uri> {
+        self.blank_nodes.get(iri)
+    }
+
+    /// Iterates over the IRIs and the blank nodes they stand for.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Uri)> {
+        self.blank_nodes.iter().map(|(iri, blank_node)| (iri.as_str(), blank_node))
+    }
+
+    fn insert(&mut self, iri: String, blank_node: Uri) {
+        self.iris.insert(blank_node.name.clone(), iri.clone());
+        self.blank_nodes.insert(iri, blank_node);
+    }
+}
+
+// Replaces the characters that cannot appear in a blank node label
+fn label(name: &str) -> String {
+    let label: String = name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect();
+
+    if label.is_empty() { "genid".into() } else { label }
+}
+
+// The absolute IRI of a URI that is not a blank node, where the graph defines its prefix
+fn absolute(u: &Uri, base: Option<&String>, prefixes: &PrefixMap) -> Option<String> {
+    match u.uri_type {
+        UriType::Full => Some(u.to_string()),
+        UriType::Prefixed if !is_blank_node(u) => prefixes.get(&u.prefix).map(|namespace| format!("{}{}", namespace, u.name)),
+        UriType::Relative | UriType::PrefixedWithBase => base.map(|base| format!("{}{}", base, u.name)),
+        _ => None
+    }
+}
+
+impl Graph {
+    // Rewrites the URIs of every triple, keeping their locations
+    fn rewrite<F: Fn(&Uri) -> Uri>(&mut self, f: F) {
+        let rewritten: Vec<Triple> = self.triples.iter().map(|t| map_uris(t, &f)).collect();
+
+        self.locations.rekey(&self.triples, &rewritten);
+        self.triples = rewritten.into();
+    }
+
+    /// Replaces every blank node by a skolem IRI `https://{authority}/.well-known/genid/{label}`,
+    /// as described in [RDF 1.1 Concepts](https://www.w3.org/TR/rdf11-concepts/#section-skolemization),
+    /// and returns which IRI replaced which blank node.
+    ///
+    /// `authority` is a host name, or an IRI with its scheme such as `http://example.com`. A
+    /// blank node keeps its label in its IRI, followed by `_` and a number if the graph already
+    /// uses the IRI.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph("ex:John foaf:knows _:jane . _:jane foaf:name 'Jane' .")?;
+    ///
+    /// let mut skolemized = graph.clone();
+    /// let table = skolemized.skolemize("example.com");
+    /// assert_eq!(skolemized.triples[0].object.to_string(), "https://example.com/.well-known/genid/jane");
+    /// assert_eq!(table.iri("jane"), Some("https://example.com/.well-known/genid/jane"));
+    ///
+    /// skolemized.deskolemize(&table);
+    /// assert_eq!(skolemized, graph);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn skolemize(&mut self, authority: &str) -> SkolemTable {
+        let authority = authority.trim_end_matches('/');
+        let namespace = if authority.contains("://") {
+            format!("{}{}", authority, GENID)
+        } else {
+            format!("https://{}{}", authority, GENID)
+        };
+
+        let used = RefCell::new(HashSet::new());
+        let blank_nodes = RefCell::new(Vec::new());
+        for t in self.triples.iter() {
+            map_uris(t, &|u: &Uri| {
+                if is_blank_node(u) {
+                    blank_nodes.borrow_mut().push(u.clone());
+                } else if let Some(iri) = absolute(u, self.base_prefix.as_ref(), &self.prefixes) {
+                    used.borrow_mut().insert(iri);
+                }
+
+                u.clone()
+            });
+        }
+
+        let mut used = used.into_inner();
+        let mut table = SkolemTable::new();
+        for blank_node in blank_nodes.into_inner() {
+            if table.iri(&blank_node.name).is_some() {
+                continue;
+            }
+
+            let iri = std::iter::once(format!("{}{}", namespace, blank_node.name))
+                .chain((1..).map(|n| format!("{}{}_{}", namespace, blank_node.name, n)))
+                .find(|iri| !used.contains(iri))
+                .unwrap();
+
+            used.insert(iri.clone());
+            table.insert(iri, blank_node);
+        }
+
+        self.rewrite(|u: &Uri| match table.iri(&u.name) {
+            Some(iri) if is_blank_node(u) => {
+                let name = &iri[namespace.len()..];
+                Uri::new(&namespace, name, UriType::Full)
+            },
+            _ => u.clone()
+        });
+
+        table
+    }
+
+    /// Replaces the skolem IRIs of `table` by the blank nodes they stand for, undoing
+    /// [`Graph::skolemize()`]. Other IRIs containing `/.well-known/genid/`, such as those
+    /// skolemized by another system, become blank nodes labelled after their last segment.
+    ///
+    /// Returns which blank node replaced which IRI.
+    pub fn deskolemize(&mut self, table: &SkolemTable) -> SkolemTable {
+        let labels = RefCell::new(HashSet::new());
+        let iris = RefCell::new(Vec::new());
+        for t in self.triples.iter() {
+            map_uris(t, &|u: &Uri| {
+                if is_blank_node(u) {
+                    labels.borrow_mut().insert(u.name.clone());
+                } else if let Some(iri) = absolute(u, self.base_prefix.as_ref(), &self.prefixes).filter(|iri| iri.contains(GENID)) {
+                    iris.borrow_mut().push(iri);
+                }
+
+                u.clone()
+            });
+        }
+
+        let mut labels = labels.into_inner();
+        labels.extend(table.iris.keys().cloned());
+
+        let mut replaced = SkolemTable::new();
+        for iri in iris.into_inner() {
+            if replaced.blank_node(&iri).is_some() {
+                continue;
+            }
+
+            let blank_node = match table.blank_node(&iri) {
+                Some(blank_node) => blank_node.clone(),
+                None => {
+                    let name = label(iri.rsplit(GENID).next().unwrap_or_default());
+                    let name = std::iter::once(name.clone())
+                        .chain((1..).map(|n| format!("{}_{}", name, n)))
+                        .find(|l| !labels.contains(l))
+                        .unwrap();
+
+                    labels.insert(name.clone());
+                    Uri::new("_:", &name, UriType::BlankNode)
+                }
+            };
+
+            replaced.insert(iri, blank_node);
+        }
+
+        let (base, prefixes) = (self.base_prefix.clone(), self.prefixes.clone());
+        self.rewrite(|u: &Uri| match absolute(u, base.as_ref(), &prefixes).and_then(|iri| replaced.blank_node(&iri)) {
+            Some(blank_node) => blank_node.clone(),
+            None => u.clone()
+        });
+
+        replaced
+    }
+}
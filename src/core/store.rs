@@ -0,0 +1,284 @@
+use std::collections::{ BTreeMap, BTreeSet, HashMap, HashSet };
+use std::iter::FromIterator;
+use std::ops::{ Deref, RangeInclusive };
+
+use crate::core::{ Resource, Relationship, Object, Triple };
+
+// The ids of the subject, predicate and object of a triple, in the order of the index
+type Key = (u32, u32, u32);
+
+/// The triples of a [`Graph`](crate::core::Graph), indexed by subject, predicate and object.
+///
+/// A store is a set: inserting a triple that is already in it does nothing, and two stores are
+/// equal if they hold the same triples, in whatever order. It keeps the order in which triples
+/// were first inserted (until one is removed, see [`TripleStore::remove()`]), and dereferences
+/// to a slice in that order, so it can be iterated and indexed like the `Vec<Triple>` it
+/// replaces. It cannot be mutated through the slice, as that would
+/// invalidate its indexes; use [`TripleStore::insert()`], [`TripleStore::remove()`] and
+/// [`TripleStore::retain()`] instead.
+///
+/// [`Graph::triples`](crate::core::Graph::triples) used to be a `Vec<Triple>`. Code written
+/// against it needs these changes:
+///
+/// * `push()` becomes [`TripleStore::insert()`], which ignores duplicates.
+/// * `append()` becomes [`Extend::extend()`].
+/// * `iter_mut()` and indexed assignment are gone: build a new store from the modified triples
+///   (`graph.triples = triples.into_iter().map(...).collect()`), or use
+///   [`TripleStore::into_vec()`] and convert back with `.into()`.
+/// * `Vec` methods that take `&self` still work through the slice.
+///
+/// Any triple pattern is answered from one of three indexes (subject-predicate-object,
+/// predicate-object-subject and object-subject-predicate) without scanning the whole store, see
+/// [`TripleStore::matching()`].
+#[derive(Clone, Default)]
+pub struct TripleStore {
+    triples: Vec<Triple>,
+    ids: HashMap<Object, u32>,
+    // The ids of the terms that were removed, for new terms to reuse
+    free: Vec<u32>,
+    spo: BTreeMap<Key, usize>,
+    pos: BTreeSet<Key>,
+    osp: BTreeSet<Key>
+}
+
+impl TripleStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn id(&self, term: &Object) -> Option<u32> {
+        self.ids.get(term).copied()
+    }
+
+    fn intern(&mut self, term: &Object) -> u32 {
+        if let Some(id) = self.id(term) {
+            return id;
+        }
+
+        let id = self.free.pop().unwrap_or((self.ids.len() + self.free.len()) as u32);
+        self.ids.insert(term.clone(), id);
+        id
+    }
+
+    // Forgets the id of a term that no triple uses anymore
+    fn release(&mut self, term: Object, id: u32) {
+        let used = self.spo.range(Self::range(id, None)).next().is_some() ||
+            self.pos.range(Self::range(id, None)).next().is_some() ||
+            self.osp.range(Self::range(id, None)).next().is_some();
+
+        if !used && self.ids.remove(&term).is_some() {
+            self.free.push(id);
+        }
+    }
+
+    fn key(&self, t: &Triple) -> Option<Key> {
+        Some((
+            self.id(&Object::Resource(t.subject.0.clone()))?,
+            self.id(&Object::Resource(t.predicate.0.clone()))?,
+            self.id(&t.object)?
+        ))
+    }
+
+    /// Adds a triple to the end of the store. Returns false if it was already in it.
+    pub fn insert(&mut self, t: Triple) -> bool {
+        let (s, p, o) = (
+            self.intern(&Object::Resource(t.subject.0.clone())),
+            self.intern(&Object::Resource(t.predicate.0.clone())),
+            self.intern(&t.object)
+        );
+
+        if self.spo.contains_key(&(s, p, o)) {
+            return false;
+        }
+
+        self.spo.insert((s, p, o), self.triples.len());
+        self.pos.insert((p, o, s));
+        self.osp.insert((o, s, p));
+        self.triples.push(t);
+
+        true
+    }
+
+    /// Removes a triple from the store without scanning it. Returns false if it was not in it.
+    ///
+    /// The last triple of the store takes the place of the removed one, so the order of the
+    /// others is not kept. Use [`TripleStore::retain()`] to remove triples in order.
+    pub fn remove(&mut self, t: &Triple) -> bool {
+        let (s, p, o) = match self.key(t) {
+            Some(key) => key,
+            None => return false
+        };
+
+        let index = match self.spo.remove(&(s, p, o)) {
+            Some(index) => index,
+            None => return false
+        };
+
+        self.pos.remove(&(p, o, s));
+        self.osp.remove(&(o, s, p));
+        self.triples.swap_remove(index);
+
+        // The triple that took its place is the only one whose position changed
+        if let Some(key) = self.triples.get(index).and_then(|moved| self.key(moved)) {
+            self.spo.insert(key, index);
+        }
+
+        self.release(Object::Resource(t.subject.0.clone()), s);
+        self.release(Object::Resource(t.predicate.0.clone()), p);
+        self.release(t.object.clone(), o);
+
+        true
+    }
+
+    /// Keeps only the triples for which `f` returns true.
+    pub fn retain<F: FnMut(&Triple) -> bool>(&mut self, mut f: F) {
+        let triples = std::mem::take(&mut self.triples);
+        *self = triples.into_iter().filter(|t| f(t)).collect();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns true if the store contains `t`, without scanning it.
+    pub fn contains(&self, t: &Triple) -> bool {
+        self.key(t).map(|key| self.spo.contains_key(&key)).unwrap_or(false)
+    }
+
+    /// Returns the triples that match a pattern, straight from the index that answers it, so
+    /// they are ordered by the terms of the index rather than by insertion. Each term that is
+    /// [`None`] matches anything.
+    ///
+    /// Blank nodes are matched by their label like any other term. For patterns where blank nodes
+    /// act as variables, see [`Triple::matches_pattern()`].
+    pub fn matching(&self, s: Option<&Resource>, p: Option<&Relationship>, o: Option<&Object>) -> impl Iterator<Item = &Triple> {
+        let id = |term: Option<Object>| match term {
+            Some(term) => self.id(&term).map(Some).ok_or(()),
+            None => Ok(None)
+        };
+
+        let ids = (
+            id(s.map(|s| Object::Resource(s.0.clone()))),
+            id(p.map(|p| Object::Resource(p.0.clone()))),
+            id(o.cloned())
+        );
+
+        // The triple with the given ids, if it is in the store
+        let triple = move |key: Key| self.spo.get(&key).map(|&i| &self.triples[i]);
+
+        let matches: Box<dyn Iterator<Item = &Triple>> = match ids {
+            // One of the terms is not in the store, so neither is any triple using it
+            (Err(_), _, _) | (_, Err(_), _) | (_, _, Err(_)) => Box::new(std::iter::empty()),
+            (Ok(s), Ok(p), Ok(o)) => match (s, p, o) {
+                (Some(s), Some(p), Some(o)) => Box::new(triple((s, p, o)).into_iter()),
+                (Some(s), p, None) => Box::new(self.spo.range(Self::range(s, p))
+                    .map(move |(_, &i)| &self.triples[i])),
+                (Some(s), None, Some(o)) => Box::new(self.osp.range(Self::range(o, Some(s)))
+                    .filter_map(move |&(o, s, p)| triple((s, p, o)))),
+                (None, Some(p), o) => Box::new(self.pos.range(Self::range(p, o))
+                    .filter_map(move |&(p, o, s)| triple((s, p, o)))),
+                (None, None, Some(o)) => Box::new(self.osp.range(Self::range(o, None))
+                    .filter_map(move |&(o, s, p)| triple((s, p, o)))),
+                (None, None, None) => Box::new(self.triples.iter())
+            }
+        };
+
+        matches
+    }
+
+    // The range of the keys of an index that start with `first` and, if given, `second`
+    fn range(first: u32, second: Option<u32>) -> RangeInclusive<Key> {
+        match second {
+            Some(second) => (first, second, 0)..=(first, second, u32::MAX),
+            None => (first, 0, 0)..=(first, u32::MAX, u32::MAX)
+        }
+    }
+
+    /// Returns the triples in the order they were inserted.
+    pub fn into_vec(self) -> Vec<Triple> {
+        self.triples
+    }
+}
+
+impl Deref for TripleStore {
+    type Target = [Triple];
+
+    fn deref(&self) -> &[Triple] {
+        &self.triples
+    }
+}
+
+impl std::fmt::Debug for TripleStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.triples.iter()).finish()
+    }
+}
+
+impl PartialEq for TripleStore {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && other.triples.iter().all(|t| self.contains(t))
+    }
+}
+
+impl Eq for TripleStore { }
+
+// A vector equals a store if it holds the same triples, in whatever order and however often
+impl PartialEq<Vec<Triple>> for TripleStore {
+    fn eq(&self, other: &Vec<Triple>) -> bool {
+        let others: HashSet<&Triple> = other.iter().collect();
+        self.len() == others.len() && others.iter().all(|t| self.contains(t))
+    }
+}
+
+impl PartialEq<TripleStore> for Vec<Triple> {
+    fn eq(&self, other: &TripleStore) -> bool {
+        other == self
+    }
+}
+
+impl Extend<Triple> for TripleStore {
+    fn extend<I: IntoIterator<Item = Triple>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(t);
+        }
+    }
+}
+
+impl FromIterator<Triple> for TripleStore {
+    fn from_iter<I: IntoIterator<Item = Triple>>(iter: I) -> Self {
+        let mut store = Self::new();
+        store.extend(iter);
+
+        store
+    }
+}
+
+impl From<Vec<Triple>> for TripleStore {
+    fn from(triples: Vec<Triple>) -> Self {
+        triples.into_iter().collect()
+    }
+}
+
+impl From<TripleStore> for Vec<Triple> {
+    fn from(store: TripleStore) -> Self {
+        store.triples
+    }
+}
+
+impl IntoIterator for TripleStore {
+    type Item = Triple;
+    type IntoIter = std::vec::IntoIter<Triple>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.triples.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TripleStore {
+    type Item = &'a Triple;
+    type IntoIter = std::slice::Iter<'a, Triple>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.triples.iter()
+    }
+}
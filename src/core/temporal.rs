@@ -0,0 +1,640 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::ops::Neg;
+use std::str::FromStr;
+
+use crate::core::Decimal;
+use crate::parsing::{ Parsed, ParserError };
+
+const SECONDS_PER_DAY: i128 = 86_400;
+
+// The largest timezone offset, in seconds. A value without a timezone could be in any timezone
+// up to this far from UTC.
+const MAX_OFFSET: i128 = 14 * 3_600;
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31
+    }
+}
+
+// The number of days from 1970-01-01 to the given date of the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u8, day: u8) -> i128 {
+    let year = year as i128;
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i128;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i128 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+// The date that is the given number of days after 1970-01-01, if its year fits an i64
+fn civil_from_days(days: i128) -> Option<(i64, u8, u8)> {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year = year_of_era + era * 400;
+
+    let year = i64::try_from(if month <= 2 { year + 1 } else { year }).ok()?;
+
+    Some((year, month as u8, day as u8))
+}
+
+// Parses a timezone (`Z`, `+hh:mm` or `-hh:mm`) as an offset in minutes
+fn parse_timezone(s: Option<&str>) -> Parsed<Option<i16>> {
+    let s = match s {
+        None | Some("") => return Ok(None),
+        Some("Z") => return Ok(Some(0)),
+        Some(s) => s
+    };
+
+    let sign = if s.starts_with('-') { -1 } else { 1 };
+    let (hours, minutes) = s[1..].split_once(':')
+        .ok_or_else(|| ParserError(format!("Invalid timezone: {}", s)))?;
+    let (hours, minutes): (i16, i16) = (hours.parse()?, minutes.parse()?);
+
+    if minutes > 59 || hours > 14 || (hours == 14 && minutes > 0) {
+        return Err(ParserError(format!("Timezone out of range: {}", s)));
+    }
+
+    Ok(Some(sign * (hours * 60 + minutes)))
+}
+
+// Parses a year of at least four digits, which only has leading zeros if it has four digits
+fn parse_year(s: &str) -> Parsed<i64> {
+    let digits = s.trim_start_matches('-');
+    if digits.len() > 4 && digits.starts_with('0') {
+        return Err(ParserError(format!("Invalid year: {}", s)));
+    }
+
+    let year = s.parse()?;
+    if year == 0 && s.starts_with('-') {
+        return Err(ParserError(format!("Invalid year: {}", s)));
+    }
+
+    Ok(year)
+}
+
+fn write_year(f: &mut std::fmt::Formatter<'_>, year: i64) -> std::fmt::Result {
+    let sign = if year < 0 { "-" } else { "" };
+    write!(f, "{}{:04}", sign, year.abs())
+}
+
+fn write_timezone(f: &mut std::fmt::Formatter<'_>, timezone: Option<i16>) -> std::fmt::Result {
+    match timezone {
+        None => Ok(()),
+        Some(0) => write!(f, "Z"),
+        Some(offset) => {
+            let sign = if offset < 0 { '-' } else { '+' };
+            write!(f, "{}{:02}:{:02}", sign, offset.abs() / 60, offset.abs() % 60)
+        }
+    }
+}
+
+fn write_time(f: &mut std::fmt::Formatter<'_>, hour: u8, minute: u8, second: Decimal) -> std::fmt::Result {
+    let padding = if second < Decimal::from(10) { "0" } else { "" };
+    write!(f, "{:02}:{:02}:{}{}", hour, minute, padding, second)
+}
+
+// Compares two instants given as seconds in their own timezone. An instant without a timezone
+// is only known to lie within 14 hours of the same local time in UTC, so comparing it with an
+// instant that has a timezone can be indeterminate.
+fn compare_instants(a: Decimal, a_timezone: Option<i16>, b: Decimal, b_timezone: Option<i16>) -> Option<Ordering> {
+    let utc = |seconds: Decimal, timezone: i16| seconds.checked_sub(&Decimal::from(timezone as i64 * 60));
+    let max_offset = Decimal::from(MAX_OFFSET);
+
+    match (a_timezone, b_timezone) {
+        (Some(a_timezone), Some(b_timezone)) => Some(utc(a, a_timezone)?.cmp(&utc(b, b_timezone)?)),
+        (None, None) => Some(a.cmp(&b)),
+        (Some(a_timezone), None) => {
+            let a = utc(a, a_timezone)?;
+
+            if a < b.checked_sub(&max_offset)? {
+                Some(Ordering::Less)
+            } else if a > b.checked_add(&max_offset)? {
+                Some(Ordering::Greater)
+            } else {
+                None
+            }
+        },
+        (None, Some(_)) => compare_instants(b, b_timezone, a, a_timezone).map(Ordering::reverse)
+    }
+}
+
+/// An `xsd:dateTime`, with an optional timezone offset in minutes.
+///
+/// A time of `24:00:00` is read as midnight of the following day. Date times are compared as
+/// instants with [`DateTime::instant_cmp()`], while equality is structural: `09:30:10Z` and
+/// `11:30:10+02:00` of the same day are the same instant, but are not equal.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::core::{ DateTime, Duration };
+/// # use rdf_rs::parsing::ParserError;
+/// # fn main() -> Result<(), ParserError> {
+/// let start: DateTime = "2024-01-31T22:00:00Z".parse()?;
+/// let end = start.checked_add(&"P1MT3H".parse()?).unwrap();
+///
+/// assert_eq!(end.to_string(), "2024-03-01T01:00:00Z");
+/// assert_eq!(end.duration_since(&start).map(|d| d.to_string()), Some("P29DT3H".to_string()));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: Decimal,
+    pub timezone: Option<i16>
+}
+
+impl DateTime {
+    // Seconds since 1970-01-01T00:00:00 in the date time's own timezone, if they fit a decimal
+    fn local_seconds(&self) -> Option<Decimal> {
+        let days = days_from_civil(self.year, self.month, self.day);
+        let seconds = (days * 24 + self.hour as i128) * 3_600 + self.minute as i128 * 60;
+
+        Decimal::from(seconds).checked_add(&self.second)
+    }
+
+    fn from_local_seconds(seconds: Decimal, timezone: Option<i16>) -> Option<Self> {
+        let (days, second_of_day) = seconds.div_rem_euclid(SECONDS_PER_DAY)?;
+        let (year, month, day) = civil_from_days(days)?;
+        let (minute_of_day, second) = second_of_day.div_rem_euclid(60)?;
+
+        Some(DateTime {
+            year,
+            month,
+            day,
+            hour: (minute_of_day / 60) as u8,
+            minute: (minute_of_day % 60) as u8,
+            second,
+            timezone
+        })
+    }
+
+    /// The date time in UTC, or itself if it has no timezone. Returns [`None`] if its year in UTC
+    /// does not fit an `i64`.
+    pub fn to_utc(&self) -> Option<Self> {
+        match self.timezone {
+            None => Some(*self),
+            Some(offset) => Self::from_local_seconds(self.local_seconds()?.checked_sub(&Decimal::from(offset as i64 * 60))?, Some(0))
+        }
+    }
+
+    /// Compares two date times as instants: date times with different timezones are equal if
+    /// they denote the same instant. Comparing a date time without a timezone with one that has a
+    /// timezone is indeterminate ([`None`]) unless they are more than 14 hours apart.
+    pub fn instant_cmp(&self, other: &Self) -> Option<Ordering> {
+        compare_instants(self.local_seconds()?, self.timezone, other.local_seconds()?, other.timezone)
+    }
+
+    /// Adds a duration following XML Schema: the months are added first, clamping the day to
+    /// the length of the resulting month, then the seconds. Returns [`None`] if the year of the
+    /// result does not fit an `i64`.
+    pub fn checked_add(&self, d: &Duration) -> Option<Self> {
+        let months = self.year as i128 * 12 + self.month as i128 - 1 + d.months as i128;
+        let (year, month) = (i64::try_from(months.div_euclid(12)).ok()?, (months.rem_euclid(12) + 1) as u8);
+        let day = self.day.min(days_in_month(year, month));
+
+        let shifted = DateTime { year, month, day, ..*self };
+        Self::from_local_seconds(shifted.local_seconds()?.checked_add(&d.seconds)?, self.timezone)
+    }
+
+    /// Subtracts a duration, see [`DateTime::checked_add()`].
+    pub fn checked_sub(&self, d: &Duration) -> Option<Self> {
+        self.checked_add(&-*d)
+    }
+
+    /// The date part of the date time.
+    pub fn date(&self) -> Date {
+        Date { year: self.year, month: self.month, day: self.day, timezone: self.timezone }
+    }
+
+    /// The time part of the date time.
+    pub fn time(&self) -> Time {
+        Time { hour: self.hour, minute: self.minute, second: self.second, timezone: self.timezone }
+    }
+
+    /// The duration from `earlier` to this date time, which is negative if `earlier` is later.
+    /// Returns [`None`] if only one of the date times has a timezone, or if the duration does not
+    /// fit a [`Decimal`].
+    pub fn duration_since(&self, earlier: &DateTime) -> Option<Duration> {
+        let seconds = |d: &DateTime| d.local_seconds()?.checked_sub(&Decimal::from(d.timezone.unwrap_or(0) as i64 * 60));
+
+        if self.timezone.is_some() != earlier.timezone.is_some() {
+            return None;
+        }
+
+        Duration::from_parts(0, seconds(self)?.checked_sub(&seconds(earlier)?)?)
+    }
+}
+
+impl FromStr for DateTime {
+    type Err = ParserError;
+
+    /// Parses the lexical form of an `xsd:dateTime`, such as `2002-05-30T09:30:10.5+02:00`.
+    fn from_str(s: &str) -> Parsed<Self> {
+        let invalid = || ParserError(format!("Invalid xsd:dateTime: {}", s));
+        let caps = matches::DATE_TIME.captures(s).ok_or_else(invalid)?;
+
+        let date = Date::new(parse_year(&caps[1])?, caps[2].parse()?, caps[3].parse()?, None).ok_or_else(invalid)?;
+        let time: Time = format!("{}:{}:{}", &caps[4], &caps[5], &caps[6]).parse().map_err(|_| invalid())?;
+        let date_time = date.at(time.hour, time.minute, time.second);
+
+        // 24:00:00 is the first instant of the next day
+        let date_time = if caps[4].starts_with("24") {
+            date_time.checked_add(&Duration::new(0, Decimal::from(SECONDS_PER_DAY))).ok_or_else(invalid)?
+        } else {
+            date_time
+        };
+
+        Ok(DateTime { timezone: parse_timezone(caps.get(7).map(|m| m.as_str()))?, ..date_time })
+    }
+}
+
+impl std::fmt::Display for DateTime {
+    /// Writes the canonical form of the date time.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_year(f, self.year)?;
+        write!(f, "-{:02}-{:02}T", self.month, self.day)?;
+        write_time(f, self.hour, self.minute, self.second)?;
+        write_timezone(f, self.timezone)
+    }
+}
+
+/// An `xsd:date`, with an optional timezone offset in minutes. Compared as the first instant of
+/// the day with [`Date::instant_cmp()`], like [`DateTime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Date {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+    pub timezone: Option<i16>
+}
+
+impl Date {
+    /// Creates a date. Returns [`None`] if the day does not exist.
+    pub fn new(year: i64, month: u8, day: u8, timezone: Option<i16>) -> Option<Self> {
+        if (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) {
+            Some(Self { year, month, day, timezone })
+        } else {
+            None
+        }
+    }
+
+    /// The date time at the given time of this date.
+    pub fn at(&self, hour: u8, minute: u8, second: Decimal) -> DateTime {
+        DateTime { year: self.year, month: self.month, day: self.day, hour, minute, second, timezone: self.timezone }
+    }
+
+    fn start(&self) -> DateTime {
+        self.at(0, 0, Decimal::from(0))
+    }
+
+    /// The duration from `earlier` to this date. Returns [`None`] if only one of the dates has a
+    /// timezone.
+    pub fn duration_since(&self, earlier: &Date) -> Option<Duration> {
+        self.start().duration_since(&earlier.start())
+    }
+
+    /// Compares the first instants of two dates, see [`DateTime::instant_cmp()`].
+    pub fn instant_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.start().instant_cmp(&other.start())
+    }
+
+    /// Adds a duration to the first instant of the date, see [`DateTime::checked_add()`].
+    pub fn checked_add(&self, d: &Duration) -> Option<Self> {
+        self.start().checked_add(d).map(|t| t.date())
+    }
+
+    /// Subtracts a duration from the first instant of the date, see
+    /// [`DateTime::checked_add()`].
+    pub fn checked_sub(&self, d: &Duration) -> Option<Self> {
+        self.checked_add(&-*d)
+    }
+}
+
+impl FromStr for Date {
+    type Err = ParserError;
+
+    /// Parses the lexical form of an `xsd:date`, such as `2002-05-30` or `2002-05-30Z`.
+    fn from_str(s: &str) -> Parsed<Self> {
+        let invalid = || ParserError(format!("Invalid xsd:date: {}", s));
+        let caps = matches::DATE.captures(s).ok_or_else(invalid)?;
+        let timezone = parse_timezone(caps.get(4).map(|m| m.as_str()))?;
+
+        Date::new(parse_year(&caps[1])?, caps[2].parse()?, caps[3].parse()?, timezone).ok_or_else(invalid)
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_year(f, self.year)?;
+        write!(f, "-{:02}-{:02}", self.month, self.day)?;
+        write_timezone(f, self.timezone)
+    }
+}
+
+/// An `xsd:time`, with an optional timezone offset in minutes. `24:00:00` is read as
+/// `00:00:00`. Times are compared as instants of the same day with [`Time::instant_cmp()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: Decimal,
+    pub timezone: Option<i16>
+}
+
+impl Time {
+    // The time on the reference date XML Schema uses to compare times
+    fn on_reference_date(&self) -> DateTime {
+        DateTime { year: 1972, month: 12, day: 31, hour: self.hour, minute: self.minute, second: self.second, timezone: self.timezone }
+    }
+
+    /// Compares two times as instants of the same day, see [`DateTime::instant_cmp()`].
+    pub fn instant_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.on_reference_date().instant_cmp(&other.on_reference_date())
+    }
+}
+
+impl FromStr for Time {
+    type Err = ParserError;
+
+    /// Parses the lexical form of an `xsd:time`, such as `13:20:00` or `13:20:00.5-05:00`.
+    fn from_str(s: &str) -> Parsed<Self> {
+        let invalid = || ParserError(format!("Invalid xsd:time: {}", s));
+        let caps = matches::TIME.captures(s).ok_or_else(invalid)?;
+
+        let (hour, minute, second): (u8, u8, Decimal) = (caps[1].parse()?, caps[2].parse()?, caps[3].parse()?);
+        let midnight = hour == 24 && minute == 0 && second == Decimal::from(0);
+
+        if (hour > 23 && !midnight) || minute > 59 || second >= Decimal::from(60) {
+            return Err(invalid());
+        }
+
+        Ok(Time {
+            hour: if midnight { 0 } else { hour },
+            minute,
+            second,
+            timezone: parse_timezone(caps.get(4).map(|m| m.as_str()))?
+        })
+    }
+}
+
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_time(f, self.hour, self.minute, self.second)?;
+        write_timezone(f, self.timezone)
+    }
+}
+
+/// An `xsd:gYear`, with an optional timezone offset in minutes. Compared as the first instant of
+/// the year with [`GYear::instant_cmp()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GYear {
+    pub year: i64,
+    pub timezone: Option<i16>
+}
+
+impl GYear {
+    /// Compares the first instants of two years, see [`DateTime::instant_cmp()`].
+    pub fn instant_cmp(&self, other: &Self) -> Option<Ordering> {
+        let start = |g: &GYear| Date { year: g.year, month: 1, day: 1, timezone: g.timezone };
+        start(self).instant_cmp(&start(other))
+    }
+}
+
+impl FromStr for GYear {
+    type Err = ParserError;
+
+    /// Parses the lexical form of an `xsd:gYear`, such as `1999` or `-0044Z`.
+    fn from_str(s: &str) -> Parsed<Self> {
+        let caps = matches::G_YEAR.captures(s)
+            .ok_or_else(|| ParserError(format!("Invalid xsd:gYear: {}", s)))?;
+
+        Ok(GYear {
+            year: parse_year(&caps[1])?,
+            timezone: parse_timezone(caps.get(2).map(|m| m.as_str()))?
+        })
+    }
+}
+
+impl std::fmt::Display for GYear {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write_year(f, self.year)?;
+        write_timezone(f, self.timezone)
+    }
+}
+
+/// An `xsd:duration`: a number of months and a number of seconds, which are both negative for a
+/// negative duration. `xsd:yearMonthDuration` and `xsd:dayTimeDuration` are durations with only
+/// months and only seconds respectively.
+///
+/// Durations are only partially ordered: `P1M` is neither shorter nor longer than `P30D`, as
+/// months have different lengths. Following XML Schema, two durations are compared by adding
+/// them to four reference date times, and are incomparable unless all four agree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Duration {
+    months: i64,
+    seconds: Decimal
+}
+
+impl Duration {
+    /// Creates a duration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `months` and `seconds` have different signs, or if `months` is `i64::MIN`,
+    /// which cannot be negated.
+    pub fn new(months: i64, seconds: Decimal) -> Self {
+        let zero = Decimal::from(0);
+        assert!(!((months < 0 && seconds > zero) || (months > 0 && seconds < zero)), "A duration cannot mix signs");
+        assert!(months != i64::MIN, "A duration cannot have i64::MIN months");
+
+        Self { months, seconds }
+    }
+
+    // A duration from its parts, if they have the same sign and can be negated
+    fn from_parts(months: i64, seconds: Decimal) -> Option<Self> {
+        let zero = Decimal::from(0);
+        let mixed = (months < 0 && seconds > zero) || (months > 0 && seconds < zero);
+
+        (!mixed && months != i64::MIN).then_some(Self { months, seconds })
+    }
+
+    /// Adds two durations. Returns [`None`] on overflow, or if the sum would have months and
+    /// seconds of different signs, as when adding `P1M` and `-P1D`.
+    pub fn checked_add(&self, other: &Duration) -> Option<Duration> {
+        Self::from_parts(self.months.checked_add(other.months)?, self.seconds.checked_add(&other.seconds)?)
+    }
+
+    /// Subtracts two durations, see [`Duration::checked_add()`].
+    pub fn checked_sub(&self, other: &Duration) -> Option<Duration> {
+        self.checked_add(&-*other)
+    }
+
+    /// The total number of months, as in the years and months of `P1Y2M` (14).
+    pub fn months(&self) -> i64 {
+        self.months
+    }
+
+    /// The total number of seconds, as in the days, hours, minutes and seconds of `P1DT1S` (86401).
+    pub fn seconds(&self) -> Decimal {
+        self.seconds
+    }
+
+    /// Returns true if the duration only has years and months, as an `xsd:yearMonthDuration`.
+    pub fn is_year_month(&self) -> bool {
+        self.seconds == Decimal::from(0)
+    }
+
+    /// Returns true if the duration only has days, hours, minutes and seconds, as an
+    /// `xsd:dayTimeDuration`.
+    pub fn is_day_time(&self) -> bool {
+        self.months == 0
+    }
+}
+
+impl FromStr for Duration {
+    type Err = ParserError;
+
+    /// Parses the lexical form of an `xsd:duration`, such as `P1Y2M3DT4H5M6.7S` or `-PT30M`.
+    fn from_str(s: &str) -> Parsed<Self> {
+        let invalid = || ParserError(format!("Invalid xsd:duration: {}", s));
+        let caps = matches::DURATION.captures(s).ok_or_else(invalid)?;
+
+        // At least one component, and a time component after the 'T'
+        if s.ends_with('P') || s.ends_with('T') {
+            return Err(invalid());
+        }
+
+        let number = |i: usize| -> Parsed<i128> {
+            caps.get(i).map(|m| m.as_str().parse()).unwrap_or(Ok(0)).map_err(|_| invalid())
+        };
+
+        let (years, months, days, hours, minutes) = (number(2)?, number(3)?, number(4)?, number(5)?, number(6)?);
+        let fraction: Decimal = caps.get(7).map(|m| m.as_str().parse()).unwrap_or_else(|| Ok(Decimal::from(0)))?;
+
+        let months = years.checked_mul(12)
+            .and_then(|m| m.checked_add(months))
+            .and_then(|m| i64::try_from(m).ok());
+        let seconds = days.checked_mul(24)
+            .and_then(|h| h.checked_add(hours))
+            .and_then(|h| h.checked_mul(60))
+            .and_then(|m| m.checked_add(minutes))
+            .and_then(|m| m.checked_mul(60))
+            .and_then(|s| Decimal::from(s).checked_add(&fraction));
+
+        let duration = months.zip(seconds)
+            .and_then(|(months, seconds)| Duration::from_parts(months, seconds))
+            .ok_or_else(|| ParserError(format!("xsd:duration out of range: {}", s)))?;
+
+        Ok(if caps.get(1).is_some() { -duration } else { duration })
+    }
+}
+
+impl std::fmt::Display for Duration {
+    /// Writes the canonical form of the duration, such as `P1Y2M` or `-P1DT2H`, or `PT0S` if it
+    /// is empty.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let zero = Decimal::from(0);
+        let negative = self.months < 0 || self.seconds < zero;
+        let (months, seconds) = if negative { (-self.months, -self.seconds) } else { (self.months, self.seconds) };
+
+        if months == 0 && seconds == zero {
+            return write!(f, "PT0S");
+        }
+
+        write!(f, "{}P", if negative { "-" } else { "" })?;
+
+        if months / 12 != 0 {
+            write!(f, "{}Y", months / 12)?;
+        }
+        if months % 12 != 0 {
+            write!(f, "{}M", months % 12)?;
+        }
+
+        // The seconds are not negative, so they can always be divided
+        let (days, second_of_day) = seconds.div_rem_euclid(SECONDS_PER_DAY).unwrap_or((0, seconds));
+        let (minute_of_day, second) = second_of_day.div_rem_euclid(60).unwrap_or((0, second_of_day));
+
+        if days != 0 {
+            write!(f, "{}D", days)?;
+        }
+        if minute_of_day != 0 || second != zero {
+            write!(f, "T")?;
+
+            if minute_of_day / 60 != 0 {
+                write!(f, "{}H", minute_of_day / 60)?;
+            }
+            if minute_of_day % 60 != 0 {
+                write!(f, "{}M", minute_of_day % 60)?;
+            }
+            if second != zero {
+                write!(f, "{}S", second)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialOrd for Duration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.is_day_time() && other.is_day_time() {
+            return Some(self.seconds.cmp(&other.seconds));
+        } else if self.is_year_month() && other.is_year_month() {
+            return Some(self.months.cmp(&other.months));
+        }
+
+        let references = [(1696, 9), (1697, 2), (1903, 3), (1903, 7)];
+        let mut orderings = references.iter().map(|&(year, month)| {
+            let reference = DateTime { year, month, day: 1, hour: 0, minute: 0, second: Decimal::from(0), timezone: Some(0) };
+            reference.checked_add(self)?.instant_cmp(&reference.checked_add(other)?)
+        });
+
+        let first = orderings.next()?;
+        if orderings.all(|o| o == first) { first } else { None }
+    }
+}
+
+impl Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Duration {
+        Duration { months: -self.months, seconds: -self.seconds }
+    }
+}
+
+pub mod matches {
+    use regex::Regex;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref DATE_TIME: Regex = Regex::new(r"^(-?\d{4,})-(\d{2})-(\d{2})T(\d{2}):(\d{2}):(\d{2}(?:\.\d+)?)(Z|[+-]\d{2}:\d{2})?$").unwrap();
+        pub static ref DATE: Regex = Regex::new(r"^(-?\d{4,})-(\d{2})-(\d{2})(Z|[+-]\d{2}:\d{2})?$").unwrap();
+        pub static ref TIME: Regex = Regex::new(r"^(\d{2}):(\d{2}):(\d{2}(?:\.\d+)?)(Z|[+-]\d{2}:\d{2})?$").unwrap();
+        pub static ref G_YEAR: Regex = Regex::new(r"^(-?\d{4,})(Z|[+-]\d{2}:\d{2})?$").unwrap();
+        pub static ref DURATION: Regex = Regex::new(r"^(-)?P(?:(\d+)Y)?(?:(\d+)M)?(?:(\d+)D)?(?:T(?:(\d+)H)?(?:(\d+)M)?(?:(\d+(?:\.\d+)?)S)?)?$").unwrap();
+    }
+}
@@ -0,0 +1,344 @@
+use std::convert::TryFrom;
+
+use crate::core::{ Uri, Literal, Object, Resource, Relationship, Triple, Graph, PrefixMap, uri::UriType };
+use crate::parsing::{ Parsed, ParserError };
+
+/// An IRI. Unlike a [`Uri`], it is always absolute: prefixed names and relative IRIs are
+/// resolved before a [`NamedNode`] is created from them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NamedNode {
+    iri: String
+}
+
+impl NamedNode {
+    /// Creates a [`NamedNode`] from an absolute IRI, without angle brackets.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if `iri` has no scheme or contains characters IRIs cannot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::NamedNode;
+    /// assert!(NamedNode::new("http://example.com/John").is_ok());
+    /// assert!(NamedNode::new("ex:John").is_ok());
+    /// assert!(NamedNode::new("John").is_err());
+    /// ```
+    pub fn new(iri: &str) -> Parsed<Self> {
+        if matches::ABSOLUTE_IRI.is_match(iri) {
+            Ok(Self { iri: iri.into() })
+        } else {
+            Err(ParserError(format!("Not an absolute IRI: {}", iri)))
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.iri
+    }
+}
+
+impl std::fmt::Display for NamedNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<{}>", self.iri)
+    }
+}
+
+/// A blank node, identified by its label within a graph.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BlankNode {
+    id: String
+}
+
+impl BlankNode {
+    /// Creates a [`BlankNode`] from its label, without the leading `_:`.
+    pub fn new(id: &str) -> Self {
+        Self { id: id.trim_start_matches("_:").into() }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+}
+
+impl std::fmt::Display for BlankNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "_:{}", self.id)
+    }
+}
+
+/// What RDF 1.1 allows as the subject of a triple.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Subject {
+    NamedNode(NamedNode),
+    BlankNode(BlankNode)
+}
+
+impl std::fmt::Display for Subject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Subject::NamedNode(node) => write!(f, "{}", node),
+            Subject::BlankNode(node) => write!(f, "{}", node)
+        }
+    }
+}
+
+/// Any RDF term, and thus what RDF 1.2 allows as the object of a triple. The datatype of a
+/// [`Literal`] term is always a full URI.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Term {
+    NamedNode(NamedNode),
+    BlankNode(BlankNode),
+    Literal(Literal),
+    /// A triple term, which refers to a triple without asserting it.
+    Triple(Box<TermTriple>)
+}
+
+impl Term {
+    pub fn is_named_node(&self) -> bool {
+        matches!(self, Term::NamedNode(_))
+    }
+
+    pub fn is_blank_node(&self) -> bool {
+        matches!(self, Term::BlankNode(_))
+    }
+
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Term::Literal(_))
+    }
+
+    pub fn is_triple(&self) -> bool {
+        matches!(self, Term::Triple(_))
+    }
+}
+
+impl std::fmt::Display for Term {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Term::NamedNode(node) => write!(f, "{}", node),
+            Term::BlankNode(node) => write!(f, "{}", node),
+            Term::Literal(literal) => write!(f, "{}", literal),
+            Term::Triple(t) => write!(f, "<<( {} {} {} )>>", t.subject, t.predicate, t.object)
+        }
+    }
+}
+
+impl From<Subject> for Term {
+    fn from(s: Subject) -> Self {
+        match s {
+            Subject::NamedNode(node) => Term::NamedNode(node),
+            Subject::BlankNode(node) => Term::BlankNode(node)
+        }
+    }
+}
+
+impl From<NamedNode> for Term {
+    fn from(n: NamedNode) -> Self {
+        Term::NamedNode(n)
+    }
+}
+
+/// A triple whose positions are typed to the terms RDF 1.2 allows in them. Created from a
+/// [`Graph`] with [`Graph::term_triples()`], or from an expanded [`Triple`] with `try_into()`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TermTriple {
+    pub subject: Subject,
+    pub predicate: NamedNode,
+    pub object: Term
+}
+
+impl std::fmt::Display for TermTriple {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} .", self.subject, self.predicate, self.object)
+    }
+}
+
+// Turns a URI into a term, expanding it with the given prefixes and base. Without prefixes or
+// base, only full URIs and blank nodes can be turned into terms.
+fn resolve(u: &Uri, prefixes: &PrefixMap, base: Option<&str>) -> Parsed<Term> {
+    match u.uri_type {
+        UriType::BlankNode => Ok(Term::BlankNode(BlankNode::new(&u.name))),
+        UriType::Prefixed if u.prefix == "_:" => Ok(Term::BlankNode(BlankNode::new(&u.name))),
+        UriType::Full => Ok(Term::NamedNode(NamedNode::new(&u.to_string())?)),
+        UriType::Prefixed => Ok(Term::NamedNode(NamedNode::new(&prefixes.expand_uri(u)?.to_string())?)),
+        UriType::Relative | UriType::PrefixedWithBase => {
+            let base = base
+                .ok_or_else(|| ParserError(format!("Relative IRI without a base: {}", u.name)))?;
+
+            Ok(Term::NamedNode(NamedNode::new(&format!("{}{}", base, u.name))?))
+        }
+    }
+}
+
+fn resolve_literal(l: &Literal, prefixes: &PrefixMap, base: Option<&str>) -> Parsed<Literal> {
+    match resolve(&l.datatype, prefixes, base)? {
+        Term::NamedNode(datatype) => Ok(Literal { datatype: datatype.into(), ..l.clone() }),
+        _ => Err(ParserError(format!("The datatype of a literal must be an IRI. Found: {}", l.datatype)))
+    }
+}
+
+fn resolve_triple(t: &Triple, prefixes: &PrefixMap, base: Option<&str>) -> Parsed<TermTriple> {
+    let Resource(subject) = &t.subject;
+    let Relationship(predicate) = &t.predicate;
+
+    let subject = match resolve(subject, prefixes, base)? {
+        Term::NamedNode(node) => Subject::NamedNode(node),
+        Term::BlankNode(node) => Subject::BlankNode(node),
+        _ => unreachable!()
+    };
+
+    let predicate = match resolve(predicate, prefixes, base)? {
+        Term::NamedNode(node) => node,
+        _ => return Err(ParserError(format!("The predicate of a triple must be an IRI. Found: {}", predicate)))
+    };
+
+    let object = match &t.object {
+        Object::Resource(object) => resolve(object, prefixes, base)?,
+        Object::Literal(literal) => Term::Literal(resolve_literal(literal, prefixes, base)?),
+        Object::Triple(t) => Term::Triple(Box::new(resolve_triple(t, prefixes, base)?))
+    };
+
+    Ok(TermTriple { subject, predicate, object })
+}
+
+impl Graph {
+    /// Returns the triples of the graph as [`TermTriple`]s, with every prefixed name and relative
+    /// IRI resolved against the graph's prefixes and base.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if a prefix is not defined, if a relative IRI is used without
+    /// a base, or if a predicate is a blank node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::{ Subject, Term, NamedNode };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/> .
+    ///     _:john ex:knows ex:Jane .
+    /// "#)?;
+    /// let triples = graph.term_triples()?;
+    ///
+    /// assert!(matches!(triples[0].subject, Subject::BlankNode(_)));
+    /// assert_eq!(triples[0].object, Term::NamedNode(NamedNode::new("http://example.com/Jane")?));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn term_triples(&self) -> Parsed<Vec<TermTriple>> {
+        self.triples.iter()
+            .map(|t| resolve_triple(t, &self.prefixes, self.base_prefix.as_deref()))
+            .collect()
+    }
+}
+
+impl TryFrom<Uri> for NamedNode {
+    type Error = ParserError;
+
+    /// Converts a full URI or a blank node. Other URIs need the prefixes and base of their graph,
+    /// see [`Graph::term_triples()`].
+    fn try_from(u: Uri) -> Parsed<Self> {
+        match resolve(&u, &PrefixMap::new(), None)? {
+            Term::NamedNode(node) => Ok(node),
+            _ => Err(ParserError(format!("Not an IRI: {}", u)))
+        }
+    }
+}
+
+impl TryFrom<Resource> for Subject {
+    type Error = ParserError;
+
+    fn try_from(r: Resource) -> Parsed<Self> {
+        match resolve(&r.0, &PrefixMap::new(), None)? {
+            Term::NamedNode(node) => Ok(Subject::NamedNode(node)),
+            Term::BlankNode(node) => Ok(Subject::BlankNode(node)),
+            _ => unreachable!()
+        }
+    }
+}
+
+impl TryFrom<Relationship> for NamedNode {
+    type Error = ParserError;
+
+    fn try_from(r: Relationship) -> Parsed<Self> {
+        NamedNode::try_from(r.0)
+    }
+}
+
+impl TryFrom<Object> for Term {
+    type Error = ParserError;
+
+    fn try_from(o: Object) -> Parsed<Self> {
+        match o {
+            Object::Resource(u) => resolve(&u, &PrefixMap::new(), None),
+            Object::Literal(l) => Ok(Term::Literal(resolve_literal(&l, &PrefixMap::new(), None)?)),
+            Object::Triple(t) => Ok(Term::Triple(Box::new(resolve_triple(&t, &PrefixMap::new(), None)?)))
+        }
+    }
+}
+
+impl TryFrom<Triple> for TermTriple {
+    type Error = ParserError;
+
+    fn try_from(t: Triple) -> Parsed<Self> {
+        resolve_triple(&t, &PrefixMap::new(), None)
+    }
+}
+
+impl From<NamedNode> for Uri {
+    /// Splits the IRI after its last `#`, `/` or `:` into a full URI.
+    fn from(n: NamedNode) -> Self {
+        let split = n.iri.rfind(['#', '/', ':']).map(|i| i + 1).unwrap_or(0);
+
+        Uri::new(&n.iri[..split], &n.iri[split..], UriType::Full)
+    }
+}
+
+impl From<BlankNode> for Uri {
+    fn from(b: BlankNode) -> Self {
+        Uri::new("_:", &b.id, UriType::BlankNode)
+    }
+}
+
+impl From<Subject> for Resource {
+    fn from(s: Subject) -> Self {
+        match s {
+            Subject::NamedNode(node) => Resource(node.into()),
+            Subject::BlankNode(node) => Resource(node.into())
+        }
+    }
+}
+
+impl From<NamedNode> for Relationship {
+    fn from(n: NamedNode) -> Self {
+        Relationship(n.into())
+    }
+}
+
+impl From<Term> for Object {
+    fn from(t: Term) -> Self {
+        match t {
+            Term::NamedNode(node) => Object::Resource(node.into()),
+            Term::BlankNode(node) => Object::Resource(node.into()),
+            Term::Literal(literal) => Object::Literal(literal),
+            Term::Triple(t) => Object::Triple(Box::new((*t).into()))
+        }
+    }
+}
+
+impl From<TermTriple> for Triple {
+    fn from(t: TermTriple) -> Self {
+        (Resource::from(t.subject), Relationship::from(t.predicate), Object::from(t.object)).into()
+    }
+}
+
+pub mod matches {
+    use regex::Regex;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref ABSOLUTE_IRI: Regex = Regex::new(r#"^[A-Za-z][A-Za-z0-9+.\-]*:[^\s<>"{}|\\^`]*$"#).unwrap();
+    }
+}
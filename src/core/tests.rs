@@ -0,0 +1,991 @@
+#![allow(dead_code, unused_imports)]
+use std::convert::TryFrom;
+
+use crate::core::*;
+use crate::assert_isomorphic;
+use crate::parsing::{ BaseParser, TurtleParser, ParserError };
+
+type TestReturn = Result<(), ParserError>;
+
+#[test]
+fn can_convert_to_terms() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        @base <http://example.com/base/> .
+        @prefix ex: <http://example.com/> .
+
+        ex:John ex:knows _:jane ;
+            ex:age "42"^^xsd:integer ;
+            ex:homepage <home> .
+    "#)?;
+    let triples = graph.term_triples()?;
+
+    let john = Subject::NamedNode(NamedNode::new("http://example.com/John")?);
+    assert!(triples.iter().all(|t| t.subject == john));
+    assert_eq!(triples[0].predicate.as_str(), "http://example.com/knows");
+    assert_eq!(triples[0].object, Term::BlankNode(BlankNode::new("jane")));
+    assert_eq!(triples[2].object, Term::NamedNode(NamedNode::new("http://example.com/base/home")?));
+
+    let age = match &triples[1].object {
+        Term::Literal(literal) => literal,
+        other => panic!("Expected a literal, found {}", other)
+    };
+    assert_eq!(age.datatype.to_string(), "http://www.w3.org/2001/XMLSchema#integer");
+
+    // Expanded triples convert back and forth
+    for term_triple in triples {
+        assert_eq!(TermTriple::try_from(Triple::from(term_triple.clone()))?, term_triple);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn cannot_convert_unresolved_terms() -> TestReturn {
+    let graph = TurtleParser::graph("ex:John ex:knows <Jane> .")?;
+
+    assert!(graph.term_triples().is_err());
+    assert!(NamedNode::try_from(graph.triples[0].predicate.clone()).is_err());
+    assert!(NamedNode::new("John").is_err());
+    assert!(NamedNode::new("http://example.com/John Smith").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_compare_triples_structurally() -> TestReturn {
+    use std::collections::{ BTreeSet, HashSet };
+    use crate::core::uri::UriType;
+
+    let full = Uri::new("http://example.com/people/", "John", UriType::Full);
+    let split = Uri::new("http://example.com/", "people/John", UriType::Prefixed);
+    assert_eq!(full, split);
+    assert_eq!([full.clone(), split].iter().collect::<HashSet<_>>().len(), 1);
+
+    let a = TurtleParser::graph(r#"ex:John ex:name "John"@EN-gb ; ex:nick 'Johnny' ."#)?;
+    let b = TurtleParser::graph(r#"ex:John ex:name "John"@en-GB ; ex:nick "Johnny" ."#)?;
+    assert_eq!(a.triples, b.triples);
+
+    let triples: Vec<Triple> = a.triples.into_iter().chain(b.triples).collect();
+    let hashed: HashSet<&Triple> = triples.iter().collect();
+    let ordered: BTreeSet<&Triple> = triples.iter().collect();
+    assert_eq!(hashed.len(), 2);
+    assert_eq!(ordered.len(), 2);
+
+    let c = TurtleParser::graph(r#"ex:John ex:name "John"@en-US ."#)?;
+    assert_ne!(c.triples[0], triples[0]);
+
+    Ok(())
+}
+
+#[test]
+fn can_intern_terms() -> TestReturn {
+    let dictionary = TermDictionary::shared();
+    let graph = TurtleParser::from_file("./test_data/simple.ttl")?;
+
+    let interned = InternedGraph::from_graph(&graph, &dictionary)?;
+    let again = InternedGraph::from_graph(&graph, &dictionary)?;
+    assert_eq!(interned.triples, again.triples);
+    assert_eq!(interned.term_triples()?, graph.term_triples()?);
+
+    // Repeated terms are only stored once
+    let distinct: std::collections::HashSet<Term> = graph.term_triples()?.into_iter()
+        .flat_map(|t| vec![t.subject.into(), t.predicate.into(), t.object])
+        .collect();
+    assert_eq!(dictionary.read().unwrap().len(), distinct.len());
+
+    let enemy = NamedNode::new("http://www.perceive.net/schemas/relationship/enemyOf")?;
+    let id = dictionary.read().unwrap().id(&enemy.clone().into());
+    assert_eq!(interned.term(interned.triples[0].predicate).as_deref(), Some(&Term::NamedNode(enemy)));
+    assert_eq!(id, Some(interned.triples[0].predicate));
+
+    Ok(())
+}
+
+#[test]
+fn can_match_triple_patterns() -> TestReturn {
+    let mut graph = TurtleParser::graph(r#"
+        ex:John ex:knows ex:Jane , ex:Jim ;
+            ex:name "John" .
+        ex:Jane ex:knows ex:Jim .
+        ex:Jim ex:name "Jim" .
+        ex:John ex:knows ex:Jane .
+    "#)?;
+
+    // The duplicate statement is only stored once
+    assert_eq!(graph.triples.len(), 5);
+
+    let john = TurtleParser::resource("ex:John")?;
+    let knows = TurtleParser::relationship("ex:knows")?;
+    let jim = TurtleParser::object("ex:Jim")?;
+    let count = |g: &Graph, s: Option<&Resource>, p: Option<&Relationship>, o: Option<&Object>| {
+        g.triples_matching(s, p, o).count()
+    };
+
+    assert_eq!(count(&graph, None, None, None), 5);
+    assert_eq!(count(&graph, Some(&john), None, None), 3);
+    assert_eq!(count(&graph, Some(&john), Some(&knows), None), 2);
+    assert_eq!(count(&graph, Some(&john), None, Some(&jim)), 1);
+    assert_eq!(count(&graph, None, Some(&knows), None), 3);
+    assert_eq!(count(&graph, None, Some(&knows), Some(&jim)), 2);
+    assert_eq!(count(&graph, None, None, Some(&jim)), 2);
+    assert_eq!(count(&graph, Some(&john), Some(&knows), Some(&jim)), 1);
+    assert_eq!(count(&graph, None, None, Some(&TurtleParser::object("ex:Nobody")?)), 0);
+
+    let mut objects: Vec<String> = graph.triples_matching(None, Some(&knows), None)
+        .map(|t| t.object.to_string())
+        .collect();
+    objects.sort();
+    assert_eq!(objects, ["ex:Jane", "ex:Jim", "ex:Jim"]);
+
+    let first = graph.triples[0].clone();
+    assert!(graph.triples.remove(&first));
+    assert!(!graph.triples.contains(&first));
+    assert_eq!(count(&graph, Some(&john), Some(&knows), None), 1);
+    assert_eq!(graph.triples_matching(None, None, Some(&jim)).next(), graph.triples.iter().find(|t| t.object == jim));
+
+    // The last triple moved into the removed one's place and is still found there
+    for t in graph.triples.iter() {
+        assert!(graph.triples.contains(t));
+        assert_eq!(graph.triples_matching(Some(&t.subject), Some(&t.predicate), Some(&t.object)).collect::<Vec<_>>(), [t]);
+    }
+
+    graph.triples.retain(|t| t.predicate != knows);
+    assert_eq!(count(&graph, None, Some(&knows), None), 0);
+    assert_eq!(count(&graph, None, None, None), 2);
+
+    // Stores with the same triples are equal whatever their order
+    let reversed: TripleStore = graph.triples.iter().rev().cloned().collect();
+    assert_eq!(reversed, graph.triples);
+    assert_eq!(graph.triples.iter().rev().cloned().collect::<Vec<_>>(), graph.triples);
+    assert_ne!(reversed, TripleStore::new());
+
+    // Terms that are no longer used give their ids to new ones
+    let mut store = TripleStore::new();
+    let triples = TurtleParser::graph("ex:a ex:b ex:c . ex:d ex:e ex:f . ex:a ex:e ex:g .")?.triples.into_vec();
+    store.extend(triples.clone());
+    assert!(store.remove(&triples[1]));
+    store.extend(TurtleParser::graph("ex:h ex:i ex:j . ex:k ex:l ex:c .")?.triples);
+
+    assert_eq!(store.len(), 4);
+    for t in store.iter() {
+        assert_eq!(store.matching(Some(&t.subject), Some(&t.predicate), Some(&t.object)).collect::<Vec<_>>(), [t]);
+        assert_eq!(store.matching(Some(&t.subject), None, None).count(), if t.subject == triples[0].subject { 2 } else { 1 });
+    }
+    assert_eq!(store.matching(None, Some(&triples[1].predicate), None).count(), 1);
+    assert_eq!(store.matching(None, None, Some(&triples[0].object)).count(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn can_read_typed_literals() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        ex:John ex:age 42 ;
+            ex:height 1.80 ;
+            ex:mass "7.5E1"^^xsd:double ;
+            ex:alive true ;
+            ex:born "1980-02-29T10:00:00+01:00"^^xsd:dateTime ;
+            ex:name "John" .
+    "#)?;
+    let literal = |i: usize| graph.triples[i].object.literal().cloned().unwrap();
+
+    assert_eq!(literal(0).as_i64(), Some(42));
+    assert_eq!(literal(1).as_decimal(), Some(Decimal::new(18, 1)));
+    assert_eq!(literal(1).as_decimal().map(|d| d.to_string()), Some("1.8".to_string()));
+    assert_eq!(literal(2).as_f64(), Some(75.0));
+    assert_eq!(literal(3).as_bool(), Some(true));
+    assert_eq!(literal(4).as_date_time().map(|d| (d.year, d.day, d.timezone)), Some((1980, 29, Some(60))));
+    assert_eq!(literal(5).as_i64(), None);
+    assert_eq!(literal(5).value()?, LiteralValue::String("John".into()));
+
+    // Equality agrees with the value-space ordering
+    assert_eq!(LiteralValue::Integer(1), LiteralValue::Decimal(Decimal::new(10, 1)));
+    assert_eq!(LiteralValue::Decimal(Decimal::new(15, 1)), LiteralValue::Double(1.5));
+    assert_ne!(LiteralValue::Double(f64::NAN), LiteralValue::Double(f64::NAN));
+    assert_ne!(LiteralValue::Integer(1), LiteralValue::Boolean(true));
+
+    // Rust values convert to literals of the matching datatype
+    assert_eq!(Literal::from(42i64), literal(0));
+    assert_eq!(Literal::from(true), literal(3));
+    assert!(Literal::from(75.0).value_eq(&literal(2)));
+
+    Ok(())
+}
+
+#[test]
+fn can_compare_literal_values() -> TestReturn {
+    let typed = |value: &str, datatype: &str| Literal {
+        value: format!("\"{}\"", value),
+        datatype: TurtleParser::uri(datatype).unwrap(),
+        language: None
+    };
+
+    assert!(typed("01", "xsd:integer").value_eq(&typed("1", "xsd:integer")));
+    assert!(typed("1", "xsd:integer").value_eq(&typed("1.0", "xsd:decimal")));
+    assert!(typed("1", "xsd:byte").value_eq(&typed("1e0", "xsd:double")));
+    assert!(!typed("1", "xsd:integer").value_eq(&typed("1", "xsd:string")));
+    assert!(typed("2002-05-30T09:30:10Z", "xsd:dateTime").value_eq(&typed("2002-05-30T11:30:10+02:00", "xsd:dateTime")));
+
+    use std::cmp::Ordering;
+    assert_eq!(typed("-5", "xsd:integer").value_cmp(&typed("0.25", "xsd:decimal")), Some(Ordering::Less));
+    assert_eq!(typed("10", "xsd:integer").value_cmp(&typed("9", "xsd:integer")), Some(Ordering::Greater));
+    assert_eq!(typed("2002-05-30T09:30:10Z", "xsd:dateTime").value_cmp(&typed("2002-05-30T09:30:10", "xsd:dateTime")), None);
+
+    Ok(())
+}
+
+#[test]
+fn can_validate_literals() -> TestReturn {
+    use crate::parsing::ParserOptions;
+
+    let typed = |value: &str, datatype: &str| Literal {
+        value: format!("\"{}\"", value),
+        datatype: TurtleParser::uri(datatype).unwrap(),
+        language: None
+    };
+
+    assert!(typed("1.5", "xsd:integer").validate().is_err());
+    assert!(typed("300", "xsd:byte").validate().is_err());
+    assert!(typed("-1", "xsd:nonNegativeInteger").validate().is_err());
+    assert!(typed("maybe", "xsd:boolean").validate().is_err());
+    assert!(typed("2001-02-29T00:00:00", "xsd:dateTime").validate().is_err());
+    assert!(typed("INF", "xsd:double").validate().is_ok());
+    assert!(typed("anything", "ex:custom").validate().is_ok());
+
+    let midnight = typed("1999-12-31T24:00:00", "xsd:dateTime").as_date_time().unwrap();
+    assert_eq!(midnight.to_string(), "2000-01-01T00:00:00");
+
+    let document = r#"ex:John ex:age "forty"^^xsd:integer ."#;
+    assert!(TurtleParser::graph(document).is_ok());
+    assert!(TurtleParser::graph_with_options(document, &ParserOptions::default().strict()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_read_temporal_literals() -> TestReturn {
+    let typed = |value: &str, datatype: &str| Literal {
+        value: format!("\"{}\"", value),
+        datatype: TurtleParser::uri(datatype).unwrap(),
+        language: None
+    };
+
+    assert_eq!(typed("2002-05-30T09:30:10.500+02:00", "xsd:dateTime").as_date_time().unwrap().to_string(), "2002-05-30T09:30:10.5+02:00");
+    assert_eq!(typed("2002-05-30-00:00", "xsd:date").as_date().unwrap().to_string(), "2002-05-30Z");
+    assert_eq!(typed("24:00:00", "xsd:time").as_time().unwrap().to_string(), "00:00:00");
+    assert_eq!(typed("-0044", "xsd:gYear").as_g_year().unwrap().year, -44);
+    assert_eq!(typed("PT36H", "xsd:dayTimeDuration").as_duration().unwrap().to_string(), "P1DT12H");
+    assert_eq!(typed("P0Y18M", "xsd:yearMonthDuration").as_duration().unwrap().to_string(), "P1Y6M");
+    assert_eq!(typed("-P0D", "xsd:duration").as_duration().unwrap().to_string(), "PT0S");
+
+    assert!(typed("2001-02-29", "xsd:date").validate().is_err());
+    assert!(typed("12:00:00+15:00", "xsd:time").validate().is_err());
+    assert!(typed("P", "xsd:duration").validate().is_err());
+    assert!(typed("P1DT", "xsd:duration").validate().is_err());
+    assert!(typed("P1M", "xsd:dayTimeDuration").validate().is_err());
+    assert!(typed("P1D", "xsd:yearMonthDuration").validate().is_err());
+
+    let duration: Duration = "P1Y2M3DT4H5M6.7S".parse()?;
+    assert_eq!(Literal::from(duration).xsd_datatype(), Some("duration"));
+    assert_eq!((duration.months(), duration.seconds()), (14, "273906.7".parse()?));
+
+    Ok(())
+}
+
+#[test]
+fn can_compare_temporal_values() -> TestReturn {
+    use std::cmp::Ordering;
+
+    let date_time = |s: &str| s.parse::<DateTime>().unwrap();
+    let duration = |s: &str| s.parse::<Duration>().unwrap();
+
+    assert_eq!(date_time("2000-01-15T12:00:00").instant_cmp(&date_time("2000-01-16T12:00:00Z")), Some(Ordering::Less));
+    assert_eq!(date_time("2000-01-15T12:00:00").instant_cmp(&date_time("2000-01-15T12:00:00Z")), None);
+    assert_eq!(date_time("2000-01-16T00:00:00-05:00").instant_cmp(&date_time("2000-01-16T05:00:00Z")), Some(Ordering::Equal));
+    assert_eq!(date_time("2000-01-16T00:00:00-05:00").to_utc().unwrap().to_string(), "2000-01-16T05:00:00Z");
+
+    // The same instant in different timezones is not equal, but their literal values are
+    let (a, b) = (date_time("2002-05-30T09:30:10Z"), date_time("2002-05-30T11:30:10+02:00"));
+    assert_ne!(a, b);
+    assert_eq!(LiteralValue::DateTime(a), LiteralValue::DateTime(b));
+
+    let date = |s: &str| s.parse::<Date>().unwrap();
+    assert_eq!(date("2002-05-30+14:00").instant_cmp(&date("2002-05-29-10:00")), Some(Ordering::Equal));
+
+    let time = |s: &str| s.parse::<Time>().unwrap();
+    assert_eq!(time("23:00:00+00:00").instant_cmp(&time("01:00:00+02:00")), Some(Ordering::Greater));
+
+    // Out of range instants are not comparable rather than overflowing
+    let far = date_time("9223372036854775807-12-31T23:59:59.000000000000000000000000000001Z");
+    assert_eq!(far.instant_cmp(&date_time("2000-01-01T00:00:00Z")), None);
+
+    assert_eq!(duration("P1M").partial_cmp(&duration("P30D")), None);
+    assert_eq!(duration("P1M").partial_cmp(&duration("P32D")), Some(Ordering::Less));
+    assert_eq!(duration("P1Y").partial_cmp(&duration("P364D")), Some(Ordering::Greater));
+    assert_eq!(duration("PT24H").partial_cmp(&duration("P1D")), Some(Ordering::Equal));
+
+    Ok(())
+}
+
+#[test]
+fn can_add_durations() -> TestReturn {
+    let date_time = |s: &str| s.parse::<DateTime>().unwrap();
+    let date = |s: &str| s.parse::<Date>().unwrap();
+    let duration = |s: &str| s.parse::<Duration>().unwrap();
+
+    let sum = |a: Option<DateTime>| a.unwrap().to_string();
+    assert_eq!(sum(date_time("2000-01-12T12:13:14Z").checked_add(&duration("P1Y3M5DT7H10M3.3S"))), "2001-04-17T19:23:17.3Z");
+    assert_eq!(sum(date_time("2000-03-30T00:00:00").checked_sub(&duration("P1M"))), "2000-02-29T00:00:00");
+    assert_eq!(sum(date_time("1999-12-31T23:00:00").checked_add(&duration("PT1H"))), "2000-01-01T00:00:00");
+    assert_eq!(date("2000-01-31").checked_add(&duration("P1M")).unwrap().to_string(), "2000-02-29");
+    assert_eq!(date("0001-01-01").checked_sub(&duration("P1D")).unwrap().to_string(), "0000-12-31");
+    assert_eq!(date("9223372036854775807-01-01").checked_add(&duration("P1Y")), None);
+
+    assert_eq!(duration("P1D").checked_add(&duration("PT12H")).unwrap().to_string(), "P1DT12H");
+    assert_eq!(duration("P1Y").checked_sub(&duration("P13M")).unwrap().to_string(), "-P1M");
+    // Months and seconds of different signs, and overflows
+    assert_eq!(duration("P1M").checked_add(&duration("-P1D")), None);
+    assert_eq!(duration("P1M").checked_sub(&duration("P1D")), None);
+    assert_eq!(duration("P9223372036854775807M").checked_add(&duration("P1M")), None);
+    assert_eq!((-Duration::new(i64::MAX, Decimal::from(0))).months(), -i64::MAX);
+    assert!("P99999999999999999999999999999999999D".parse::<Duration>().is_err());
+    assert!("P9999999999999999999999Y".parse::<Duration>().is_err());
+
+    // Decimals overflow into None rather than panicking
+    let max: Decimal = "99999999999999999999999999999999999999".parse()?;
+    assert_eq!(max.checked_add(&max), None);
+    assert_eq!(max.checked_sub(&-max), None);
+    assert!("0.000000000000000000000000000000000000001".parse::<Decimal>().is_err());
+
+    // Every decimal can be negated, as none has i128::MIN as its digits
+    assert_eq!(Decimal::checked_new(i128::MIN, 2), None);
+    assert_eq!(-Decimal::new(i128::MAX, 2), Decimal::new(-i128::MAX, 2));
+
+    let since = date("2000-03-01").duration_since(&date("2000-02-01")).unwrap();
+    assert_eq!(since.to_string(), "P29D");
+    assert!(date_time("2000-01-01T00:00:00Z").duration_since(&date_time("2000-01-01T00:00:00")).is_none());
+
+    Ok(())
+}
+
+#[test]
+fn can_validate_language_tags() -> TestReturn {
+    use crate::parsing::ParserOptions;
+
+    for tag in &["de", "zh-yue-HK", "sr-Latn-RS", "es-419", "sl-rozaj-biske", "en-a-bbb-x-a-ccc", "x-whatever", "i-klingon"] {
+        assert!(tag.parse::<LanguageTag>().is_ok(), "{} should be valid", tag);
+    }
+    for tag in &["", "a", "abcdefghi", "en--US", "de-419-DE", "sl-rozaj-rozaj", "en-a-bbb-a-ccc", "en-a", "en-x"] {
+        assert!(tag.parse::<LanguageTag>().is_err(), "{} should be invalid", tag);
+    }
+
+    assert_eq!("SGN-be-fr".parse::<LanguageTag>()?.as_str(), "sgn-BE-FR");
+    assert_eq!("az-CYRL-az-U-NU-ARAB".parse::<LanguageTag>()?.as_str(), "az-Cyrl-AZ-u-nu-arab");
+
+    let graph = TurtleParser::graph(r#"ex:Tom ex:said "x"@en-US-x-twain, "y"@EN ."#)?;
+    let languages: Vec<_> = graph.triples.iter().filter_map(|t| t.object.literal()?.language.clone()).collect();
+    assert_eq!(languages, vec!["en-US-x-twain", "en"]);
+
+    let document = r#"ex:Tom ex:said "x"@abcdefghi ."#;
+    assert!(TurtleParser::graph(document).is_ok());
+    assert!(TurtleParser::graph_with_options(document, &ParserOptions::default().strict()).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_match_language_ranges() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        ex:Colour rdfs:label "Colour"@en-GB, "Color"@en-US, "Farbe"@de-Latn-DE, "Couleur"@fr, "Colour" .
+    "#)?;
+    let labels: Vec<&Literal> = graph.triples.iter().filter_map(|t| t.object.literal()).collect();
+    let matching = |range: &str| labels.iter()
+        .filter(|l| l.matches_language(range))
+        .map(|l| l.lexical_form())
+        .collect::<Vec<_>>();
+
+    assert_eq!(matching("en-*"), vec!["Colour", "Color"]);
+    assert_eq!(matching("*-US"), vec!["Color"]);
+    assert_eq!(matching("de-DE"), vec!["Farbe"]);
+    assert_eq!(matching("*").len(), 4);
+    assert!(!labels[2].matches_language_basic("de-DE"));
+
+    let label = |ranges: &[&str]| Literal::lookup_language(labels.iter().copied(), ranges).map(|l| l.lexical_form());
+    assert_eq!(label(&["en-GB-oxendict", "en"]), Some("Colour"));
+    assert_eq!(label(&["fr-CA", "en"]), Some("Couleur"));
+    assert_eq!(label(&["*", "it"]), None);
+
+    // Ranges and tags are not necessarily ASCII, and a range may end inside a character of the tag
+    assert!(!basic_filter("e", "éa"));
+    assert!(!basic_filter("é", "e"));
+    assert!(basic_filter("é", "é-a"));
+    assert!(!extended_filter("e-*", "é-a"));
+
+    Ok(())
+}
+
+#[test]
+fn can_convert_triple_terms() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/> .
+        ex:Alice ex:claims <<( _:someone ex:age 23 )>> .
+    "#)?;
+    let triples = graph.term_triples()?;
+
+    let claim = match &triples[0].object {
+        Term::Triple(t) => t,
+        other => panic!("Expected a triple term, found {}", other)
+    };
+    assert_eq!(claim.subject, Subject::BlankNode(BlankNode::new("someone")));
+    assert_eq!(claim.predicate, NamedNode::new("http://example.com/age")?);
+
+    let round_trip = TermTriple::try_from(Triple::from(triples[0].clone()))?;
+    assert_eq!(round_trip, triples[0]);
+
+    // Blank nodes within triple terms match anything
+    let mut graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/> .
+        ex:Alice ex:claims <<( _:x ex:age 23 )>> , <<( ex:Bob ex:age 23 )>> , <<( ex:Bob ex:age 24 )>> .
+    "#)?;
+    graph.apply_metadata()?;
+
+    let (pattern, claim, other) = (&graph.triples[0], &graph.triples[1], &graph.triples[2]);
+    assert!(claim.matches_pattern(pattern));
+    assert!(!other.matches_pattern(pattern));
+
+    Ok(())
+}
+
+#[test]
+fn can_combine_graphs() -> TestReturn {
+    let a = TurtleParser::graph(r#"
+        @base <http://example.com/base/> .
+        @prefix ex: <http://example.com/> .
+        ex:John ex:knows ex:Jane , <Jim> .
+        _:b ex:name "Bob" .
+    "#)?;
+    let b = TurtleParser::graph(r#"
+        @base <http://example.com/other/> .
+        @prefix e: <http://example.com/> .
+        e:John e:knows e:Jane , <Jim> .
+        _:b e:name "Bobby" .
+    "#)?;
+
+    // ex:Jane and e:Jane are the same IRI, <Jim> is relative to different bases
+    let union = a.union(&b);
+    assert_eq!(union.triples.len(), 5);
+    assert_eq!(union.triples[3].object.resource().unwrap().to_string(), "http://example.com/other/Jim");
+    assert_eq!(union.prefixes["e:"], "http://example.com/");
+
+    assert_eq!(a.intersection(&b).triples.len(), 1);
+    assert_eq!(a.difference(&b).triples.len(), 2);
+    assert_eq!(a.difference(&a).triples.len(), 0);
+
+    // Blank nodes are shared by the union, but kept apart by the merge
+    let names = |g: &Graph| g.triples.iter().filter(|t| t.predicate.to_string().ends_with("name")).map(|t| t.subject.to_string()).collect::<Vec<_>>();
+    assert_eq!(names(&union), vec!["_:b", "_:b"]);
+    assert_eq!(names(&a.merge(&b)), vec!["_:b", "_:b_1"]);
+
+    Ok(())
+}
+
+#[test]
+fn can_merge_graphs_with_conflicting_prefixes() -> TestReturn {
+    let a = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/a/> .
+        @prefix ex1: <http://example.com/one/> .
+        _:x ex:knows _:x_1 .
+    "#)?;
+    let b = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/b/> .
+        ex:Claim ex:about <<( _:x ex:knows ex:Bob )>> .
+        _:x ex:name "X" .
+    "#)?;
+
+    let conflicts = a.prefix_conflicts(&b);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].namespace, "http://example.com/a/");
+    assert_eq!(conflicts[0].other_namespace, "http://example.com/b/");
+    assert_eq!(conflicts[0].renamed_to, "ex2:");
+
+    let merged = a.merge(&b);
+    assert_eq!(merged.prefixes["ex:"], "http://example.com/a/");
+    assert_eq!(merged.prefixes["ex2:"], "http://example.com/b/");
+    assert_eq!(merged.triples[1].to_string(), "ex2:Claim ex2:about <<( _:x_2 ex2:knows ex2:Bob )>> .");
+    assert_eq!(merged.triples[2].subject.to_string(), "_:x_2");
+
+    Ok(())
+}
+
+#[test]
+fn can_check_isomorphism() -> TestReturn {
+    // Two triangles and a hexagon: every blank node looks the same to its neighbours
+    let triangles = TurtleParser::graph(r#"
+        _:a ex:next _:b . _:b ex:next _:c . _:c ex:next _:a .
+        _:d ex:next _:e . _:e ex:next _:f . _:f ex:next _:d .
+    "#)?;
+    let hexagon = TurtleParser::graph(r#"
+        _:a ex:next _:b . _:b ex:next _:c . _:c ex:next _:d .
+        _:d ex:next _:e . _:e ex:next _:f . _:f ex:next _:a .
+    "#)?;
+    let relabelled = TurtleParser::graph(r#"
+        _:x ex:next _:y . _:z ex:next _:x . _:y ex:next _:z .
+        _:u ex:next _:w . _:v ex:next _:u . _:w ex:next _:v .
+    "#)?;
+
+    assert!(!triangles.is_isomorphic(&hexagon));
+    assert!(triangles.is_isomorphic(&relabelled));
+    assert_isomorphic!(triangles, relabelled);
+
+    let mapping = triangles.isomorphism(&relabelled).unwrap();
+    assert_eq!(mapping.len(), 6);
+    assert_eq!(mapping["a"] == "x", mapping["b"] == "y");
+
+    // Blank nodes within triple terms are mapped too, and prefixed names match their IRIs
+    let a = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/ns/> .
+        ex:Alice ex:claims <<( _:someone ex:age 23 )>> .
+        _:someone ex:name "Bob" .
+    "#)?;
+    let b = TurtleParser::graph(r#"
+        _:b0 <http://example.com/ns/name> "Bob" .
+        <http://example.com/ns/Alice> <http://example.com/ns/claims> <<( _:b0 <http://example.com/ns/age> 23 )>> .
+    "#)?;
+    assert_isomorphic!(a, b);
+
+    let diff = a.isomorphism_diff(&triangles).unwrap();
+    assert!(diff.lines().any(|l| l.starts_with("- ") && l.contains("Bob")));
+    assert!(diff.lines().any(|l| l.starts_with("+ ") && l.contains("ex:next")));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "`left` is isomorphic to `right`")]
+fn isomorphism_assertion_shows_diff() {
+    let a = TurtleParser::graph("_:a foaf:knows _:b .").unwrap();
+    let b = TurtleParser::graph("_:a foaf:knows _:a .").unwrap();
+
+    assert_isomorphic!(a, b);
+}
+
+#[test]
+fn can_check_isomorphism_of_many_blank_nodes() -> TestReturn {
+    // The search does not recurse once per blank node, so large graphs do not overflow the stack
+    let statements = |prefix: &str| (0..20_000).map(|i| format!("_:{prefix}{i} ex:p ex:o .\n")).collect::<String>();
+    let a = TurtleParser::graph(&statements("a"))?;
+    let b = TurtleParser::graph(&statements("b"))?;
+
+    assert!(a.is_isomorphic(&b));
+    assert!(a.diff(&b).is_empty());
+
+    // Every node of a chain gets a color of its own, so it is mapped without searching
+    let chain = |prefix: &str| (0..50).map(|i| format!("_:{prefix}{i} ex:next _:{prefix}{} .\n", i + 1)).collect::<String>();
+    assert!(TurtleParser::graph(&chain("a"))?.is_isomorphic(&TurtleParser::graph(&chain("b"))?));
+    assert!(!TurtleParser::graph(&chain("a"))?.is_isomorphic(&TurtleParser::graph(&chain("b").replace("_:b50", "_:b0"))?));
+
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "A decimal cannot have i128::MIN as its digits")]
+fn decimals_reject_the_minimum_digits() {
+    Decimal::new(i128::MIN, 0);
+}
+
+#[test]
+#[should_panic(expected = "A duration cannot have i64::MIN months")]
+fn durations_reject_the_minimum_months() {
+    Duration::new(i64::MIN, Decimal::from(0));
+}
+
+#[test]
+fn can_diff_and_patch_graphs() -> TestReturn {
+    let before = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/ns/> .
+        ex:John ex:knows [ ex:name "Jane" ] , [ ex:name "Jim" ; ex:age 30 ] .
+    "#)?;
+    // The blank nodes are numbered the other way round
+    let after = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/ns/> .
+        ex:John ex:knows [ ex:name "Jim" ; ex:age 31 ] , [ ex:name "Jane" ] .
+    "#)?;
+
+    assert!(before.diff(&before.clone()).is_empty());
+
+    let patch = before.diff(&after);
+    let (removed, added): (Vec<&Triple>, Vec<&Triple>) = (patch.removed().collect(), patch.added().collect());
+    assert_eq!((removed.len(), added.len()), (1, 1));
+    assert_eq!(removed[0].subject, added[0].subject);
+    assert_eq!(added[0].subject.to_string(), "_:blank2");
+
+    let mut patched = before.clone();
+    patched.apply_patch(&patch)?;
+    assert_isomorphic!(patched, after);
+
+    // Removing a triple the graph does not contain fails without changing the graph
+    let error = patched.apply_patch(&patch).unwrap_err();
+    assert!(error.0.contains("30"));
+    assert_isomorphic!(patched, after);
+
+    // Rows are applied in order
+    let t = "<http://example.com/ns/a> <http://example.com/ns/b> <http://example.com/ns/c> .";
+    let mut empty = TurtleParser::graph("")?;
+    empty.apply_patch(&format!("A {}\nD {}", t, t).parse()?)?;
+    assert!(empty.triples.is_empty());
+
+    let mut graph = TurtleParser::graph(t)?;
+    graph.apply_patch(&format!("D {}\nA {}", t, t).parse()?)?;
+    assert_eq!(graph.triples.len(), 1);
+
+    assert!(graph.apply_patch(&format!("D {}\nD {}", t, t).parse()?).is_err());
+    assert_eq!(graph.triples.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn can_parse_patches() -> TestReturn {
+    let patch: Patch = r#"
+        # An aborted transaction, then a committed one
+        H id <uuid:0d8e6e4f> .
+        TX .
+        A _:a <http://example.com/ns/name> "Ignored" .
+        TA .
+        PA "ex" "http://example.com/ns/" .
+        PA foaf: <http://xmlns.com/foaf/0.1/> .
+        PD "foaf" .
+        TX .
+        D ex:John ex:age 41 .
+        A ex:John ex:age 42 .
+        A ex:Census ex:states <<( ex:John ex:age 42 )>> .
+        TC .
+    "#.parse()?;
+
+    assert_eq!(patch.prefixes.len(), 1);
+    let ops: Vec<Op> = patch.rows.iter().map(|(op, _)| *op).collect();
+    assert_eq!(ops, [Op::Delete, Op::Add, Op::Add]);
+
+    let added: Vec<&Triple> = patch.added().collect();
+    assert_eq!(added[0].subject.0.prefix, "http://example.com/ns/");
+    assert_eq!(added[0].object.literal().unwrap().datatype.prefix, "http://www.w3.org/2001/XMLSchema#");
+    assert_eq!(added[1].object.triple().unwrap().subject.0.prefix, "http://example.com/ns/");
+
+    let round_trip: Patch = patch.to_string().parse()?;
+    assert_eq!(round_trip.rows[..2], patch.rows[..2]);
+
+    assert!("X ex:a ex:b ex:c .".parse::<Patch>().is_err());
+    assert!("A foaf:a foaf:b foaf:c .".parse::<Patch>().unwrap_err().0.contains("foaf:"));
+
+    Ok(())
+}
+
+#[test]
+fn can_skolemize_blank_nodes() -> TestReturn {
+    let options = crate::parsing::ParserOptions::default().with_locations(Some("people.ttl"));
+    let mut graph = TurtleParser::graph_with_options(r#"
+        @prefix ex: <http://example.com/ns/> .
+        ex:John ex:knows [ ex:name "Jane" ] .
+        ex:Alice ex:claims <<( _:x ex:age 23 )>> .
+    "#, &options)?;
+    let genid = Uri::new("http://example.com/.well-known/genid/", "x", crate::core::uri::UriType::Full);
+    graph.triples.insert((TurtleParser::resource("ex:Old")?, TurtleParser::relationship("ex:sameAs")?, Object::Resource(genid)).into());
+    let mut skolemized = graph.clone();
+    let table = skolemized.skolemize("http://example.com/");
+    assert_eq!(table.len(), 2);
+
+    // The IRI the graph already uses is not reused
+    let x = table.iri("x").unwrap();
+    assert_eq!(x, "http://example.com/.well-known/genid/x_1");
+    assert_eq!(table.blank_node(x).unwrap().to_string(), "_:x");
+    assert_eq!(skolemized.triples[2].object.triple().unwrap().subject.to_string(), x);
+    assert_eq!(skolemized.locations_of(&skolemized.triples[0]), graph.locations_of(&graph.triples[0]));
+
+    let mut round_trip = skolemized.clone();
+    let replaced = round_trip.deskolemize(&table);
+    assert_eq!(replaced.len(), 3);
+    assert_eq!(round_trip.triples[..3], graph.triples[..3]);
+    assert_eq!(round_trip.locations_of(&round_trip.triples[0]), graph.locations_of(&graph.triples[0]));
+
+    // The IRI that was not in the table becomes a blank node with a label of its own
+    let foreign = round_trip.triples[3].object.resource().unwrap();
+    assert_eq!(foreign.name, "x_1");
+    assert_eq!(replaced.blank_node("http://example.com/.well-known/genid/x").unwrap(), foreign);
+
+    Ok(())
+}
+
+#[test]
+fn can_lean_graphs() -> TestReturn {
+    // What rdfs1 leaves behind: a blank node for each occurrence of the same literal
+    let graph = TurtleParser::graph(r#"
+        ex:John ex:name _:blank1 , _:blank2 .
+        _:blank1 rdf:type xsd:string .
+        _:blank2 rdf:type xsd:string .
+    "#)?;
+
+    for lean in [graph.lean(), graph.approximate_lean()] {
+        assert_eq!(lean.triples.len(), 2);
+        assert!(lean.is_lean());
+    }
+    assert!(!graph.is_lean());
+
+    // A cycle of blank nodes is only redundant as a whole
+    let graph = TurtleParser::graph(r#"
+        ex:a ex:p ex:a .
+        _:x ex:p _:y .
+        _:y ex:p _:x .
+    "#)?;
+
+    assert_eq!(graph.lean().triples.len(), 1);
+    assert_eq!(graph.approximate_lean().triples.len(), 3);
+
+    // Neither an IRI nor a longer path can stand for a blank node that says more
+    let graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/ns/> .
+        ex:John ex:knows [ ex:name "Jane" ; ex:age 30 ] , [ ex:name "Jane" ] .
+        <http://example.com/ns/John> <http://example.com/ns/knows> ex:Jane .
+    "#)?;
+
+    let lean = graph.lean();
+    assert_eq!(lean.triples.len(), 4);
+    assert!(lean.triples.iter().all(|t| !t.object.to_string().contains("blank2")));
+    assert_eq!(lean.prefixes, graph.prefixes);
+    assert_isomorphic!(lean, graph.approximate_lean());
+
+    // A long path of blank nodes that copies a path of IRIs is searched for without recursion
+    let length = 3000;
+    let mut document = String::from("@prefix ex: <http://example.com/ns/> .\n");
+    for i in 0..length {
+        document += &format!("ex:n{} ex:next ex:n{} .\n", i, i + 1);
+    }
+    document += "ex:n0 ex:next _:b1 .\n";
+    for i in 1..length - 1 {
+        document += &format!("_:b{} ex:next _:b{} .\n", i, i + 1);
+    }
+    document += &format!("_:b{} ex:next ex:n{} .\n", length - 1, length);
+
+    let graph = TurtleParser::graph(&document)?;
+    assert_eq!(graph.lean().triples.len(), length);
+    assert_eq!(graph.approximate_lean().triples.len(), 2 * length);
+
+    Ok(())
+}
+
+#[test]
+fn can_read_and_build_lists() -> TestReturn {
+    let mut graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/ns/> .
+        ex:basket ex:contains ( ex:apple "banana" ( 1 2 ) ) .
+        ex:bag ex:contains () .
+        _:expanded rdf:first 1 ; rdf:rest <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .
+    "#)?;
+    let head = graph.triples.iter().find(|t| t.predicate.0.name == "contains").unwrap().object.resource().unwrap().clone();
+
+    let items = graph.list(&head)?;
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].resource().unwrap().name, "apple");
+    assert_eq!(graph.list(items[2].resource().unwrap())?.len(), 2);
+
+    let nil = TurtleParser::resource("rdf:nil")?.0;
+    assert_eq!(graph.list(&nil)?, vec![]);
+    assert_eq!(graph.triples.iter().find(|t| t.subject.0.name == "bag").unwrap().object.resource(), Some(&nil));
+
+    // A list that ends with the expanded IRI of rdf:nil
+    let expanded = Uri::new("_:", "expanded", crate::core::uri::UriType::Prefixed);
+    assert_eq!(graph.list(&expanded)?.len(), 1);
+
+    let mut built = graph.clone();
+    let copy = built.insert_list(items.clone());
+    assert_eq!(copy.name, "list1");
+    assert_eq!(built.list(&copy)?, items);
+
+    // Removing the outer list keeps the inner one
+    let triples = graph.triples.len();
+    assert_eq!(graph.remove_list(&head)?, items);
+    assert_eq!(graph.triples.len(), triples - 6);
+    assert!(graph.list(&head).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_detect_malformed_lists() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        _:cycle rdf:first 1 ; rdf:rest _:cycle2 .
+        _:cycle2 rdf:first 2 ; rdf:rest _:cycle .
+        _:branch rdf:first 1 ; rdf:rest rdf:nil , _:branch2 .
+        _:branch2 rdf:first 2 ; rdf:rest rdf:nil .
+        _:open rdf:first 1 ; rdf:rest _:missing .
+        _:empty rdf:rest rdf:nil .
+    "#)?;
+    let error = |label: &str| graph.list(&Uri::new("_:", label, crate::core::uri::UriType::Prefixed)).unwrap_err().0;
+
+    assert!(error("cycle").contains("cyclic"));
+    assert!(error("branch").contains("branches"));
+    assert!(error("open").contains("does not end with rdf:nil"));
+    assert!(error("empty").contains("no rdf:first"));
+
+    let mut unchanged = graph.clone();
+    assert!(unchanged.remove_list(&Uri::new("_:", "cycle", crate::core::uri::UriType::Prefixed)).is_err());
+    assert_eq!(unchanged, graph);
+
+    Ok(())
+}
+
+#[test]
+fn can_read_and_edit_containers() -> TestReturn {
+    let mut graph = TurtleParser::graph(r#"
+        ex:fruits a rdf:Bag ; rdf:_3 ex:pear ; rdf:_1 ex:apple .
+        ex:other rdf:_1 ex:plum .
+    "#)?;
+    let fruits = graph.triples[0].subject.0.clone();
+    let object = |s: &str| TurtleParser::object(s);
+
+    assert_eq!(graph.container_kind(&fruits), Some(ContainerKind::Bag));
+    assert_eq!(graph.container_members(&fruits), vec![object("ex:apple")?, object("ex:pear")?]);
+
+    graph.renumber_container(&fruits);
+    assert!(graph.triples.iter().any(|t| t.to_string() == "ex:fruits rdf:_2 ex:pear ."));
+    assert!(!graph.triples.iter().any(|t| t.predicate.0.name == "_3"));
+
+    graph.insert_container_member(&fruits, 1, object("ex:fig")?);
+    graph.push_container_member(&fruits, object("ex:kiwi")?);
+    assert_eq!(graph.container_members(&fruits), vec![object("ex:fig")?, object("ex:apple")?, object("ex:pear")?, object("ex:kiwi")?]);
+
+    assert_eq!(graph.remove_container_member(&fruits, 2), Some(object("ex:apple")?));
+    assert_eq!(graph.remove_container_member(&fruits, 4), None);
+    assert_eq!(graph.container_members(&fruits), vec![object("ex:fig")?, object("ex:pear")?, object("ex:kiwi")?]);
+
+    // Other containers are left alone
+    let other = graph.triples.iter().find(|t| t.subject.0.name == "other").unwrap().subject.0.clone();
+    assert_eq!(graph.container_kind(&other), None);
+    assert_eq!(graph.container_members(&other), vec![object("ex:plum")?]);
+
+    let alternatives = graph.insert_container(ContainerKind::Alt, vec![object("ex:tea")?, object("ex:coffee")?]);
+    assert_eq!(alternatives.name, "container1");
+    assert_eq!(graph.container_kind(&alternatives), Some(ContainerKind::Alt));
+    assert_eq!(graph.container_members(&alternatives), vec![object("ex:tea")?, object("ex:coffee")?]);
+    assert_eq!(graph.triples.len(), 8);
+
+    // Membership properties are recognized wherever the parser splits their IRI
+    let split = Uri::new("http://www.w3.org/1999/02/", "22-rdf-syntax-ns#_2", uri::UriType::Full);
+    assert_eq!(container::membership_index(&split), Some(2));
+    assert_eq!(container::membership_index(&Uri::new("ex:", "_2", uri::UriType::Prefixed)), None);
+
+    Ok(())
+}
+
+#[test]
+fn can_expand_and_compact_with_prefix_maps() -> TestReturn {
+    let prefixes: PrefixMap = vec![
+        ("ex:", "http://example.com/"),
+        ("people", "http://example.com/people/"),
+        ("a", "http://example.com/people/a"),
+        ("b:", "http://example.com/people/a")
+    ].into_iter().collect();
+
+    assert_eq!(prefixes.get("people"), prefixes.get("people:"));
+    assert_eq!(prefixes.expand("ex:John")?, "http://example.com/John");
+    assert_eq!(prefixes.expand("ex:a\\/b")?, "http://example.com/a/b");
+    assert_eq!(prefixes.expand("ex:")?, "http://example.com/");
+
+    assert!(prefixes.expand("foaf:name").unwrap_err().0.contains("foaf:"));
+    assert!(prefixes.expand("ex:a/b").unwrap_err().0.contains("Invalid local name"));
+    assert!(prefixes.expand("John").is_err());
+    assert!(prefixes.expand("_:b1").is_err());
+
+    // The longest namespace wins, then the first label
+    assert_eq!(prefixes.compact("http://example.com/people/John").as_deref(), Some("people:John"));
+    assert_eq!(prefixes.compact("http://example.com/people/alice").as_deref(), Some("a:lice"));
+    // Unless its local name would not be valid
+    assert_eq!(prefixes.compact("http://example.com/people/John/photo").as_deref(), None);
+    assert_eq!(prefixes.compact("http://example.com/people/.hidden"), None);
+    assert_eq!(prefixes.compact("http://example.com/people"), Some("ex:people".to_string()));
+    assert_eq!(prefixes.compact("https://example.com/John"), None);
+
+    assert!(is_pn_prefix("ex") && is_pn_prefix("") && is_pn_prefix("a.b"));
+    assert!(!is_pn_prefix("1ex") && !is_pn_prefix("ex.") && !is_pn_prefix("_"));
+    assert!(is_pn_local("_under") && is_pn_local(":colon") && is_pn_local("%41"));
+    assert!(!is_pn_local("-dash") && !is_pn_local("%4") && !is_pn_local("a\\b") && !is_pn_local("a b"));
+
+    Ok(())
+}
+
+#[test]
+fn can_apply_metadata_without_panicking() -> TestReturn {
+    let mut graph = TurtleParser::graph("ex:John foaf:knows ex:Jane . ex:Jane dc:title \"Dr\" .")?;
+    graph.prefixes.insert("ex", "http://example.com/ns/");
+
+    let unchanged = graph.clone();
+    let error = graph.apply_metadata().unwrap_err();
+    assert!(error.0.contains("dc:, foaf:"));
+    assert_eq!(graph, unchanged);
+
+    graph.prefixes.insert("foaf", "http://xmlns.com/foaf/0.1/");
+    graph.prefixes.insert("dc", "http://purl.org/dc/elements/1.1/");
+    graph.apply_metadata()?;
+    assert_eq!(graph.triples[0].predicate.0.to_string(), "http://xmlns.com/foaf/0.1/knows");
+
+    // Compacting uses the longest namespace
+    graph.prefixes.insert("people", "http://example.com/ns/people/");
+    graph.triples.insert(TurtleParser::triple("<http://example.com/ns/people/Jim> foaf:knows ex:John .")?.remove(0));
+    graph.apply_metadata()?;
+    graph.apply_prefixes();
+    let subjects: Vec<String> = graph.triples.iter().map(|t| t.subject.to_string()).collect();
+    assert_eq!(subjects, vec!["ex:John", "ex:Jane", "people:Jim"]);
+
+    Ok(())
+}
+
+#[test]
+fn can_normalize_graph() -> TestReturn {
+    use crate::parsing::ParserOptions;
+
+    // Every undefined prefix and relative IRI is reported at once, with its location
+    let options = ParserOptions::default().with_locations(None);
+    let graph = TurtleParser::graph_with_options("ex:John foaf:knows ex:Jane .\nex:Jane dc:title <title> .", &options)?;
+
+    let error = graph.normalize().unwrap_err();
+    assert_eq!(error.prefixes(), vec!["dc:", "ex:", "foaf:"]);
+    assert_eq!(error.undefined.len(), 6);
+    let relative = error.undefined.iter().find(|u| u.prefix.is_none()).unwrap();
+    assert_eq!(relative.term.name, "title");
+    assert_eq!(relative.locations[0].start.line, 2);
+    assert!(error.to_string().contains("Relative IRI without a base: title"));
+
+    // Prefixed names, relative IRIs, datatypes and blank nodes all end up in one form
+    let graph = TurtleParser::graph(r#"
+        @base <http://example.com/base/> .
+        @prefix ex: <http://example.com/ns/> .
+
+        ex:John ex:knows _:jane ;
+            ex:age "42"^^xsd:integer ;
+            <http://example.com/ns/homepage> <home> .
+        _:jane ex:knows ex:John .
+    "#)?;
+    let normalized = graph.normalize()?;
+    assert_eq!(normalized.triples.len(), 4);
+    assert_eq!(normalized.prefixes, graph.prefixes);
+
+    for t in normalized.triples.iter() {
+        for u in [&t.subject.0, &t.predicate.0] {
+            assert!(u.uri_type == uri::UriType::Full || u.uri_type == uri::UriType::BlankNode);
+        }
+        match &t.object {
+            Object::Resource(u) => assert!(u.uri_type == uri::UriType::Full || u.uri_type == uri::UriType::BlankNode),
+            Object::Literal(l) => assert_eq!(l.datatype.to_string(), "http://www.w3.org/2001/XMLSchema#integer"),
+            Object::Triple(_) => unreachable!()
+        }
+    }
+
+    assert_eq!(normalized.triples[0].object.to_string(), "_:jane");
+    assert_eq!(normalized.triples[2].to_string(), "http://example.com/ns/John http://example.com/ns/homepage http://example.com/base/home .");
+
+    // Triples that only differed in how they were written are merged
+    let graph = TurtleParser::graph("@prefix ex: <http://example.com/ns/> . ex:John a ex:Person . <http://example.com/ns/John> rdf:type ex:Person .")?;
+    assert_eq!(graph.triples.len(), 2);
+    assert_eq!(NormalizedGraph::try_from(graph)?.triples.len(), 1);
+
+    Ok(())
+}
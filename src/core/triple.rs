@@ -1,6 +1,3 @@
-use std::collections::HashMap;
-use std::hash::Hash;
-
 use crate::core::{
     Resource,
     Relationship,
@@ -8,9 +5,15 @@ use crate::core::{
     uri::UriType
 };
 
-#[derive(Clone, Eq, PartialOrd, Ord)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 /// An RDF triple. Typically constructed from a ([`Resource`], [`Relationship`], [`Object`]) tuple with
 /// `.into()`.
+///
+/// Triples are compared, ordered and hashed term by term, following the equality of [`Uri`] and
+/// [`Literal`].
+///
+/// [`Uri`]: crate::core::Uri
+/// [`Literal`]: crate::core::Literal
 pub struct Triple {
     pub subject: Resource,
     pub predicate: Relationship,
@@ -38,6 +41,9 @@ impl Triple {
                     } else {
                         false
                     }
+                // Blank nodes within a triple term are variables too
+                } else if let Object::Triple(pattern_o) = pattern_o {
+                    matches!(o, Object::Triple(o) if o.matches_pattern(&pattern_o))
                 } else {
                     false
                 }
@@ -48,58 +54,6 @@ impl Triple {
             false
         }
     }
-
-    pub(crate) fn apply_graph_prefixes(&mut self, base: &str, prefixes: &HashMap<String, String>) {
-        let Resource(subject_uri) = &mut self.subject;
-        if subject_uri.uri_type == UriType::PrefixedWithBase || subject_uri.uri_type == UriType::Relative {
-            subject_uri.prefix = base.to_string();
-            subject_uri.uri_type = UriType::Full;
-        } else if subject_uri.uri_type == UriType::Prefixed {
-            let pref = format!("{}", subject_uri.prefix);
-
-            if prefixes.contains_key(&pref) {
-                subject_uri.prefix = prefixes.get(&pref).unwrap().to_string();
-                subject_uri.uri_type = UriType::Full;
-            } else if pref.starts_with('_') { // Blank Node
-                subject_uri.uri_type = UriType::BlankNode;
-            } else {
-                panic!("Use of prefix without first defining it: {}", pref)
-            }
-        }
-
-        let Relationship(predicate_uri) = &mut self.predicate;
-        if predicate_uri.uri_type == UriType::PrefixedWithBase || predicate_uri.uri_type == UriType::Relative {
-            predicate_uri.prefix = base.to_string();
-            predicate_uri.uri_type = UriType::Full;
-        } else if predicate_uri.uri_type == UriType::Prefixed {
-            let pref = format!("{}", predicate_uri.prefix);
-
-            if prefixes.contains_key(&pref) {
-                predicate_uri.prefix = prefixes.get(&pref).unwrap().to_string();
-                predicate_uri.uri_type = UriType::Full;
-            } else {
-                panic!("Use of prefix without first defining it: {}", pref)
-            }
-        }
-
-        if let Object::Resource(object_uri) = &mut self.object {
-            if object_uri.uri_type == UriType::PrefixedWithBase || object_uri.uri_type == UriType::Relative {
-                object_uri.prefix = base.to_string();
-                object_uri.uri_type = UriType::Full;
-            } else if object_uri.uri_type == UriType::Prefixed {
-                let pref = format!("{}", object_uri.prefix);
-
-                if prefixes.contains_key(&pref) {
-                    object_uri.prefix = prefixes.get(&pref).unwrap().to_string();
-                    object_uri.uri_type = UriType::Full;
-                } else if pref.starts_with('_') { // Blank Node
-                    object_uri.uri_type = UriType::BlankNode;
-                } else {
-                    panic!("Use of prefix without first defining it: {}", pref)
-                }
-            }
-        }
-    }
 }
 
 impl From<(Resource, Relationship, Object)> for Triple {
@@ -120,27 +74,15 @@ impl From<Triple> for (Resource, Relationship, Object) {
     }
 }
 
-impl ToString for Triple {
-    fn to_string(&self) -> String {
-        format!("{} {} {} .", self.subject.to_string(), self.predicate.to_string(), self.object.to_string())
+impl std::fmt::Display for Triple {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {} .", self.subject, self.predicate, self.object)
     }
 }
 
 impl std::fmt::Debug for Triple {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_string())
-    }
-}
-
-impl PartialEq for Triple {
-    fn eq(&self, other: &Self) -> bool {
-        self.to_string().eq(&other.to_string())
-    }
-}
-
-impl Hash for Triple {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.to_string().hash(state)
+        write!(f, "{}", self)
     }
 }
 
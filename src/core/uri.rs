@@ -1,4 +1,9 @@
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+use std::cmp::Ordering;
+use std::hash::{ Hash, Hasher };
+
+use crate::core::UriRef;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum UriType {
     Full,
     Relative,
@@ -7,7 +12,14 @@ pub enum UriType {
     BlankNode
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// A URI split into a prefix and a name.
+///
+/// Two URIs are equal if their prefix and name spell the same IRI, regardless of where it is
+/// split or of their type. Prefixed names are not expanded for the comparison, so `ex:John` only
+/// equals `<http://example.com/John>` after
+/// [`Graph::normalize()`](crate::core::Graph::normalize) or
+/// [`Graph::apply_metadata()`](crate::core::Graph::apply_metadata).
+#[derive(Debug, Clone)]
 pub struct Uri {
     pub prefix: String,
     pub name: String,
@@ -22,11 +34,68 @@ impl Uri {
             uri_type
         }
     }
+
+    // The bytes of the IRI, regardless of where it is split
+    fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.prefix.bytes().chain(self.name.bytes())
+    }
+
+    /// Borrows the parts of this [`Uri`].
+    pub fn as_uri_ref(&self) -> UriRef<'_> {
+        UriRef::new(&self.prefix, &self.name, self.uri_type)
+    }
+}
+
+impl PartialEq for Uri {
+    fn eq(&self, other: &Self) -> bool {
+        self.prefix.len() + self.name.len() == other.prefix.len() + other.name.len() &&
+            self.bytes().eq(other.bytes())
+    }
+}
+
+impl Eq for Uri { }
+
+impl PartialOrd for Uri {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Uri {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes().cmp(other.bytes())
+    }
+}
+
+impl Hash for Uri {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_bytes(self.bytes(), state)
+    }
+}
+
+// Feeds the bytes to the hasher in chunks of 8, so that the hash does not depend on how the bytes
+// were split, followed by a terminator like `str` does
+pub(crate) fn hash_bytes<H: Hasher>(bytes: impl Iterator<Item = u8>, state: &mut H) {
+    let mut chunk = [0u8; 8];
+    let mut length = 0;
+
+    for b in bytes {
+        chunk[length] = b;
+        length += 1;
+
+        if length == chunk.len() {
+            state.write_u64(u64::from_le_bytes(chunk));
+            length = 0;
+        }
+    }
+
+    state.write(&chunk[..length]);
+    state.write_u8(0xff);
 }
 
-impl ToString for Uri {
-    fn to_string(&self) -> String {
-        format!("{}{}", self.prefix, self.name)
+impl std::fmt::Display for Uri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.prefix, self.name)
     }
 }
 
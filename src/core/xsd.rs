@@ -0,0 +1,586 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::ops::Neg;
+use std::str::FromStr;
+
+use crate::core::{ Literal, Uri, uri::UriType, DateTime, Date, Time, GYear, Duration };
+use crate::parsing::{ Parsed, ParserError };
+use crate::vocab::xsd;
+
+/// An arbitrary-precision (up to 38 digits) decimal number, as used by `xsd:decimal`.
+///
+/// Decimals are kept normalized, so `1.50` and `1.5` are equal and both print as `1.5`. There
+/// are no arithmetic operators, as the result may not fit in 38 digits: use
+/// [`Decimal::checked_add()`] and [`Decimal::checked_sub()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    // The number is `digits * 10^-scale`
+    digits: i128,
+    scale: u32
+}
+
+impl Decimal {
+    /// Creates the decimal `digits * 10^-scale`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `digits` is `i128::MIN`, which cannot be negated; see [`Decimal::checked_new()`].
+    pub fn new(digits: i128, scale: u32) -> Self {
+        Self::checked_new(digits, scale).expect("A decimal cannot have i128::MIN as its digits")
+    }
+
+    /// Creates the decimal `digits * 10^-scale`. Returns [`None`] if `digits` is `i128::MIN`, so
+    /// that every decimal can be negated.
+    pub fn checked_new(digits: i128, scale: u32) -> Option<Self> {
+        if digits == i128::MIN {
+            return None;
+        }
+
+        let (mut digits, mut scale) = (digits, scale);
+        while scale > 0 && digits % 10 == 0 {
+            digits /= 10;
+            scale -= 1;
+        }
+
+        Some(Self { digits, scale })
+    }
+
+    /// Returns true if the decimal has no fractional part.
+    pub fn is_integer(&self) -> bool {
+        self.scale == 0
+    }
+
+    /// The integer part of the decimal, rounded towards zero.
+    pub fn trunc(&self) -> i128 {
+        // A power of ten too large for an i128 is larger than the digits
+        10i128.checked_pow(self.scale).map_or(0, |power| self.digits / power)
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.digits as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    // Both digits with the same scale, if they fit
+    fn aligned(&self, other: &Self) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        let a = self.digits.checked_mul(10i128.checked_pow(scale - self.scale)?)?;
+        let b = other.digits.checked_mul(10i128.checked_pow(scale - other.scale)?)?;
+
+        Some((a, b, scale))
+    }
+
+    // A decimal from digits that may have overflowed. `i128::MIN` counts as an overflow.
+    fn checked(digits: Option<i128>, scale: u32) -> Option<Self> {
+        Self::checked_new(digits?, scale)
+    }
+
+    /// Adds two decimals. Returns [`None`] on overflow.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let (a, b, scale) = self.aligned(other)?;
+        Self::checked(a.checked_add(b), scale)
+    }
+
+    /// Subtracts two decimals. Returns [`None`] on overflow.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let (a, b, scale) = self.aligned(other)?;
+        Self::checked(a.checked_sub(b), scale)
+    }
+
+    // Divides the decimal by a positive integer, rounding the quotient towards negative
+    // infinity, and returns the quotient and the (non-negative) remainder. Returns `None` if the
+    // remainder of a negative decimal does not fit, which never happens for positive decimals.
+    pub(crate) fn div_rem_euclid(&self, divisor: i128) -> Option<(i128, Self)> {
+        assert!(divisor > 0, "Division by a non-positive number");
+
+        match 10i128.checked_pow(self.scale).and_then(|power| power.checked_mul(divisor)) {
+            Some(scaled) => Some((self.digits.div_euclid(scaled), Self::new(self.digits.rem_euclid(scaled), self.scale))),
+            // The divisor is larger than any decimal with this scale
+            None if self.digits >= 0 => Some((0, *self)),
+            None => None
+        }
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        Self::new(-self.digits, self.scale)
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.aligned(other) {
+            Some((a, b, _)) => a.cmp(&b),
+            // Only numbers of very different magnitudes overflow, so floats are precise enough
+            None => self.to_f64().partial_cmp(&other.to_f64()).unwrap_or(Ordering::Equal)
+        }
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = ParserError;
+
+    /// Parses the lexical form of an `xsd:decimal`, such as `-1.50`, `+.5` or `42`.
+    fn from_str(s: &str) -> Parsed<Self> {
+        if !matches::DECIMAL.is_match(s) {
+            return Err(ParserError(format!("Invalid xsd:decimal: {}", s)));
+        }
+
+        let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+        let negative = integer.starts_with('-');
+        let integer = integer.trim_start_matches(['+', '-']);
+        let fraction = fraction.trim_end_matches('0');
+
+        let overflow = || ParserError(format!("xsd:decimal out of range: {}", s));
+        let digits: i128 = format!("{}{}", integer, fraction).trim_start_matches('0').parse()
+            .or_else(|_| if integer.chars().chain(fraction.chars()).all(|c| c == '0') { Ok(0) } else { Err(overflow()) })?;
+        let scale = u32::try_from(fraction.len()).ok().filter(|&scale| scale <= 38).ok_or_else(overflow)?;
+
+        Ok(Self::new(if negative { -digits } else { digits }, scale))
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    /// Writes the canonical form of the decimal: no leading or trailing zeros, and no decimal
+    /// point for integers.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.scale == 0 {
+            return write!(f, "{}", self.digits);
+        }
+
+        let sign = if self.digits < 0 { "-" } else { "" };
+        let digits = format!("{:0>width$}", self.digits.unsigned_abs(), width = self.scale as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(i: i64) -> Self {
+        Self::new(i.into(), 0)
+    }
+}
+
+impl From<i32> for Decimal {
+    fn from(i: i32) -> Self {
+        Self::new(i.into(), 0)
+    }
+}
+
+/// Panics for `i128::MIN`, see [`Decimal::new()`].
+impl From<i128> for Decimal {
+    fn from(i: i128) -> Self {
+        Self::new(i, 0)
+    }
+}
+
+/// The value of a [`Literal`], according to its datatype.
+///
+/// Values are compared and tested for equality in the value space, see its [`PartialOrd`]
+/// implementation, so `Integer(1)`, `Decimal(1.0)` and `Double(1.0)` are all equal.
+#[derive(Debug, Clone)]
+pub enum LiteralValue {
+    Boolean(bool),
+    /// `xsd:integer` and the datatypes derived from it, such as `xsd:int` and `xsd:byte`.
+    Integer(i128),
+    Decimal(Decimal),
+    /// `xsd:double` and `xsd:float`.
+    Double(f64),
+    DateTime(DateTime),
+    Date(Date),
+    Time(Time),
+    GYear(GYear),
+    /// `xsd:duration`, `xsd:dayTimeDuration` and `xsd:yearMonthDuration`.
+    Duration(Duration),
+    /// `xsd:string` and the datatypes derived from it.
+    String(String),
+    /// A string with a language tag (`rdf:langString`).
+    LangString(String, String),
+    /// A literal whose datatype is not known, kept as its lexical form and datatype.
+    Other(String, Uri)
+}
+
+impl LiteralValue {
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            LiteralValue::Integer(i) => Some(*i as f64),
+            LiteralValue::Decimal(d) => Some(d.to_f64()),
+            LiteralValue::Double(d) => Some(*d),
+            _ => None
+        }
+    }
+}
+
+impl PartialOrd for LiteralValue {
+    /// Compares values in the value space: numbers of any numeric datatype are compared with
+    /// each other, date and time values as instants, durations by their length, and strings by
+    /// code point. Values of unrelated datatypes are not comparable, and neither are some
+    /// temporal values, see [`DateTime`] and [`Duration`].
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        use LiteralValue::*;
+
+        match (self, other) {
+            (Boolean(a), Boolean(b)) => a.partial_cmp(b),
+            (Integer(a), Integer(b)) => a.partial_cmp(b),
+            (Integer(a), Decimal(b)) => crate::core::Decimal::from(*a).partial_cmp(b),
+            (Decimal(a), Integer(b)) => a.partial_cmp(&crate::core::Decimal::from(*b)),
+            (Decimal(a), Decimal(b)) => a.partial_cmp(b),
+            (Double(_), _) | (_, Double(_)) => self.as_f64()?.partial_cmp(&other.as_f64()?),
+            (DateTime(a), DateTime(b)) => a.instant_cmp(b),
+            (Date(a), Date(b)) => a.instant_cmp(b),
+            (Time(a), Time(b)) => a.instant_cmp(b),
+            (GYear(a), GYear(b)) => a.instant_cmp(b),
+            (Duration(a), Duration(b)) => a.partial_cmp(b),
+            (String(a), String(b)) => a.partial_cmp(b),
+            (LangString(a, a_language), LangString(b, b_language)) if a_language.eq_ignore_ascii_case(b_language) => a.partial_cmp(b),
+            (Other(a, a_datatype), Other(b, b_datatype)) if a == b && a_datatype == b_datatype => Some(Ordering::Equal),
+            _ => None
+        }
+    }
+}
+
+impl PartialEq for LiteralValue {
+    /// Two values are equal if they compare as equal, so that equality agrees with
+    /// [`PartialOrd`]: numbers of different numeric datatypes can be equal, while values that
+    /// are not comparable, such as `NaN`, are not.
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+// The ranges of the datatypes derived from xsd:integer
+fn integer_range(name: &str) -> Option<(i128, i128)> {
+    let range = match name {
+        "integer" => (i128::MIN, i128::MAX),
+        "nonPositiveInteger" => (i128::MIN, 0),
+        "negativeInteger" => (i128::MIN, -1),
+        "nonNegativeInteger" => (0, i128::MAX),
+        "positiveInteger" => (1, i128::MAX),
+        "long" => (i64::MIN.into(), i64::MAX.into()),
+        "int" => (i32::MIN.into(), i32::MAX.into()),
+        "short" => (i16::MIN.into(), i16::MAX.into()),
+        "byte" => (i8::MIN.into(), i8::MAX.into()),
+        "unsignedLong" => (0, u64::MAX.into()),
+        "unsignedInt" => (0, u32::MAX.into()),
+        "unsignedShort" => (0, u16::MAX.into()),
+        "unsignedByte" => (0, u8::MAX.into()),
+        _ => return None
+    };
+
+    Some(range)
+}
+
+const STRING_TYPES: &[&str] = &[
+    "string", "normalizedString", "token", "language", "Name", "NCName", "NMTOKEN", "ENTITY",
+    "ID", "IDREF", "anyURI"
+];
+
+fn parse_double(s: &str) -> Option<f64> {
+    match s {
+        "INF" | "+INF" => Some(f64::INFINITY),
+        "-INF" => Some(f64::NEG_INFINITY),
+        "NaN" => Some(f64::NAN),
+        _ if matches::DOUBLE.is_match(s) => s.parse().ok(),
+        _ => None
+    }
+}
+
+impl Literal {
+    /// The local name of the datatype if it is one of the XML Schema datatypes, either prefixed
+    /// with `xsd:` or as a full IRI.
+    pub fn xsd_datatype(&self) -> Option<&str> {
+        let datatype = &self.datatype;
+        let is_xsd = (datatype.uri_type == UriType::Prefixed && datatype.prefix == xsd::PREFIX) ||
+            datatype.prefix == xsd::NAMESPACE;
+
+        if is_xsd { Some(&datatype.name) } else { None }
+    }
+
+    /// Parses the lexical form of the literal into a value of its datatype.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the lexical form is not valid for an XML Schema datatype, for
+    /// instance `"1.5"^^xsd:integer` or `"300"^^xsd:byte`, or if the language tag is not
+    /// well-formed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::{ Literal, LiteralValue };
+    /// # use rdf_rs::parsing::ParserError;
+    /// # fn main() -> Result<(), ParserError> {
+    /// assert_eq!(Literal::from(42).value()?, LiteralValue::Integer(42));
+    /// assert_eq!(Literal::from(true).value()?, LiteralValue::Boolean(true));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn value(&self) -> Parsed<LiteralValue> {
+        let lexical_form = self.lexical_form();
+        let invalid = |name: &str| ParserError(format!("Invalid xsd:{}: {}", name, lexical_form));
+
+        if let Some(language) = self.language_tag() {
+            return Ok(LiteralValue::LangString(lexical_form.into(), language?.to_string()));
+        }
+
+        let name = match self.xsd_datatype() {
+            Some(name) => name,
+            None => return Ok(LiteralValue::Other(lexical_form.into(), self.datatype.clone()))
+        };
+
+        // Only strings keep their surrounding whitespace
+        let trimmed = lexical_form.trim();
+
+        if let Some((min, max)) = integer_range(name) {
+            if !matches::INTEGER.is_match(trimmed) {
+                return Err(invalid(name));
+            }
+
+            let i: i128 = trimmed.parse().map_err(|_| invalid(name))?;
+            if i < min || i > max {
+                return Err(ParserError(format!("xsd:{} out of range: {}", name, lexical_form)));
+            }
+
+            return Ok(LiteralValue::Integer(i));
+        }
+
+        match name {
+            "boolean" => match trimmed {
+                "true" | "1" => Ok(LiteralValue::Boolean(true)),
+                "false" | "0" => Ok(LiteralValue::Boolean(false)),
+                _ => Err(invalid(name))
+            },
+            "decimal" => Ok(LiteralValue::Decimal(trimmed.parse()?)),
+            "double" => parse_double(trimmed).map(LiteralValue::Double).ok_or_else(|| invalid(name)),
+            "float" => parse_double(trimmed).map(|f| LiteralValue::Double(f as f32 as f64)).ok_or_else(|| invalid(name)),
+            "dateTime" => Ok(LiteralValue::DateTime(trimmed.parse()?)),
+            "date" => Ok(LiteralValue::Date(trimmed.parse()?)),
+            "time" => Ok(LiteralValue::Time(trimmed.parse()?)),
+            "gYear" => Ok(LiteralValue::GYear(trimmed.parse()?)),
+            "duration" => Ok(LiteralValue::Duration(trimmed.parse()?)),
+            // The restrictions of xsd:duration that only allow some of its components
+            "dayTimeDuration" if trimmed.split('T').next().unwrap_or("").contains(['Y', 'M']) => Err(invalid(name)),
+            "yearMonthDuration" if trimmed.contains(['D', 'T']) => Err(invalid(name)),
+            "dayTimeDuration" | "yearMonthDuration" => Ok(LiteralValue::Duration(trimmed.parse()?)),
+            name if STRING_TYPES.contains(&name) => Ok(LiteralValue::String(lexical_form.into())),
+            _ => Ok(LiteralValue::Other(lexical_form.into(), self.datatype.clone()))
+        }
+    }
+
+    /// Checks that the lexical form of the literal is valid for its datatype, and that its
+    /// language tag, if any, is well-formed. Other literals whose datatype is not an XML Schema
+    /// datatype are always valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] describing why the lexical form is invalid.
+    pub fn validate(&self) -> Parsed<()> {
+        self.value().map(|_| ())
+    }
+
+    /// The value of an `xsd:integer` (or derived datatype) literal, if it fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.value().ok()? {
+            LiteralValue::Integer(i) => i64::try_from(i).ok(),
+            _ => None
+        }
+    }
+
+    /// The value of a numeric literal as a float.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.value().ok()?.as_f64()
+    }
+
+    /// The value of an `xsd:decimal` or `xsd:integer` literal.
+    pub fn as_decimal(&self) -> Option<Decimal> {
+        match self.value().ok()? {
+            LiteralValue::Integer(i) => Some(Decimal::from(i)),
+            LiteralValue::Decimal(d) => Some(d),
+            _ => None
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.value().ok()? {
+            LiteralValue::Boolean(b) => Some(b),
+            _ => None
+        }
+    }
+
+    pub fn as_date_time(&self) -> Option<DateTime> {
+        match self.value().ok()? {
+            LiteralValue::DateTime(d) => Some(d),
+            _ => None
+        }
+    }
+
+    pub fn as_date(&self) -> Option<Date> {
+        match self.value().ok()? {
+            LiteralValue::Date(d) => Some(d),
+            _ => None
+        }
+    }
+
+    pub fn as_time(&self) -> Option<Time> {
+        match self.value().ok()? {
+            LiteralValue::Time(t) => Some(t),
+            _ => None
+        }
+    }
+
+    pub fn as_g_year(&self) -> Option<GYear> {
+        match self.value().ok()? {
+            LiteralValue::GYear(g) => Some(g),
+            _ => None
+        }
+    }
+
+    /// The value of an `xsd:duration`, `xsd:dayTimeDuration` or `xsd:yearMonthDuration` literal.
+    pub fn as_duration(&self) -> Option<Duration> {
+        match self.value().ok()? {
+            LiteralValue::Duration(d) => Some(d),
+            _ => None
+        }
+    }
+
+    /// Returns true if both literals have the same value, even if their lexical forms differ:
+    /// `"01"^^xsd:integer` equals `"1"^^xsd:integer` and `"1.0"^^xsd:decimal`. Invalid literals
+    /// only equal literals with the same lexical form and datatype.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::Literal;
+    /// let a = Literal::from(1);
+    /// let b = Literal { value: "\"01\"".into(), ..a.clone() };
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.value_eq(&b));
+    /// ```
+    pub fn value_eq(&self, other: &Literal) -> bool {
+        self.value_cmp(other) == Some(Ordering::Equal)
+    }
+
+    /// Compares the values of two literals, see [`LiteralValue::partial_cmp()`]. Returns [`None`]
+    /// if they are not comparable.
+    pub fn value_cmp(&self, other: &Literal) -> Option<Ordering> {
+        match (self.value(), other.value()) {
+            (Ok(a), Ok(b)) => a.partial_cmp(&b),
+            _ if self == other => Some(Ordering::Equal),
+            _ => None
+        }
+    }
+
+    // A literal of an XML Schema datatype
+    fn typed(lexical_form: &str, name: &str) -> Self {
+        Literal {
+            value: format!("\"{}\"", lexical_form),
+            datatype: Uri::new("xsd:", name, UriType::Prefixed),
+            language: None
+        }
+    }
+}
+
+impl From<i64> for Literal {
+    fn from(i: i64) -> Self {
+        Literal::typed(&i.to_string(), "integer")
+    }
+}
+
+impl From<i32> for Literal {
+    fn from(i: i32) -> Self {
+        Literal::from(i as i64)
+    }
+}
+
+impl From<u32> for Literal {
+    fn from(i: u32) -> Self {
+        Literal::from(i as i64)
+    }
+}
+
+impl From<f64> for Literal {
+    fn from(f: f64) -> Self {
+        let lexical_form = if f.is_nan() {
+            "NaN".to_string()
+        } else if f.is_infinite() {
+            if f > 0.0 { "INF" } else { "-INF" }.to_string()
+        } else {
+            format!("{:E}", f)
+        };
+
+        Literal::typed(&lexical_form, "double")
+    }
+}
+
+impl From<bool> for Literal {
+    fn from(b: bool) -> Self {
+        Literal::typed(&b.to_string(), "boolean")
+    }
+}
+
+impl From<Decimal> for Literal {
+    fn from(d: Decimal) -> Self {
+        Literal::typed(&d.to_string(), "decimal")
+    }
+}
+
+impl From<DateTime> for Literal {
+    fn from(d: DateTime) -> Self {
+        Literal::typed(&d.to_string(), "dateTime")
+    }
+}
+
+impl From<Date> for Literal {
+    fn from(d: Date) -> Self {
+        Literal::typed(&d.to_string(), "date")
+    }
+}
+
+impl From<Time> for Literal {
+    fn from(t: Time) -> Self {
+        Literal::typed(&t.to_string(), "time")
+    }
+}
+
+impl From<GYear> for Literal {
+    fn from(g: GYear) -> Self {
+        Literal::typed(&g.to_string(), "gYear")
+    }
+}
+
+impl From<Duration> for Literal {
+    /// Creates an `xsd:dayTimeDuration` or `xsd:yearMonthDuration` literal if the duration only
+    /// has one kind of component, or an `xsd:duration` literal otherwise.
+    fn from(d: Duration) -> Self {
+        let name = if d.is_day_time() {
+            "dayTimeDuration"
+        } else if d.is_year_month() {
+            "yearMonthDuration"
+        } else {
+            "duration"
+        };
+
+        Literal::typed(&d.to_string(), name)
+    }
+}
+
+pub mod matches {
+    use regex::Regex;
+    use lazy_static::lazy_static;
+
+    lazy_static! {
+        pub static ref INTEGER: Regex = Regex::new(r"^[+-]?\d+$").unwrap();
+        pub static ref DECIMAL: Regex = Regex::new(r"^[+-]?(\d+(\.\d*)?|\.\d+)$").unwrap();
+        pub static ref DOUBLE: Regex = Regex::new(r"^[+-]?(\d+(\.\d*)?|\.\d+)([eE][+-]?\d+)?$").unwrap();
+    }
+}
@@ -6,14 +6,14 @@
 //!
 //! * To provide a simple and easy-to-use RDF parsing API.
 //! * To act as an inference engine capable of filling a graph with all the triples that can be
-//! inferred from the parsed data.
+//!   inferred from the parsed data.
 //!
 //! # Usage
 //!
 //! This crate is not on [crates.io](https://crates.io) and thus the `Cargo.toml` entry looks like
 //! the following:
 //!
-//! ```
+//! ```toml
 //! [dependencies]
 //! rdf-rs = { git = "https://github.com/CoBrooks/rdf-rs" }
 //! ```
@@ -30,6 +30,23 @@ pub mod core {
     pub(crate) mod object;
     mod triple;
     mod graph;
+    mod location;
+    mod borrowed;
+    mod term;
+    mod dictionary;
+    mod store;
+    pub(crate) mod xsd;
+    mod temporal;
+    pub(crate) mod language;
+    pub(crate) mod merge;
+    mod isomorphism;
+    mod patch;
+    mod skolem;
+    mod lean;
+    mod list;
+    pub(crate) mod container;
+    mod prefix;
+    mod normalize;
 
     pub use uri::Uri;
     pub use resource::Resource;
@@ -37,22 +54,80 @@ pub mod core {
     pub use object::{ Object, Literal };
     pub use triple::Triple;
     pub use graph::Graph;
+    pub use store::TripleStore;
+    pub use xsd::{ Decimal, LiteralValue };
+    pub use temporal::{ DateTime, Date, Time, GYear, Duration };
+    pub use language::{ LanguageTag, basic_filter, extended_filter, lookup };
+    pub use merge::PrefixConflict;
+    pub use patch::{ Patch, Op };
+    pub use skolem::SkolemTable;
+    pub use container::ContainerKind;
+    pub use prefix::{ PrefixMap, is_pn_prefix, is_pn_local };
+    pub use normalize::{ NormalizedGraph, NormalizationError, UndefinedPrefix };
+    pub use location::{ Position, SourceLocation, SourceMap };
+    pub use borrowed::{ UriRef, LiteralRef, ObjectRef, TripleRef };
+    pub use term::{ NamedNode, BlankNode, Subject, Term, TermTriple };
+    pub use dictionary::{ TermId, TermDictionary, SharedDictionary, IdTriple, InternedGraph };
+
+    mod tests;
 }
 
-/// Contains the currently-implemented parsers and a base [`RDFParser`](crate::parsing::RDFParser) trait allowing 
+/// Contains the currently-implemented parsers and a base [`BaseParser`](crate::parsing::BaseParser) trait allowing 
 /// their creation
 pub mod parsing {
     mod base;
+    mod options;
+    pub(crate) mod parallel;
+    mod streaming;
     mod turtle;
 
     pub use base::{ ParserError, Parsed, BaseParser };
+    pub use options::ParserOptions;
     pub use turtle::TurtleParser;
+    pub use streaming::TripleRefs;
+
+    mod tests;
+}
+
+/// Contains the currently-implemented serializers and a base
+/// [`BaseSerializer`](crate::serializing::BaseSerializer) trait allowing their creation.
+pub mod serializing {
+    mod base;
+    mod turtle;
+    mod ntriples;
+
+    pub use base::{ SerializerError, Serialized, BaseSerializer };
+    pub use turtle::TurtleSerializer;
+    pub use ntriples::NTriplesSerializer;
+
+    mod tests;
+}
+
+/// Transparent decompression of parser input and compression of serializer output. Each format
+/// is enabled by the cargo feature of the same name: `gzip`, `bzip2` and `zstd`.
+pub mod compression {
+    mod codec;
+
+    pub use codec::{ Compression, Encoder, decompress, read_to_string };
+
+    mod tests;
+}
+
+/// Parser and serializer entry points for [`tokio`] readers and writers. Requires the `async`
+/// feature.
+#[cfg(feature = "async")]
+pub mod asynchronous {
+    mod read;
+    mod write;
+
+    pub use read::{ triples, graph };
+    pub use write::{ write_graph, write_triples };
 
     mod tests;
 }
 
 /// Contains the currently-implemented reasoner and a base
-/// [`RDFReasoner`](crate::reasoning::RDFReasoner) trait allowing their creation.
+/// [`BaseReasoner`](crate::reasoning::BaseReasoner) trait allowing their creation.
 pub mod reasoning {
     mod base;
     mod entailment;
@@ -65,6 +140,28 @@ pub mod reasoning {
     mod tests;
 }
 
+/// Constants for the terms of well-known vocabularies, such as [`rdfs::SUB_CLASS_OF`](crate::vocab::rdfs::SUB_CLASS_OF).
+pub mod vocab {
+    mod term;
+    mod namespaces;
+
+    pub use term::VocabTerm;
+    pub use namespaces::{ rdf, rdfs, xsd, owl, skos, foaf, dc, dcterms, sh, prov };
+
+    mod tests;
+}
+
+/// Generates vocabulary modules like those of [`vocab`](crate::vocab) from ontologies, for use
+/// from build scripts.
+pub mod codegen {
+    mod vocabulary;
+    mod rdfxml;
+
+    pub use vocabulary::{ CodegenOptions, generate, generate_from_graph, generate_file, constant_name };
+
+    mod tests;
+}
+
 pub mod querying {
     mod query;
 
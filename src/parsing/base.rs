@@ -1,6 +1,9 @@
 use std::error::Error;
 
+use crate::compression;
 use crate::core::*;
+use crate::parsing::ParserOptions;
+use crate::parsing::parallel;
 
 #[derive(Debug)]
 pub struct ParserError(pub String);
@@ -23,27 +26,107 @@ pub trait BaseParser {
     fn resource(r: &str) -> Parsed<Resource>;
     fn relationship(r: &str) -> Parsed<Relationship>;
     fn object(o: &str) -> Parsed<Object>;
-    fn triple(t: &str) -> Parsed<Vec<Triple>>;
-    fn graph(g: &str) -> Parsed<Graph>;
+    fn triple_with_options(t: &str, options: &ParserOptions) -> Parsed<Vec<Triple>>;
+    fn graph_with_options(g: &str, options: &ParserOptions) -> Parsed<Graph>;
 
-    /// Acts as a wrapper around [`RDFParser::graph()`] that automatically reads and 
+    /// Parses a [`Vec<Triple>`] using the default [`ParserOptions`].
+    fn triple(t: &str) -> Parsed<Vec<Triple>> {
+        Self::triple_with_options(t, &ParserOptions::default())
+    }
+
+    /// Parses a [`Graph`] using the default [`ParserOptions`].
+    fn graph(g: &str) -> Parsed<Graph> {
+        Self::graph_with_options(g, &ParserOptions::default())
+    }
+
+    /// Acts as a wrapper around [`BaseParser::graph()`] that automatically reads and 
     /// parses a file.
     ///
+    /// Files compressed with gzip, bzip2 or zstd are decompressed transparently when the
+    /// corresponding feature is enabled (see [`Compression`](crate::compression::Compression)).
+    ///
     /// # Errors
     ///
-    /// Returns a [`ParserError`] if the file is not a valid Graph.
+    /// Returns a [`ParserError`] if the file is not a valid Graph, or if it is compressed with a
+    /// format whose feature is disabled.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use rdf_rs::parsing::{ TurtleParser, RDFParser, ParserError };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
     /// # fn main() -> Result<(), ParserError> {
     /// let triple = TurtleParser::from_file("./test_data/simple.ttl")?;
     /// # Ok(())
     /// # }
     /// ```
     fn from_file(path: &str) -> Parsed<Graph> {
-        let file = std::fs::read_to_string(path)?;
-        Self::graph(&file)
+        Self::from_file_with_options(path, &ParserOptions::default())
+    }
+
+    /// Acts as a wrapper around [`BaseParser::graph_with_options()`] that automatically reads
+    /// and parses a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the file is not a valid Graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserOptions, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let path = "./test_data/simple.ttl";
+    /// let options = ParserOptions::default().with_base_from_path(path)?;
+    /// let graph = TurtleParser::from_file_with_options(path, &options)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn from_file_with_options(path: &str, options: &ParserOptions) -> Parsed<Graph> {
+        let file = compression::read_to_string(path)?;
+        Self::graph_with_options(&file, &options.for_file(path))
+    }
+
+    /// Parses a line-based document, such as N-Triples or Turtle made only of simple statements,
+    /// on `threads` worker threads (`0` uses every available core).
+    ///
+    /// The document is split at line boundaries that no statement continues past, found by
+    /// following strings, IRIs and brackets, so statements and literals may span several lines.
+    /// The directives of the whole document apply to every statement. Blank node labels
+    /// from the document refer to the same node throughout, while generated blank nodes are
+    /// labelled per chunk (`_:blank2_1`). The triples keep their document order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if any of the chunks is not valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserOptions, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph_parallel(r#"
+    ///     @prefix ex: <http://example.com/> .
+    ///     ex:a ex:knows _:b .
+    ///     _:b ex:knows ex:c .
+    ///     ex:c ex:knows ex:a .
+    /// "#, &ParserOptions::default(), 2)?;
+    ///
+    /// assert_eq!(graph.triples.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn graph_parallel(g: &str, options: &ParserOptions, threads: usize) -> Parsed<Graph> {
+        parallel::graph_parallel(g, options, threads, Self::graph_with_options)
+    }
+
+    /// Acts as a wrapper around [`BaseParser::graph_parallel()`] that automatically reads and
+    /// parses a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ParserError`] if the file is not a valid Graph.
+    fn from_file_parallel(path: &str, options: &ParserOptions, threads: usize) -> Parsed<Graph> {
+        let file = compression::read_to_string(path)?;
+        Self::graph_parallel(&file, &options.for_file(path), threads)
     }
 }
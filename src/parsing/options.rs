@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use crate::vocab::{ rdf, rdfs, xsd, owl, foaf };
+
+/// Configuration shared by every [`BaseParser`](crate::parsing::BaseParser) through its
+/// `*_with_options` methods.
+///
+/// The [`Default`] options mirror the historical parser behaviour: only the `rdf:` and `xsd:`
+/// prefixes are predefined, there is no external base IRI, generated blank nodes are labelled
+/// `_:blank1`, `_:blank2`, ..., and nothing is checked beyond the syntax itself.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserOptions, ParserError };
+/// # fn main() -> Result<(), ParserError> {
+/// let options = ParserOptions::default()
+///     .with_well_known_prefixes()
+///     .with_prefix("ex", "http://example.com/")
+///     .with_base("http://example.com/data/")
+///     .with_blank_node_prefix("b");
+///
+/// let graph = TurtleParser::graph_with_options(r#"
+///     ex:John foaf:knows [ foaf:name "Alice" ] .
+/// "#, &options)?;
+///
+/// assert_eq!(graph.base_prefix, Some("http://example.com/data/".to_string()));
+/// assert_eq!(graph.triples[1].object.to_string(), "_:b1");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParserOptions {
+    /// Prefixes defined before the document is read, keyed by their label including the
+    /// trailing colon (`"rdf:"`). `@prefix` directives in the document take precedence.
+    pub prefixes: HashMap<String, String>,
+    /// The base IRI used when the document does not declare one with `@base`.
+    pub base: Option<String>,
+    /// The label given to generated blank nodes, which are numbered from 1 (`_:blank1`).
+    pub blank_node_prefix: String,
+    /// Fail when a prefixed name uses a prefix that was neither predefined nor declared.
+    pub require_declared_prefixes: bool,
+    /// Fail when the lexical form of a literal is not valid for its XML Schema datatype, such as
+    /// `"1.5"^^xsd:integer` (see [`Literal::validate()`](crate::core::Literal::validate)).
+    pub validate_literals: bool,
+    /// Skip statements that fail to parse instead of failing the whole document.
+    pub skip_invalid_statements: bool,
+    /// Record the location of the statement each triple was parsed from in
+    /// [`Graph::locations`](crate::core::Graph::locations).
+    pub track_locations: bool,
+    /// The name of the parsed source used in the recorded locations. Defaults to the path when
+    /// reading a file.
+    pub source_name: Option<String>,
+    /// The line number of the first line of the input, for inputs that are only part of their
+    /// source.
+    pub first_line: usize,
+}
+
+impl ParserOptions {
+    /// Options without any predefined prefixes.
+    pub fn empty() -> Self {
+        Self {
+            prefixes: HashMap::new(),
+            base: None,
+            blank_node_prefix: "blank".into(),
+            require_declared_prefixes: false,
+            validate_literals: false,
+            skip_invalid_statements: false,
+            track_locations: false,
+            source_name: None,
+            first_line: 1,
+        }
+    }
+
+    /// Adds (or replaces) a predefined prefix. The trailing colon of the label is optional.
+    pub fn with_prefix(mut self, label: &str, namespace: &str) -> Self {
+        let label = if label.ends_with(':') { label.to_string() } else { format!("{}:", label) };
+        self.prefixes.insert(label, namespace.into());
+        self
+    }
+
+    /// Predefines the `rdf:`, `rdfs:`, `xsd:`, `owl:` and `foaf:` prefixes.
+    pub fn with_well_known_prefixes(self) -> Self {
+        self.with_prefix(rdf::PREFIX, rdf::NAMESPACE)
+            .with_prefix(rdfs::PREFIX, rdfs::NAMESPACE)
+            .with_prefix(xsd::PREFIX, xsd::NAMESPACE)
+            .with_prefix(owl::PREFIX, owl::NAMESPACE)
+            .with_prefix(foaf::PREFIX, foaf::NAMESPACE)
+    }
+
+    /// Sets the base IRI used for documents without an `@base` directive.
+    pub fn with_base(mut self, base: &str) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// Uses the `file://` IRI of `path` as the base IRI, as if the document declared
+    /// `@base <file:///absolute/path>`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`std::io::Error`] if the path cannot be made absolute.
+    pub fn with_base_from_path(self, path: &str) -> std::io::Result<Self> {
+        let path = std::fs::canonicalize(path)?;
+        let path = path.to_string_lossy().replace('\\', "/");
+
+        if path.starts_with('/') {
+            Ok(self.with_base(&format!("file://{}", path)))
+        } else {
+            Ok(self.with_base(&format!("file:///{}", path)))
+        }
+    }
+
+    /// Sets the label used for generated blank nodes.
+    pub fn with_blank_node_prefix(mut self, prefix: &str) -> Self {
+        self.blank_node_prefix = prefix.into();
+        self
+    }
+
+    /// Records the location of every parsed triple, attributing them to `source_name` if given.
+    pub fn with_locations(mut self, source_name: Option<&str>) -> Self {
+        self.track_locations = true;
+        self.source_name = source_name.map(|s| s.into());
+        self
+    }
+
+    // Names the source after the file at `path` unless it already has a name
+    pub(crate) fn for_file(&self, path: &str) -> Self {
+        let mut options = self.clone();
+        if options.source_name.is_none() {
+            options.source_name = Some(path.into());
+        }
+
+        options
+    }
+
+    /// Fails on prefixed names whose prefix was never defined and on invalid typed literals.
+    pub fn strict(mut self) -> Self {
+        self.require_declared_prefixes = true;
+        self.validate_literals = true;
+        self
+    }
+
+    /// Skips statements that fail to parse instead of returning an error.
+    pub fn lenient(mut self) -> Self {
+        self.skip_invalid_statements = true;
+        self
+    }
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        Self::empty()
+            .with_prefix(rdf::PREFIX, rdf::NAMESPACE)
+            .with_prefix(xsd::PREFIX, xsd::NAMESPACE)
+    }
+}
@@ -0,0 +1,204 @@
+use std::thread;
+
+use crate::core::*;
+use crate::parsing::{ Parsed, ParserError, ParserOptions };
+
+pub(crate) type GraphParser = fn(&str, &ParserOptions) -> Parsed<Graph>;
+
+// A part of a document that only contains whole statements, along with the number of its first line
+struct Chunk<'a> {
+    first_line: usize,
+    text: &'a str
+}
+
+// Returns true if the line is an `@prefix`, `@base`, `PREFIX` or `BASE` directive
+fn is_directive(line: &str) -> bool {
+    let line = line.trim_start();
+
+    ["@prefix", "@base", "PREFIX", "BASE"].iter().any(|keyword| {
+        line.starts_with(keyword) && line[keyword.len()..].starts_with(char::is_whitespace)
+    })
+}
+
+// Where the scanner is within a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lexical {
+    Code,
+    Iri,
+    String { quote: char, long: bool }
+}
+
+// Follows the lexical state of a Turtle document line by line, to find the lines that no
+// statement continues past: outside of strings, IRIs and brackets, and after the ` .` of the
+// last statement (or a directive).
+pub(crate) struct StatementScanner {
+    state: Lexical,
+    depth: usize,
+    pending: bool
+}
+
+impl StatementScanner {
+    pub(crate) fn new() -> Self {
+        Self { state: Lexical::Code, depth: 0, pending: false }
+    }
+
+    // Scans a line, including its line break, and returns true if no statement continues past it
+    pub(crate) fn ends_statement(&mut self, line: &str) -> bool {
+        // SPARQL-style directives have no terminator
+        if !self.pending && self.state == Lexical::Code && is_directive(line) && !line.trim_start().starts_with('@') {
+            return true;
+        }
+
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+            let tripled = |q: char| next == Some(q) && chars.get(i + 2) == Some(&q);
+
+            match self.state {
+                Lexical::String { quote, long } => match c {
+                    '\\' => i += 1,
+                    c if c == quote && !long => self.state = Lexical::Code,
+                    c if c == quote && tripled(quote) => {
+                        self.state = Lexical::Code;
+                        i += 2;
+                    },
+                    _ => ()
+                },
+                Lexical::Iri => if c == '>' {
+                    self.state = Lexical::Code;
+                },
+                Lexical::Code => {
+                    match c {
+                        '#' => break,
+                        '"' | '\'' if tripled(c) => {
+                            self.state = Lexical::String { quote: c, long: true };
+                            i += 2;
+                        },
+                        '"' | '\'' => self.state = Lexical::String { quote: c, long: false },
+                        '<' if next == Some('<') => {
+                            self.depth += 1;
+                            i += 1;
+                        },
+                        '>' if next == Some('>') && self.depth > 0 => {
+                            self.depth -= 1;
+                            i += 1;
+                        },
+                        '<' => self.state = Lexical::Iri,
+                        '[' | '(' | '{' => self.depth += 1,
+                        ']' | ')' | '}' => self.depth = self.depth.saturating_sub(1),
+                        _ => ()
+                    }
+
+                    // A dot ends a statement, unless it is part of a name or a number
+                    let terminates = c == '.' && self.depth == 0 && next.is_none_or(|n| n.is_whitespace() || n == '#');
+                    if terminates {
+                        self.pending = false;
+                    } else if !c.is_whitespace() {
+                        self.pending = true;
+                    }
+                }
+            }
+
+            i += 1;
+        }
+
+        self.state == Lexical::Code && self.depth == 0 && !self.pending
+    }
+}
+
+// Splits the document into (at most) `count` chunks of roughly the same size. Chunks only end
+// after a line that no statement continues past, so that no statement is split across two
+// chunks.
+fn split(g: &str, count: usize) -> Vec<Chunk<'_>> {
+    let target = g.len() / count + 1;
+
+    let mut chunks = Vec::new();
+    let mut scanner = StatementScanner::new();
+    let mut start = 0;
+    let mut end = 0;
+    let mut first_line = 1;
+
+    for line in g.split_inclusive('\n') {
+        end += line.len();
+
+        if scanner.ends_statement(line) && end - start >= target && end < g.len() {
+            let text = &g[start..end];
+            chunks.push(Chunk { first_line, text });
+
+            first_line += text.matches('\n').count();
+            start = end;
+        }
+    }
+
+    if start < g.len() {
+        chunks.push(Chunk { first_line, text: &g[start..] });
+    }
+
+    chunks
+}
+
+// Parses every chunk of the document on its own thread and concatenates the resulting graphs.
+//
+// The directives of the whole document are read first and handed to every chunk, so a prefix
+// can be used before (or far away from) its declaration. Labelled blank nodes (`_:b1`) keep
+// their label and thus refer to the same node in every chunk, while the blank nodes generated
+// for property lists and collections get a label unique to their chunk.
+pub(crate) fn graph_parallel(g: &str, options: &ParserOptions, threads: usize, parse: GraphParser) -> Parsed<Graph> {
+    let threads = if threads == 0 {
+        thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+    } else {
+        threads
+    };
+
+    if threads == 1 {
+        return parse(g, options);
+    }
+
+    let directives: String = g.lines()
+        .filter(|l| is_directive(l))
+        .map(|l| format!("{}\n", l))
+        .collect();
+    let header = parse(&directives, &ParserOptions { track_locations: false, ..options.clone() })?;
+
+    let mut options = options.clone();
+    options.prefixes = header.prefixes.into();
+    options.base = header.base_prefix;
+
+    let chunks = split(g, threads);
+
+    let results: Vec<Parsed<Graph>> = thread::scope(|scope| {
+        let handles: Vec<_> = chunks.iter()
+            .enumerate()
+            .map(|(i, chunk)| {
+                let mut options = options.clone();
+                options.first_line = options.first_line + chunk.first_line - 1;
+                options.blank_node_prefix = format!("{}{}_", options.blank_node_prefix, i + 1);
+
+                scope.spawn(move || parse(chunk.text, &options))
+            })
+            .collect();
+
+        handles.into_iter()
+            .map(|h| h.join().unwrap_or_else(|_| Err(ParserError("Parser thread panicked".into()))))
+            .collect()
+    });
+
+    let mut graph = Graph {
+        base_prefix: options.base,
+        prefixes: options.prefixes.into(),
+        triples: TripleStore::new(),
+        locations: SourceMap::new()
+    };
+
+    for result in results {
+        let chunk = result?;
+
+        graph.triples.extend(chunk.triples);
+        graph.locations.merge(chunk.locations);
+    }
+
+    Ok(graph)
+}
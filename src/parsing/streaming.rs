@@ -0,0 +1,322 @@
+use std::collections::HashMap;
+
+use crate::core::*;
+use crate::core::uri::UriType;
+use crate::parsing::{ Parsed, ParserError, TurtleParser };
+use crate::vocab::{ rdf, xsd };
+
+// Where the iterator is within a statement
+#[derive(Clone, Copy)]
+enum State<'a> {
+    // Before the subject of a statement
+    Statement,
+    // After an object, which can be followed by ',', ';' or '.'
+    Object(UriRef<'a>, UriRef<'a>),
+    // After an error or the end of the input
+    Done
+}
+
+/// An iterator over the statements of a Turtle (or N-Triples) document that yields
+/// [`TripleRef`]s borrowing from the input instead of allocating.
+///
+/// Created with [`TurtleParser::stream()`]. The input can be any `&str`, including the contents
+/// of a memory-mapped file. Prefixed names are not expanded (that would require allocating), but
+/// the directives read so far are available through [`TripleRefs::prefixes()`] and
+/// [`TripleRefs::base()`].
+///
+/// Only simple statements are supported: predicate and object lists are, blank property lists
+/// (`[ ... ]`), collections (`( ... )`) and triple terms (`<<( ... )>>`) are reported as errors. Use
+/// [`BaseParser::graph()`](crate::parsing::BaseParser::graph) for those. Iteration stops after the
+/// first error.
+pub struct TripleRefs<'a> {
+    input: &'a str,
+    position: usize,
+    state: State<'a>,
+    prefixes: HashMap<&'a str, &'a str>,
+    base: Option<&'a str>
+}
+
+impl<'a> TripleRefs<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            position: 0,
+            state: State::Statement,
+            prefixes: HashMap::new(),
+            base: None
+        }
+    }
+
+    /// The prefixes declared so far, keyed by their label including the trailing colon, mapped
+    /// to their namespace without the angle brackets.
+    pub fn prefixes(&self) -> &HashMap<&'a str, &'a str> {
+        &self.prefixes
+    }
+
+    /// The base IRI declared so far.
+    pub fn base(&self) -> Option<&'a str> {
+        self.base
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    // Skips whitespace and comments
+    fn skip_ignored(&mut self) {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.position += rest.len() - trimmed.len();
+
+            if trimmed.starts_with('#') {
+                self.position += trimmed.find('\n').unwrap_or(trimmed.len());
+            } else {
+                break;
+            }
+        }
+    }
+
+    // Returns the next token without consuming it
+    fn peek(&mut self) -> Parsed<Option<&'a str>> {
+        self.skip_ignored();
+
+        let rest = self.rest();
+        let length = match rest.chars().next() {
+            None => return Ok(None),
+            Some(',') | Some(';') | Some('.') => 1,
+            Some('[') | Some('(') => {
+                return Err(ParserError(format!("Blank property lists and collections are not supported when streaming: {}", Self::excerpt(rest))))
+            },
+            Some('<') if rest.starts_with("<<") => {
+                return Err(ParserError(format!("Triple terms and quoted triples are not supported when streaming: {}", Self::excerpt(rest))))
+            },
+            Some('<') => {
+                rest.find('>')
+                    .ok_or_else(|| ParserError(format!("Unterminated IRI: {}", Self::excerpt(rest))))? + 1
+            },
+            Some(quote) if quote == '"' || quote == '\'' => {
+                let end = Self::quoted_length(rest, quote)?;
+                end + Self::suffix_length(&rest[end..])
+            },
+            Some(_) => {
+                let end = rest.find(|c: char| c.is_whitespace() || ",;<\"'".contains(c)).unwrap_or(rest.len());
+                // A trailing '.' terminates the statement rather than being part of the word
+                rest[..end].trim_end_matches('.').len().max(1)
+            }
+        };
+
+        Ok(Some(&rest[..length]))
+    }
+
+    fn next_token(&mut self) -> Parsed<Option<&'a str>> {
+        let token = self.peek()?;
+        if let Some(token) = token {
+            self.position += token.len();
+        }
+
+        Ok(token)
+    }
+
+    fn expect_token(&mut self, expected: &str) -> Parsed<&'a str> {
+        self.next_token()?
+            .ok_or_else(|| ParserError(format!("Unexpected end of input, expected {}", expected)))
+    }
+
+    // The length of the quoted string at the start of `s`, including its delimiters
+    fn quoted_length(s: &str, quote: char) -> Parsed<usize> {
+        let delimiter: String = if s.starts_with(&quote.to_string().repeat(3)) {
+            quote.to_string().repeat(3)
+        } else {
+            quote.to_string()
+        };
+
+        let mut escaped = false;
+        let mut end = None;
+        for (i, c) in s.char_indices().skip(delimiter.len()) {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if s[i..].starts_with(&delimiter) {
+                end = Some(i + delimiter.len());
+                break;
+            }
+        }
+
+        end.ok_or_else(|| ParserError(format!("Unterminated literal: {}", Self::excerpt(s))))
+    }
+
+    // The length of the language tag or datatype at the start of `s`
+    fn suffix_length(s: &str) -> usize {
+        if let Some(language) = s.strip_prefix('@') {
+            1 + language.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-')).unwrap_or(language.len())
+        } else if let Some(datatype) = s.strip_prefix("^^") {
+            let end = datatype.find(|c: char| c.is_whitespace() || ",;".contains(c)).unwrap_or(datatype.len());
+
+            2 + datatype[..end].trim_end_matches('.').len()
+        } else {
+            0
+        }
+    }
+
+    fn excerpt(s: &str) -> &str {
+        s.lines().next().unwrap_or("")
+    }
+
+    fn uri(token: &'a str) -> Parsed<UriRef<'a>> {
+        UriRef::parse(token).ok_or_else(|| ParserError(format!("Invalid URI: {}", token)))
+    }
+
+    fn object(token: &'a str) -> Parsed<ObjectRef<'a>> {
+        let literal = |value: &'a str, name: &'a str| {
+            ObjectRef::Literal(LiteralRef {
+                value,
+                datatype: UriRef::new(xsd::PREFIX, name, UriType::Prefixed),
+                language: None
+            })
+        };
+
+        if token.starts_with('"') || token.starts_with('\'') {
+            let quote = token.chars().next().unwrap_or('"');
+            let (value, suffix) = token.split_at(Self::quoted_length(token, quote)?);
+
+            if let Some(language) = suffix.strip_prefix('@') {
+                Ok(ObjectRef::Literal(LiteralRef {
+                    value,
+                    datatype: UriRef::new(rdf::LANG_STRING.prefix, rdf::LANG_STRING.name, UriType::Prefixed),
+                    language: Some(language)
+                }))
+            } else if let Some(datatype) = suffix.strip_prefix("^^") {
+                Ok(ObjectRef::Literal(LiteralRef {
+                    value,
+                    datatype: Self::uri(datatype)?,
+                    language: None
+                }))
+            } else {
+                Ok(literal(value, "string"))
+            }
+        } else if token == "true" || token == "false" {
+            Ok(literal(token, "boolean"))
+        } else if let Some(uri) = UriRef::parse(token) {
+            Ok(ObjectRef::Resource(uri))
+        } else if let Some(datatype) = TurtleParser::numeric_datatype(token) {
+            Ok(literal(token, datatype.name))
+        } else {
+            Err(ParserError(format!("Object must be a resource or a literal. Found: {}", token)))
+        }
+    }
+
+    fn directive(&mut self, keyword: &'a str) -> Parsed<()> {
+        if keyword == "@prefix" || keyword == "PREFIX" {
+            let label = self.expect_token("a prefix label")?;
+            let namespace = self.expect_token("a namespace")?;
+
+            self.prefixes.insert(label, namespace.trim_start_matches('<').trim_end_matches('>'));
+        } else {
+            let base = self.expect_token("a base IRI")?;
+
+            self.base = Some(base.trim_start_matches('<').trim_end_matches('>'));
+        }
+
+        // The SPARQL-style directives are not terminated with a '.'
+        if keyword.starts_with('@') {
+            match self.next_token()? {
+                Some(".") => { },
+                other => return Err(ParserError(format!("Directive must end with ' .'. Found: {:?}", other)))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn step(&mut self) -> Parsed<Option<TripleRef<'a>>> {
+        loop {
+            match self.state {
+                State::Done => return Ok(None),
+                State::Statement => {
+                    let token = match self.next_token()? {
+                        None => return Ok(None),
+                        Some(token) => token
+                    };
+
+                    if ["@prefix", "@base", "PREFIX", "BASE"].contains(&token) {
+                        self.directive(token)?;
+                        continue;
+                    }
+
+                    let subject = Self::uri(token)?;
+                    let predicate = Self::uri(self.expect_token("a predicate")?)?;
+
+                    return self.emit(subject, predicate);
+                },
+                State::Object(subject, predicate) => {
+                    match self.expect_token("' .', ' ;' or ','")? {
+                        "," => return self.emit(subject, predicate),
+                        ";" => {
+                            // A predicate list may end with a dangling ';'
+                            if let Some(".") = self.peek()? {
+                                continue;
+                            }
+
+                            let predicate = Self::uri(self.expect_token("a predicate")?)?;
+                            return self.emit(subject, predicate);
+                        },
+                        "." => self.state = State::Statement,
+                        token => return Err(ParserError(format!("Triple must end with ' .' or continue with ',' or ' ;'. Found: {}", token)))
+                    }
+                }
+            }
+        }
+    }
+
+    fn emit(&mut self, subject: UriRef<'a>, predicate: UriRef<'a>) -> Parsed<Option<TripleRef<'a>>> {
+        let object = Self::object(self.expect_token("an object")?)?;
+        self.state = State::Object(subject, predicate);
+
+        Ok(Some(TripleRef { subject, predicate, object }))
+    }
+}
+
+impl<'a> Iterator for TripleRefs<'a> {
+    type Item = Parsed<TripleRef<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.step();
+        if next.is_err() {
+            self.state = State::Done;
+        }
+
+        next.transpose()
+    }
+}
+
+impl TurtleParser {
+    /// Streams the triples of a document as [`TripleRef`]s that borrow from `input`, without
+    /// allocating for the terms. See [`TripleRefs`] for the supported syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::Triple;
+    /// # use rdf_rs::parsing::{ TurtleParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let input = r#"
+    ///     @prefix foaf: <http://xmlns.com/foaf/0.1/> .
+    ///     <http://example.com/people/John> foaf:name "John" ; foaf:knows <http://example.com/people/Jane> .
+    /// "#;
+    ///
+    /// for triple in TurtleParser::stream(input) {
+    ///     let triple = triple?;
+    ///     assert_eq!(triple.subject.name, "John");
+    ///
+    ///     // Convert the borrowed triple to an owned one
+    ///     let owned: Triple = triple.into();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(input: &str) -> TripleRefs<'_> {
+        TripleRefs::new(input)
+    }
+}
@@ -2,8 +2,10 @@
 use std::collections::HashMap;
 use crate::core::*;
 use crate::core::uri::UriType;
-use crate::parsing::{ BaseParser, TurtleParser, ParserError };
+use crate::parsing::{ BaseParser, TurtleParser, ParserError, ParserOptions };
 use crate::reasoning::{ BaseReasoner, RDFSReasoner };
+use crate::vocab::xsd;
+use crate::assert_isomorphic;
 
 type TestReturn = Result<(), ParserError>;
 
@@ -91,7 +93,7 @@ fn can_parse_meta() -> TestReturn {
         ("rdf:".to_string(), "http://www.w3.org/1999/02/22-rdf-syntax-ns#".to_string()),
         ("xsd:".to_string(), "http://www.w3.org/2001/XMLSchema#".to_string()),
     ]);
-    assert_eq!(graph.prefixes, expected_prefixes);
+    assert_eq!(graph.prefixes, PrefixMap::from(expected_prefixes));
 
     Ok(())
 }
@@ -100,7 +102,7 @@ fn can_parse_meta() -> TestReturn {
 fn can_apply_meta() -> TestReturn {
     // https://w3.org/TR/turtle Example 1
     let mut graph = TurtleParser::from_file("./test_data/simple.ttl")?;
-    graph.apply_metadata();
+    graph.apply_metadata()?;
     
     assert_eq!(graph.triples.len(), 7);
 
@@ -148,6 +150,11 @@ fn can_parse_collections() -> TestReturn {
         TurtleParser::triple(":a :b _:blank1 .")?,
     ].into_iter().flatten().collect();
     assert_eq!(triples, expected_triples);
+
+    // Longer, nested and empty collections
+    assert_eq!(TurtleParser::triple(":a :b ( 1 2 3 ) .")?.len(), 7);
+    assert_eq!(TurtleParser::triple(":a :b ( 1 ( 2 ) [ :c 3 ] ) .")?.len(), 10);
+    assert_eq!(TurtleParser::triple(":a :b () .")?, TurtleParser::triple(":a :b rdf:nil .")?);
     
     Ok(())
 }
@@ -171,3 +178,303 @@ fn can_parse_literals() -> TestReturn {
     Ok(())
 }
 
+
+#[test]
+fn can_parse_with_options() -> TestReturn {
+    let options = ParserOptions::default()
+        .with_well_known_prefixes()
+        .with_prefix("ex:", "http://example.org/")
+        .with_base("http://example.org/base/")
+        .with_blank_node_prefix("node");
+
+    let graph = TurtleParser::graph_with_options(r#"
+        @prefix ex: <http://example.com/> .
+
+        [ foaf:name "Alice" ] foaf:knows ex:Bob .
+        ex:Bob foaf:knows [ foaf:name "Eve" ] .
+    "#, &options)?;
+
+    assert_eq!(graph.base_prefix, Some("http://example.org/base/".to_string()));
+    assert_eq!(graph.prefixes.get("ex:"), Some(&"http://example.com/".to_string()));
+    assert_eq!(graph.prefixes.get("owl:"), Some(&"http://www.w3.org/2002/07/owl#".to_string()));
+
+    // generated blank node labels are unique across statements
+    let expected_triples: Vec<Triple> = vec![
+        TurtleParser::triple("_:node1 foaf:name \"Alice\" .")?,
+        TurtleParser::triple("_:node1 foaf:knows ex:Bob .")?,
+        TurtleParser::triple("_:node2 foaf:name \"Eve\" .")?,
+        TurtleParser::triple("ex:Bob foaf:knows _:node2 .")?,
+    ].into_iter().flatten().collect();
+    assert_eq!(graph.triples, expected_triples);
+
+    // a document base overrides the external one
+    let graph = TurtleParser::graph_with_options("@base <http://example.net/> .", &options)?;
+    assert_eq!(graph.base_prefix, Some("http://example.net/".to_string()));
+
+    Ok(())
+}
+
+#[test]
+fn can_toggle_strictness() -> TestReturn {
+    let document = r#"
+        ex:a ex:b ex:c .
+        ex:a ex:b .
+        ex:a ex:d ex:e .
+    "#;
+
+    assert!(TurtleParser::graph(document).is_err());
+
+    let graph = TurtleParser::graph_with_options(document, &ParserOptions::default().lenient())?;
+    assert_eq!(graph.triples.len(), 2);
+
+    let strict = ParserOptions::default().lenient().strict();
+    assert!(TurtleParser::graph_with_options(document, &strict).is_err());
+
+    let strict = strict.with_prefix("ex", "http://example.com/");
+    assert_eq!(TurtleParser::graph_with_options(document, &strict)?.triples.len(), 2);
+
+    // The prefixes of datatypes are checked too, but not those of implied datatypes
+    assert!(TurtleParser::graph_with_options(r#"ex:a ex:b "1"^^undeclared:int ."#, &strict).is_err());
+
+    let strict = ParserOptions::empty().with_prefix("ex", "http://example.com/").strict();
+    let graph = TurtleParser::graph_with_options(r#"ex:a ex:b "x", "y"@en, true, 42 ."#, &strict)?;
+    assert_eq!(graph.triples.len(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn can_reject_truncated_statements() -> TestReturn {
+    // A statement without its terminator is skipped rather than read past
+    let document = "@prefix ex: <http://e.com/> .\nex:a ex:b ex:c .\nex:a ex:b ex:c";
+    let error = TurtleParser::graph(document).unwrap_err();
+    assert!(error.0.contains("unexpected end of statement"));
+
+    let graph = TurtleParser::graph_with_options(document, &ParserOptions::default().lenient())?;
+    assert_eq!(graph.triples.len(), 1);
+
+    // A word at the very end of the input is not dropped
+    assert!(TurtleParser::graph("ex:a ex:b ex:c .\nex:d").is_err());
+    assert!(TurtleParser::graph("ex:a ex:b [ ex:c ex:d").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_track_locations() -> TestReturn {
+    let options = ParserOptions::default().with_locations(None);
+    let mut graph = TurtleParser::from_file_with_options("./test_data/simple.ttl", &options)?;
+
+    let triple = TurtleParser::triple("ex:spiderman rel:enemyOf ex:green-goblin .")?.remove(0);
+    let locations = graph.locations_of(&triple).to_vec();
+    assert_eq!(locations, vec![SourceLocation {
+        source: Some("./test_data/simple.ttl".to_string()),
+        start: Position::new(12, 1),
+        end: Position::new(15, 46)
+    }]);
+    assert_eq!(locations[0].to_string(), "./test_data/simple.ttl:12:1-15:46");
+
+    // The locations follow the triples when their URIs are expanded
+    graph.apply_metadata()?;
+    let expanded = graph.triples.iter().find(|t| t.subject.to_string() == "http://example.com/spiderman").unwrap();
+    assert_eq!(graph.locations_of(expanded).len(), 1);
+
+    // Errors report where the invalid statement is
+    let error = TurtleParser::graph_with_options("ex:a ex:b ex:c .\n  ex:a ex:b .", &options).unwrap_err();
+    assert_eq!(error.0, "<input>:2:3-2:13: Object must be a resource, literal, or a property list. Found: .");
+
+    Ok(())
+}
+
+#[test]
+fn can_parse_in_parallel() -> TestReturn {
+    let mut document = String::from("@prefix ex: <http://example.com/> .\n");
+    for i in 0..100 {
+        document += &format!("ex:node{} ex:next ex:node{} .\n", i, i + 1);
+        document += &format!("ex:node{} ex:label [ ex:text \"node {}\" ] ;\n    ex:shared _:b .\n", i, i);
+    }
+
+    let options = ParserOptions::default().with_locations(Some("nodes.ttl"));
+    let sequential = TurtleParser::graph_with_options(&document, &options)?;
+    let parallel = TurtleParser::graph_parallel(&document, &options, 4)?;
+
+    assert_eq!(parallel.triples.len(), sequential.triples.len());
+    assert_eq!(parallel.prefixes, sequential.prefixes);
+    assert_isomorphic!(parallel, sequential);
+
+    // Generated blank nodes are unique across chunks while labelled ones are shared
+    let mut generated: Vec<String> = parallel.triples.iter()
+        .filter(|t| t.predicate.to_string() == "ex:label")
+        .map(|t| t.object.to_string())
+        .collect();
+    generated.sort();
+    generated.dedup();
+    assert_eq!(generated.len(), 100);
+
+    let shared = parallel.triples.iter()
+        .filter(|t| t.predicate.to_string() == "ex:shared")
+        .all(|t| t.object.to_string() == "_:b");
+    assert!(shared);
+
+    // Locations are relative to the whole document
+    let last = TurtleParser::triple("ex:node99 ex:next ex:node100 .")?.remove(0);
+    assert_eq!(parallel.locations_of(&last), sequential.locations_of(&last));
+    assert_eq!(parallel.locations_of(&last)[0].start, Position::new(299, 1));
+
+    Ok(())
+}
+
+#[test]
+fn can_parse_multi_line_statements_in_parallel() -> TestReturn {
+    let mut document = String::from("@prefix ex: <http://example.com/> .\n@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n");
+    for i in 0..20 {
+        document += &format!("ex:node{} rdfs:comment \"\"\"First paragraph of {} ends here.\n\nSecond paragraph.\"\"\" .\n", i, i);
+        document += &format!("ex:node{} ex:next\n\n    ex:node{} ;\n\n    rdfs:label \"\"\"a.\n\n\"\"\" ,\n    <http://example.com/a.b> .\n", i, i + 1);
+        document += &format!("ex:node{} ex:note \"not the end .\" ; # a comment .\n    ex:value 1.5 .\n\n", i);
+    }
+
+    let sequential = TurtleParser::graph(&document)?;
+    assert_eq!(sequential.triples.len(), 120);
+
+    for threads in [2, 3, 4, 8] {
+        let parallel = TurtleParser::graph_parallel(&document, &ParserOptions::default(), threads)?;
+        assert_eq!(parallel.triples, sequential.triples);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn can_stream_borrowed_triples() -> TestReturn {
+    let input = r#"
+        @prefix foaf: <http://xmlns.com/foaf/0.1/> .
+        # a comment
+        <http://example.com/foo#John> foaf:lastName "Johnson" ;
+            foaf:nick "Jo", "Johnny"@en ;
+            foaf:age "42"^^xsd:integer .
+        _:a foaf:knows <http://example.com/foo#John> .
+    "#;
+
+    let streamed: Vec<TripleRef> = TurtleParser::stream(input).collect::<Result<_, _>>()?;
+    assert_eq!(streamed.len(), 5);
+
+    // The terms point into the input
+    let name = streamed[0].subject.name;
+    let offset = name.as_ptr() as usize - input.as_ptr() as usize;
+    assert_eq!(&input[offset..offset + name.len()], "John");
+
+    // and convert to the same owned triples as the regular parser produces
+    let owned: Vec<Triple> = streamed.into_iter().map(Triple::from).collect();
+    let expected = TurtleParser::graph(input)?.triples;
+    assert_eq!(owned, expected);
+
+    // Terminators do not need to be preceded by whitespace
+    let triple = TurtleParser::stream("_:a foaf:nick 'Jo'@en.").next().unwrap()?;
+    assert_eq!(triple.object, ObjectRef::Literal(LiteralRef {
+        value: "'Jo'",
+        datatype: UriRef::new("rdf:", "langString", UriType::Prefixed),
+        language: Some("en")
+    }));
+
+    let mut stream = TurtleParser::stream(input);
+    stream.next();
+    assert_eq!(stream.prefixes().get("foaf:"), Some(&"http://xmlns.com/foaf/0.1/"));
+
+    // Property lists are not supported and end the iteration
+    let mut stream = TurtleParser::stream("ex:a ex:b [ ex:c ex:d ] . ex:e ex:f ex:g .");
+    assert!(stream.next().unwrap().is_err());
+    assert!(stream.next().is_none());
+
+    Ok(())
+}
+
+#[test]
+fn can_stream_the_same_literals_as_the_regular_parser() -> TestReturn {
+    let input = r#"
+        ex:a ex:count 42 , -7 , 123456789012345678901234567890 .
+        ex:a ex:ratio 1.5 , 0.5 , 1e3 , 2.5E-2 .
+        ex:a ex:flag true .
+        ex:a ex:label "Hello"@EN-us , "Bonjour"@fr .
+    "#;
+
+    let streamed: Vec<Triple> = TurtleParser::stream(input).map(|t| t.map(Triple::from)).collect::<Result<_, _>>()?;
+    let parsed = TurtleParser::graph(input)?.triples;
+    assert_eq!(streamed, parsed.to_vec());
+
+    // Words that Rust reads as floats are not numbers in Turtle
+    for word in ["inf", "NaN", "infinity"] {
+        let document = format!("ex:a ex:b {} .", word);
+        let parsed = TurtleParser::graph(&document)?;
+
+        assert!(parsed.triples[0].object.literal().is_some_and(|l| xsd::STRING.matches(&l.datatype)));
+        assert!(TurtleParser::stream(&document).next().unwrap().is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn can_parse_triple_terms() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        ex:Alice ex:claims <<( ex:Bob ex:age 23 )>> .
+        ex:Carol ex:claims <<(ex:Bob ex:knows <<( ex:Dan ex:age "40"^^xsd:integer )>>)>> .
+    "#)?;
+
+    let claim = graph.triples[0].object.triple().unwrap();
+    assert_eq!(claim, &TurtleParser::triple("ex:Bob ex:age 23 .")?[0]);
+
+    let nested = graph.triples[1].object.triple().unwrap();
+    assert_eq!(nested.object.triple().unwrap().object, TurtleParser::object(r#""40"^^xsd:integer"#)?);
+    assert_eq!(graph.triples.len(), 2);
+
+    // A triple term only refers to its triple, which is not asserted
+    assert!(!graph.triples.contains(claim));
+
+    assert!(TurtleParser::graph("ex:a ex:b <<( ex:c ex:d )>> .").is_err());
+    assert!(TurtleParser::graph("ex:a ex:b <<( ex:c ex:d ex:e >> .").is_err());
+    assert!(TurtleParser::graph_with_options(
+        "ex:a ex:b <<( ex:c undeclared:d ex:e )>> .",
+        &ParserOptions::default().strict()
+    ).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_parse_quoted_triples_and_annotations() -> TestReturn {
+    let reifies = TurtleParser::relationship("rdf:reifies")?;
+
+    let graph = TurtleParser::graph(r#"
+        << ex:Bob ex:age 23 >> ex:confidence 0.9 .
+        ex:Alice ex:says <<<http://example.com/people/Bob> ex:age 24>> .
+    "#)?;
+    let expected = TurtleParser::graph(r#"
+        _:blank1 rdf:reifies <<( ex:Bob ex:age 23 )>> .
+        _:blank1 ex:confidence 0.9 .
+        _:blank2 rdf:reifies <<( <http://example.com/people/Bob> ex:age 24 )>> .
+        ex:Alice ex:says _:blank2 .
+    "#)?;
+    assert_eq!(graph.triples, expected.triples);
+
+    let graph = TurtleParser::graph(r#"
+        ex:Bob ex:age 23 {| ex:source ex:Census ; ex:confidence 0.9 |} {| ex:source ex:Survey |} ;
+            ex:name "Bob" .
+    "#)?;
+    let bob = TurtleParser::triple("ex:Bob ex:age 23 .")?.remove(0);
+
+    assert!(graph.triples.contains(&bob));
+    assert!(graph.triples.contains(&TurtleParser::triple("ex:Bob ex:name \"Bob\" .")?.remove(0)));
+
+    let reifiers: Vec<&Triple> = graph.triples_matching(None, Some(&reifies), Some(&Object::Triple(Box::new(bob)))).collect();
+    assert_eq!(reifiers.len(), 2);
+    assert_eq!(graph.triples_matching(Some(&reifiers[0].subject), None, None).count(), 3);
+    assert_eq!(graph.triples.len(), 7);
+
+    // Annotations can be nested, and contain property lists
+    let graph = TurtleParser::graph(r#"
+        ex:a ex:b ex:c {| ex:by [ ex:name "Eve" ] {| ex:seen ex:Today |} |} .
+    "#)?;
+    assert_eq!(graph.triples.len(), 6);
+
+    Ok(())
+}
@@ -3,6 +3,9 @@ use std::collections::HashMap;
 use if_chain::if_chain;
 
 use crate::core::*;
+use crate::core::language;
+use crate::parsing::ParserOptions;
+use crate::vocab::{ rdf, xsd, VocabTerm };
 use crate::parsing::base::{
     Parsed,
     BaseParser,
@@ -36,6 +39,12 @@ enum Token {
     PropertyListClose,
     CollectionOpen,
     CollectionClose,
+    QuotedOpen,
+    QuotedClose,
+    TripleTermOpen,
+    TripleTermClose,
+    AnnotationOpen,
+    AnnotationClose,
 }
 
 impl Token {
@@ -63,46 +72,94 @@ impl std::fmt::Debug for Token {
             Token::PropertyListClose => write!(f, "]"),
             Token::CollectionOpen => write!(f, "("),
             Token::CollectionClose => write!(f, ")"),
+            Token::QuotedOpen => write!(f, "<<"),
+            Token::QuotedClose => write!(f, ">>"),
+            Token::TripleTermOpen => write!(f, "<<("),
+            Token::TripleTermClose => write!(f, ")>>"),
+            Token::AnnotationOpen => write!(f, "{{|"),
+            Token::AnnotationClose => write!(f, "|}}"),
         }
     }
 }
 
+// The first and last position of a token or statement
+type Span = (Position, Position);
+
+// Hands out the labels of the blank nodes generated for property lists and collections
+struct BlankNodeLabels<'a> {
+    prefix: &'a str,
+    count: usize
+}
+
+impl<'a> BlankNodeLabels<'a> {
+    fn new(prefix: &'a str) -> Self {
+        Self { prefix, count: 0 }
+    }
+
+    fn next(&mut self) -> String {
+        self.count += 1;
+        format!("_:{}{}", self.prefix, self.count)
+    }
+}
+
 pub struct TurtleParser;
 impl TurtleParser {
+    // A word of the input, or the keyword it spells
+    fn word(current: String) -> Token {
+        match &current as &str {
+            "@prefix" | "PREFIX" => Token::Keyword(Keyword::Prefix),
+            "@base" | "BASE" => Token::Keyword(Keyword::Base),
+            _ => Token::Word(current)
+        }
+    }
+
+    // The tokens after a blank property list, which are missing if it is never closed
+    fn after_property_list<'t>(token_parts: &[&'t [Token]]) -> Parsed<&'t [Token]> {
+        token_parts.get(1).copied().ok_or_else(|| ParserError("Unterminated blank property list".into()))
+    }
+
+    // The token at `i`, which a statement that ends too early does not have
+    fn token(tokens: &[Token], i: usize) -> Parsed<&Token> {
+        tokens.get(i).ok_or_else(|| ParserError("unexpected end of statement".into()))
+    }
+
     // A very simple lexer to tokenize rdf input for later parsing
     fn tokenize(s: &str) -> Vec<Token> {
+        Self::tokenize_with_spans(s, 1).into_iter().map(|(t, _)| t).collect()
+    }
+
+    // Tokenizes the input, keeping the first and last position of every token
+    fn tokenize_with_spans(s: &str, first_line: usize) -> Vec<(Token, Span)> {
         let s = s.replace("\r\n", "\n");
 
         let chars: Vec<char> = s.chars().collect();
 
         let mut tokens: Vec<Token> = Vec::new();
+        let mut spans: Vec<Span> = Vec::new();
         let mut current: String = String::new();
+
+        let mut position = Position::new(first_line, 1);
+        let mut current_span = (position, position);
         
         let mut quoted = false;
 
         let mut sequential_quotes = 0;
         let mut block_quoted = false;
+        let mut escaped = false;
 
         let mut commented = false;
 
         for c in chars {
             let ignored = quoted || block_quoted || commented;
 
+            let mut was_empty = current.is_empty();
+            let word_span = current_span;
+            let pushed = tokens.len();
+
             match c {
                 c if (c.is_whitespace() || c == ',') && !ignored => {
                     if !current.is_empty() {
-                        match &current as &str {
-                            "@prefix" | "PREFIX" => {
-                                tokens.push(Token::Keyword(Keyword::Prefix));
-                            },
-                            "@base" | "BASE" => {
-                                tokens.push(Token::Keyword(Keyword::Base));
-                            },
-                            _ => { 
-                                tokens.push(Token::Word(current));
-                            }
-                        }
-
+                        tokens.push(Self::word(current));
                         current = String::new();
                     }
                     
@@ -147,11 +204,45 @@ impl TurtleParser {
                 '#' if current.is_empty() && !ignored => {
                     commented = true;
                 },
+                '<' if current == "<" && !ignored => {
+                    current = String::new();
+                    tokens.push(Token::QuotedOpen);
+
+                    sequential_quotes = 0;
+                },
+                // A '>' that does not close an IRI is the start of a '>>'
+                '>' if !ignored && current.rfind('<') <= current.rfind('>') => {
+                    if current == ">" {
+                        current = String::new();
+                        tokens.push(Token::QuotedClose);
+                    } else {
+                        if !current.is_empty() {
+                            tokens.push(Token::Word(current));
+                            was_empty = true;
+                        }
+
+                        current = ">".into();
+                    }
+
+                    sequential_quotes = 0;
+                },
+                '|' if current == "{" && !ignored => {
+                    current = String::new();
+                    tokens.push(Token::AnnotationOpen);
+
+                    sequential_quotes = 0;
+                },
+                '}' if current == "|" && !ignored => {
+                    current = String::new();
+                    tokens.push(Token::AnnotationClose);
+
+                    sequential_quotes = 0;
+                },
                 '\n' if ignored => {
                     commented = false;
                 }
                 _ if !commented => { 
-                    if c == '"' { 
+                    if c == '"' && !escaped { 
                         quoted = !quoted;
 
                         sequential_quotes += 1;
@@ -165,66 +256,136 @@ impl TurtleParser {
                 },
                 _ => { }
             }
+
+            // A backslash within a string escapes the next character, so `\"` does not end it
+            escaped = (quoted || block_quoted) && c == '\\' && !escaped;
+
+            // Words span from their first to their last character, everything else is a
+            // single character.
+            for token in &tokens[pushed..] {
+                match token {
+                    Token::Word(_) | Token::Keyword(_) => spans.push(word_span),
+                    _ => spans.push((position, position))
+                }
+            }
+
+            if !current.is_empty() {
+                if was_empty {
+                    current_span.0 = position;
+                }
+                current_span.1 = position;
+            }
+
+            if c == '\n' {
+                position = Position::new(position.line + 1, 1);
+            } else {
+                position.column += 1;
+            }
         }
 
-        tokens
+        // The input may end in the middle of a word
+        if !current.is_empty() {
+            tokens.push(Self::word(current));
+            spans.push(current_span);
+        }
+
+        // `<<(` and `)>>` delimit triple terms
+        let mut merged: Vec<(Token, Span)> = Vec::new();
+        for (token, span) in tokens.into_iter().zip(spans) {
+            match (merged.last_mut(), &token) {
+                (Some((last @ Token::QuotedOpen, last_span)), Token::CollectionOpen) => {
+                    *last = Token::TripleTermOpen;
+                    last_span.1 = span.1;
+                },
+                (Some((last @ Token::CollectionClose, last_span)), Token::QuotedClose) => {
+                    *last = Token::TripleTermClose;
+                    last_span.1 = span.1;
+                },
+                _ => merged.push((token, span))
+            }
+        }
+
+        merged
     }
 
     // Expands an rdf collection into its corresponding blank property list format
-    // (https://w3.org/TR/turtle/ Examples 20 and 21)
-    fn expand_collection_tokens_naive(mut tokens: Vec<Token>) -> Parsed<Vec<Token>> {
-        let mut expanded: Vec<Token> = vec![Token::PropertyListOpen];
+    // (https://w3.org/TR/turtle/ Examples 20 and 21). Its items are words or blank property
+    // lists, which includes the collections within it once they are expanded.
+    fn expand_collection_tokens_naive(tokens: Vec<Token>) -> Parsed<Vec<Token>> {
+        // An empty collection is rdf:nil itself
+        if tokens.is_empty() {
+            return Ok(vec![Token::Word(rdf::NIL.to_string())]);
+        }
 
-        let last: Token = tokens.pop().unwrap();
-        
-        // Will fail if the collection contains anything but Token::Word(..)s 
-        // TODO: more thorough collection unwrapping
-        for word in &tokens {
-            if let Token::Word(word) = word {
-                expanded.append(&mut vec![
-                    Token::Word("rdf:first".into()),
-                    Token::Word(word.to_string()),
-                    Token::PredicateSep,
-                    Token::Word("rdf:rest".into()),
-                    Token::PropertyListOpen
-                ])
-            } else {
-                return Err(ParserError(format!("Collections can only contain Words; {:?}", word)));
+        let mut items: Vec<&[Token]> = Vec::new();
+        let mut start = 0;
+        let mut depth = 0;
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                Token::PropertyListOpen => depth += 1,
+                Token::PropertyListClose => depth -= 1,
+                Token::Word(_) => (),
+                token if depth == 0 => return Err(ParserError(format!("Collections can only contain Words; {:?}", token))),
+                _ => ()
+            }
+
+            if depth == 0 {
+                items.push(&tokens[start..=i]);
+                start = i + 1;
             }
         }
-        
-        expanded.append(&mut vec![
-            Token::Word("rdf:first".into()),
-            last,
-            Token::PredicateSep,
-            Token::Word("rdf:rest".into()),
-            Token::Word("rdf:nil".into()),
-            Token::PropertyListClose,
-            Token::PropertyListClose
-        ]);
+
+        let mut expanded: Vec<Token> = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            expanded.push(Token::PropertyListOpen);
+            expanded.push(Token::Word(rdf::FIRST.to_string()));
+            expanded.extend_from_slice(item);
+            expanded.push(Token::PredicateSep);
+            expanded.push(Token::Word(rdf::REST.to_string()));
+
+            if i == items.len() - 1 {
+                expanded.push(Token::Word(rdf::NIL.to_string()));
+            }
+        }
+
+        expanded.extend(items.iter().map(|_| Token::PropertyListClose));
 
         Ok(expanded)
     }
-    
-    fn parse_triple_recursive(mut tokens: Vec<Token>, mut triples: Vec<Triple>, blank_node_num: &mut usize) -> Parsed<Vec<Triple>> {
-        // Expand collections into blank property lists.
-        if tokens.contains(&Token::CollectionOpen) {
-            // Get the index of the first open paren
-            let first_open_index = tokens.iter().position(|t| t == &Token::CollectionOpen).unwrap();
-
-            // Get the index of the associated closing paren
-            let mut depth = 0;
-            let mut opened = false;
-            let collection_close_index = tokens.iter().position(|t| {
-                if let Token::CollectionOpen = t {
-                    opened = true;
-                    depth += 1;
-                } else if let Token::CollectionClose = t {
-                    depth -= 1;
+
+    fn parse_triple_recursive(mut tokens: Vec<Token>, mut triples: Vec<Triple>, blank_nodes: &mut BlankNodeLabels) -> Parsed<Vec<Triple>> {
+        // Replace the innermost triple term or quoted triple with a word
+        if let Some(close) = tokens.iter().position(|t| t == &Token::QuotedClose || t == &Token::TripleTermClose) {
+            let open = tokens[..close].iter().rposition(|t| t == &Token::QuotedOpen || t == &Token::TripleTermOpen)
+                .ok_or_else(|| ParserError(format!("Unmatched {:?}", tokens[close])))?;
+
+            let word = match (&tokens[open], &tokens[open + 1..close], &tokens[close]) {
+                // A triple term is kept as a word, and parsed as an object
+                (Token::TripleTermOpen, [Token::Word(s), Token::Word(p), Token::Word(o)], Token::TripleTermClose) => {
+                    format!("<<( {} {} {} )>>", s, p, o)
+                },
+                // A quoted triple stands for a blank node that reifies the triple term
+                (Token::QuotedOpen, [Token::Word(s), Token::Word(p), Token::Word(o)], Token::QuotedClose) => {
+                    let reifier = blank_nodes.next();
+                    let reified = (Self::resource(s)?, Self::relationship(p)?, Self::object(o)?).into();
+                    triples.push(Self::reification(&reifier, reified)?);
+
+                    reifier
+                },
+                (open, inner, close) => {
+                    return Err(ParserError(format!("Triple terms and quoted triples must contain a subject, a predicate and an object. Found: {:?} {}{:?}", open, Token::vec_to_string(inner.to_vec()), close)))
                 }
+            };
 
-                depth == 0 && opened
-            }).unwrap();
+            tokens.splice(open..=close, vec![Token::Word(word)]);
+            return Self::parse_triple_recursive(tokens, triples, blank_nodes);
+        }
+
+        // Expand collections into blank property lists, innermost first.
+        if let Some(collection_close_index) = tokens.iter().position(|t| t == &Token::CollectionClose) {
+            // Get the index of the associated open paren
+            let first_open_index = tokens[..collection_close_index].iter().rposition(|t| t == &Token::CollectionOpen)
+                .ok_or_else(|| ParserError("Unmatched ')'".into()))?;
 
             // Get the tokens just within the parens
             let collection_tokens = tokens[first_open_index + 1..collection_close_index].to_vec();
@@ -234,43 +395,70 @@ impl TurtleParser {
             // Replace the collection in the original token list with the expanded version
             tokens.splice(first_open_index..=collection_close_index, collection_tokens);
             // Parse the new expanded version of the tokens.
-            return Ok(Self::parse_triple_recursive(tokens, triples, blank_node_num)?);
+            return Self::parse_triple_recursive(tokens, triples, blank_nodes);
         }
 
         // If the first token is a word (the subject)...
-        if let Token::Word(subject) = &tokens[0] {
+        if let Token::Word(subject) = Self::token(&tokens, 0)? {
             // ...And the second token is a word (the predicate)...
-            if let Token::Word(predicate) = &tokens[1] {
+            if let Token::Word(predicate) = Self::token(&tokens, 1)? {
                 // ...And the third token is a word (the object)...
-                if let Token::Word(object) = &tokens[2] {
+                if let Token::Word(object) = Self::token(&tokens, 2)? {
                     // Then add this triple to the list.
                     triples.push(
-                       (Self::resource(&subject)?, Self::relationship(&predicate)?, Self::object(&object)?).into()
+                       (Self::resource(subject)?, Self::relationship(predicate)?, Self::object(object)?).into()
                     );
 
+                    // Each annotation reifies the triple, and describes its reifier
+                    let reified = triples.last().cloned().unwrap();
+                    let mut end = 3;
+                    while let Token::AnnotationOpen = Self::token(&tokens, end)? {
+                        let mut depth = 0;
+                        let close = end + tokens[end..].iter().position(|t| {
+                            if let Token::AnnotationOpen = t {
+                                depth += 1;
+                            } else if let Token::AnnotationClose = t {
+                                depth -= 1;
+                            }
+
+                            depth == 0
+                        }).ok_or_else(|| ParserError("Unterminated annotation".into()))?;
+
+                        let reifier = blank_nodes.next();
+                        triples.push(Self::reification(&reifier, reified.clone())?);
+
+                        let mut inner = vec![Token::Word(reifier)];
+                        inner.extend_from_slice(&tokens[end + 1..close]);
+                        inner.push(Token::TripleSep);
+                        triples = Self::parse_triple_recursive(inner, triples, blank_nodes)?;
+
+                        end = close + 1;
+                    }
+                    tokens.drain(3..end);
+
                     // If this is the end of the triple,
-                    if let Token::TripleSep = &tokens[3] {
+                    if let Token::TripleSep = Self::token(&tokens, 3)? {
                         // return the triples.
                         Ok(triples)
                     // If the triple continues with a list of predicates,
-                    } else if let Token::PredicateSep = &tokens[3] {
+                    } else if let Token::PredicateSep = Self::token(&tokens, 3)? {
                         // Remove the object and predicate pair that was just parsed
                         tokens.drain(1..=3);
                         // and continue parsing.
-                        Ok(Self::parse_triple_recursive(tokens, triples, blank_node_num)?)
+                        Ok(Self::parse_triple_recursive(tokens, triples, blank_nodes)?)
                     // If the triple continues with a list of objects,
-                    } else if let Token::ObjectSep = &tokens[3] {
+                    } else if let Token::ObjectSep = Self::token(&tokens, 3)? {
                         // Remove the object that was just parsed
                         tokens.drain(2..=3);
                         // and continue parsing
-                        Ok(Self::parse_triple_recursive(tokens, triples, blank_node_num)?)
+                        Ok(Self::parse_triple_recursive(tokens, triples, blank_nodes)?)
                     } else {
                         Err(ParserError(format!("Triple must end with ' .' or continue with ',' or ' ;'. Found: {:?}", &tokens[3])))
                     }
                 // ...And the object is a blank property list...
-                } else if let Token::PropertyListOpen = &tokens[2] {
-                    // First, increment the node_num for variable naming
-                    *blank_node_num += 1;
+                } else if let Token::PropertyListOpen = Self::token(&tokens, 2)? {
+                    // First, generate the label of the blank node
+                    let object = blank_nodes.next();
 
                     // Second, get just the inner portion of the list
                     let object_tokens = &tokens[2..].to_vec();
@@ -290,21 +478,20 @@ impl TurtleParser {
                     let mut inner = token_parts[0].to_vec();
 
                     // Replace the opening brace with a blank subject token
-                    let object = format!("_:blank{}", blank_node_num);
                     inner[0] = Token::Word(object.clone());
                     // And append with a triple terminator
                     inner.push(Token::TripleSep);
 
                     // Get the list of triples from within the blank prop list
-                    let inner_triples = Self::parse_triple_recursive(inner, triples.clone(), blank_node_num)?;
+                    let inner_triples = Self::parse_triple_recursive(inner, triples.clone(), blank_nodes)?;
 
                     // Insert the subject of the prop list as the object of the current triple
                     let mut tokens = tokens[..2].to_vec();
                     tokens.push(Token::Word(object));
-                    tokens.append(&mut token_parts[1].to_vec());
+                    tokens.extend_from_slice(Self::after_property_list(&token_parts)?);
                     
                     // Rerun with new tokens and triples
-                    Ok(Self::parse_triple_recursive(tokens, inner_triples, blank_node_num)?)
+                    Ok(Self::parse_triple_recursive(tokens, inner_triples, blank_nodes)?)
                 } else {
                     Err(ParserError(format!("Object must be a resource, literal, or a property list. Found: {:?}", &tokens[2])))
                 }
@@ -312,9 +499,9 @@ impl TurtleParser {
                 Err(ParserError(format!("Predicate must be a valid URI. Found: {:?}", &tokens[1])))
             }
         // If the subject is a blank property list...
-        } else if let Token::PropertyListOpen = &tokens[0] {
-            // First, increment the node_num for variable naming
-            *blank_node_num += 1;
+        } else if let Token::PropertyListOpen = Self::token(&tokens, 0)? {
+            // First, generate the label of the blank node
+            let subject = blank_nodes.next();
 
             // Second, get just the inner portion of the list
             let mut depth: i8 = 0;
@@ -331,25 +518,100 @@ impl TurtleParser {
             let mut inner = token_parts[0].to_vec();
 
             // Replace the opening brace with a blank subject token
-            inner[0] = Token::Word(format!("_:blank{}", blank_node_num));
+            inner[0] = Token::Word(subject.clone());
             // and append with a Triple terminator
             inner.push(Token::TripleSep);
 
             // Get the list of triples from the inner section of the blank prop list
-            let inner_triples = Self::parse_triple_recursive(inner, triples.clone(), blank_node_num)?;
+            let inner_triples = Self::parse_triple_recursive(inner, triples.clone(), blank_nodes)?;
             
             // Insert the subject of the prop list as the subject of the current triple
-            let mut tokens = token_parts[1].to_vec();
-            tokens.insert(0, Token::Word(inner_triples[0].subject.to_string()));
+            let mut tokens = vec![Token::Word(subject)];
+            tokens.extend_from_slice(Self::after_property_list(&token_parts)?);
 
             // Rerun with new triples and tokens
-            Ok(Self::parse_triple_recursive(tokens, inner_triples, blank_node_num)?)
+            Ok(Self::parse_triple_recursive(tokens, inner_triples, blank_nodes)?)
         } else {
             Err(ParserError(format!("Subject must be a valid URI or a blank property list. Found: {:?}", &tokens[0])))
         }
     }
 }
 
+impl TurtleParser {
+    // Parses a single statement, drawing generated blank node labels from `blank_nodes`
+    fn statement(t: &str, blank_nodes: &mut BlankNodeLabels) -> Parsed<Vec<Triple>> {
+        // Strip all of the whitespace tokens
+        let tokens: Vec<Token> = Self::tokenize(t).into_iter().filter(|t| t != &Token::Whitespace).collect();
+
+        Self::parse_triple_recursive(tokens, Vec::new(), blank_nodes)
+    }
+
+    // The triple stating that the blank node `reifier` reifies the triple term of `t`
+    fn reification(reifier: &str, t: Triple) -> Parsed<Triple> {
+        Ok((Self::resource(reifier)?, Relationship(rdf::REIFIES.prefixed()), Object::Triple(Box::new(t))).into())
+    }
+
+    // The datatype of a bare number: `xsd:integer`, `xsd:decimal` or `xsd:double` if it has an
+    // exponent
+    pub(crate) fn numeric_datatype(o: &str) -> Option<VocabTerm> {
+        use crate::core::xsd::matches;
+
+        if matches::INTEGER.is_match(o) {
+            Some(xsd::INTEGER)
+        } else if matches::DECIMAL.is_match(o) && o.contains('.') {
+            Some(xsd::DECIMAL)
+        } else if matches::DOUBLE.is_match(o) && o.contains(['e', 'E']) {
+            Some(xsd::DOUBLE)
+        } else {
+            None
+        }
+    }
+
+    // Returns true if the datatype of a literal was not written but implied by its form: a
+    // language tag, a bare boolean or number, or a plain string
+    fn has_implied_datatype(l: &Literal) -> bool {
+        l.language.is_some() || l.lexical_form() == l.value || xsd::STRING.matches(&l.datatype)
+    }
+
+    // A triple followed by the triple terms nested within it
+    fn with_triple_terms(t: &Triple) -> impl Iterator<Item = &Triple> {
+        std::iter::successors(Some(t), |t| t.object.triple())
+    }
+
+    // Ensures that the lexical form of every typed literal of the triples is valid
+    fn check_literals(triples: &[Triple]) -> Parsed<()> {
+        for triple in triples.iter().flat_map(Self::with_triple_terms) {
+            if let Some(literal) = triple.object.literal() {
+                literal.validate().map_err(|ParserError(e)| ParserError(format!("{} in {}", e, triple)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Ensures that every prefixed name of the triples uses a defined prefix
+    fn check_prefixes(triples: &[Triple], prefixes: &HashMap<String, String>) -> Parsed<()> {
+        use crate::core::uri::UriType;
+
+        for triple in triples.iter().flat_map(Self::with_triple_terms) {
+            let Resource(subject) = &triple.subject;
+            let Relationship(predicate) = &triple.predicate;
+            let datatype = triple.object.literal()
+                .filter(|l| !Self::has_implied_datatype(l))
+                .map(|l| &l.datatype);
+
+            let uris = vec![Some(subject), Some(predicate), triple.object.resource(), datatype];
+            for uri in uris.into_iter().flatten() {
+                if uri.uri_type == UriType::Prefixed && uri.prefix != "_:" && !prefixes.contains_key(&uri.prefix) {
+                    return Err(ParserError(format!("Use of prefix without first defining it: {} in {}", uri.prefix, triple)));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl BaseParser for TurtleParser {
     /// Parses a [`Uri`] from a string
     ///
@@ -360,7 +622,7 @@ impl BaseParser for TurtleParser {
     /// # Examples
     ///
     /// ```
-    /// # use rdf_rs::parsing::{ TurtleParser, RDFParser, ParserError };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
     /// # fn main() -> Result<(), ParserError> {
     /// let uri = TurtleParser::uri("<http://example.com/rdf/Person>")?;
     /// let uri = TurtleParser::uri("<#Person>")?;
@@ -370,47 +632,15 @@ impl BaseParser for TurtleParser {
     /// # }
     /// ```
     fn uri(u: &str) -> Parsed<Uri> {
-        use crate::core::uri::{ UriType, matches };
-
-        let full_url = &matches::FULL_URL;
-        let relative_url = &matches::RELATIVE_URL;
-        let prefixed = &matches::PREFIXED;
-        let empty_prefix = &matches::EMPTY_PREFIX;
-
         // Trim the leading and trailing whitespace.
         let u = u.trim();
 
-        // If u is in the form <http://[valid url]/foo> or <http://[valid url]#foo>
-        let (prefix, name, uri_type) = if full_url.is_match(u) {
-            let caps = full_url.captures(u).unwrap();
-
-            (caps[1].to_string(), caps[2].to_string(), UriType::Full)
-        // If u is in the form <#foo>
-        } else if relative_url.is_match(u) {
-            let caps = relative_url.captures(u).unwrap();
-
-            ("".into(), caps[1].to_string(), UriType::Relative)
-        // If u is in the form prefix:foo
-        } else if prefixed.is_match(u) {
-            let caps = prefixed.captures(u).unwrap();
-
-            (caps[1].to_string(), caps[2].to_string(), UriType::Prefixed)
-        // If u is in the form :foo
-        } else if empty_prefix.is_match(u) {
-            let caps = empty_prefix.captures(u).unwrap();
-
-            ("".into(), caps[1].to_string(), UriType::PrefixedWithBase)
-        // If u is the identity relationship
-        } else if u == "a" {
-            ("rdf:".into(), "type".into(), UriType::Full)
-        } else {
-            return Err(ParserError(format!("Invalid URI: {}", u)));
-        };
-
-        Ok(Uri::new(&prefix, &name, uri_type))
+        UriRef::parse(u)
+            .map(Uri::from)
+            .ok_or_else(|| ParserError(format!("Invalid URI: {}", u)))
     }
 
-    /// Parses a [`Resource`] from a string. A wrapper around [`RDFParser::uri()`] specifically 
+    /// Parses a [`Resource`] from a string. A wrapper around [`BaseParser::uri()`] specifically 
     /// for RDF resources.
     ///
     /// # Errors
@@ -420,7 +650,7 @@ impl BaseParser for TurtleParser {
     /// # Examples
     ///
     /// ```
-    /// # use rdf_rs::parsing::{ TurtleParser, RDFParser, ParserError };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
     /// # fn main() -> Result<(), ParserError> {
     /// let res = TurtleParser::resource("<http://example.com/rdf/Person>")?;
     /// let res = TurtleParser::resource("<#Person>")?;
@@ -434,7 +664,7 @@ impl BaseParser for TurtleParser {
         Ok(Resource(uri))
     }
 
-    /// Parses a [`Relationship`] from a string. A wrapper around [`RDFParser::uri()`] specifically 
+    /// Parses a [`Relationship`] from a string. A wrapper around [`BaseParser::uri()`] specifically 
     /// for RDF relationships.
     ///
     /// # Errors
@@ -444,7 +674,7 @@ impl BaseParser for TurtleParser {
     /// # Examples
     ///
     /// ```
-    /// # use rdf_rs::parsing::{ TurtleParser, RDFParser, ParserError };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
     /// # fn main() -> Result<(), ParserError> {
     /// let rel = TurtleParser::relationship("<http://example.com/foaf#knows>")?;
     /// let rel = TurtleParser::relationship("<#knows>")?;
@@ -467,11 +697,12 @@ impl BaseParser for TurtleParser {
     /// # Examples
     ///
     /// ```
-    /// # use rdf_rs::parsing::{ TurtleParser, RDFParser, ParserError };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
     /// # fn main() -> Result<(), ParserError> {
     /// let obj = TurtleParser::object(r#""john@example.com""#)?;
     /// let obj = TurtleParser::object(r#""すし"@jp"#)?;
     /// let obj = TurtleParser::object("foaf:Person")?;
+    /// let obj = TurtleParser::object("<<( ex:Bob foaf:age 23 )>>")?;
     /// # Ok(())
     /// # }
     /// ```
@@ -480,6 +711,18 @@ impl BaseParser for TurtleParser {
         let with_datatype = &matches::WITH_DATATYPE;
         let with_lang = &matches::WITH_LANG;
 
+        // If o is a triple term <<( s p o )>>
+        if let Some(inner) = o.strip_prefix("<<(").and_then(|o| o.strip_suffix(")>>")) {
+            let mut tokens: Vec<Token> = Self::tokenize(inner).into_iter().filter(|t| t != &Token::Whitespace).collect();
+            tokens.push(Token::TripleSep);
+
+            let mut triples = Self::parse_triple_recursive(tokens, Vec::new(), &mut BlankNodeLabels::new(""))?;
+            return match triples.len() {
+                1 => Ok(Object::Triple(Box::new(triples.remove(0)))),
+                _ => Err(ParserError(format!("A triple term must contain exactly one triple. Found: {}", o)))
+            };
+        }
+
         // If o is in the form "literal"^^datatype:uri
         if with_datatype.is_match(o) {
             let caps = with_datatype.captures(o).unwrap();
@@ -493,8 +736,8 @@ impl BaseParser for TurtleParser {
             let caps = with_lang.captures(o).unwrap();
             Ok(Object::Literal(Literal{
                 value: caps[1].to_string(),
-                datatype: Self::uri("rdf:langString")?,
-                language: Some(caps[2].to_string())
+                datatype: rdf::LANG_STRING.prefixed(),
+                language: Some(language::normalize(&caps[2]))
             }))
         // If o is a valid URI
         } else if let Ok(uri) = Self::uri(o) {
@@ -503,20 +746,28 @@ impl BaseParser for TurtleParser {
         } else if o == "true" || o == "false" {
             Ok(Object::Literal(Literal{
                 value: o.to_string(),
-                datatype: Self::uri("xsd:boolean")?,
+                datatype: xsd::BOOLEAN.prefixed(),
+                language: None
+            }))
+        // If o is a number
+        } else if let Some(datatype) = Self::numeric_datatype(o) {
+            Ok(Object::Literal(Literal{
+                value: o.to_string(),
+                datatype: datatype.prefixed(),
                 language: None
             }))
         // Else, o is a string literal
         } else {
             Ok(Object::Literal(Literal {
                 value: o.to_string(),
-                datatype: Self::uri("xsd:string")?,
+                datatype: xsd::STRING.prefixed(),
                 language: None
             }))
         }
     }
 
-    /// Parses a [`Vec<Triple>`] from a string.
+    /// Parses a [`Vec<Triple>`] from a string. Generated blank nodes are labelled with
+    /// [`ParserOptions::blank_node_prefix`].
     ///
     /// # Errors
     ///
@@ -526,30 +777,30 @@ impl BaseParser for TurtleParser {
     /// # Examples
     ///
     /// ```
-    /// # use rdf_rs::parsing::{ TurtleParser, RDFParser, ParserError };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
     /// # fn main() -> Result<(), ParserError> {
     /// let triple = TurtleParser::triple(r#"ex:John foaf:mbox "john@example.com" ."#)?;
     /// let triple = TurtleParser::triple(r#"[ foaf:name "Alice" ] foaf:knows [ foaf:name "Bob" ] ."#)?;
     /// # Ok(())
     /// # }
     /// ```
-    fn triple(t: &str) -> Parsed<Vec<Triple>> {
-        // Strip all of the whitespace tokens
-        let tokens: Vec<Token> = Self::tokenize(t).into_iter().filter(|t| t != &Token::Whitespace).collect();
-
-        Ok(Self::parse_triple_recursive(tokens, Vec::new(), &mut 0)?)
+    fn triple_with_options(t: &str, options: &ParserOptions) -> Parsed<Vec<Triple>> {
+        Self::statement(t, &mut BlankNodeLabels::new(&options.blank_node_prefix))
     }
     
-    /// Parses a [`Graph`] from a string (typically a file).
+    /// Parses a [`Graph`] from a string (typically a file). The graph starts out with the
+    /// prefixes and base IRI of the [`ParserOptions`], which are overridden by the `@prefix`
+    /// and `@base` directives of the document.
     ///
     /// # Errors
     ///
-    /// Returns a [`ParserError`] if the string is not a valid Graph.
+    /// Returns a [`ParserError`] if the string is not a valid Graph, or if it uses an undefined
+    /// prefix while [`ParserOptions::require_declared_prefixes`] is set.
     ///
     /// # Examples
     ///
     /// ```
-    /// # use rdf_rs::parsing::{ TurtleParser, RDFParser, ParserError };
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
     /// # fn main() -> Result<(), ParserError> {
     /// let triple = TurtleParser::graph(r#"
     ///     @base <http://example.com/> .
@@ -562,9 +813,10 @@ impl BaseParser for TurtleParser {
     /// # Ok(())
     /// # }
     /// ```
-    fn graph(g: &str) -> Parsed<Graph> {
-        let tokens: Vec<Token> = Self::tokenize(g);
-        let mut base_prefix: Option<String> = None;
+    fn graph_with_options(g: &str, options: &ParserOptions) -> Parsed<Graph> {
+        let spanned_tokens: Vec<(Token, Span)> = Self::tokenize_with_spans(g, options.first_line);
+        let tokens: Vec<Token> = spanned_tokens.iter().map(|(t, _)| t.clone()).collect();
+        let mut base_prefix: Option<String> = options.base.clone();
 
         // Set the base of the graph if it exists
         if tokens.contains(&Token::Keyword(Keyword::Base)) {
@@ -574,9 +826,9 @@ impl BaseParser for TurtleParser {
                 .split_inclusive(|&t| t == &Token::TripleSep)
                 .for_each(|w| {
                     if_chain! {
-                        if let Token::Keyword(Keyword::Base) = &w[0];
-                        if let Token::Word(prefix) = &w[1];
-                        if let Token::TripleSep = &w[2];
+                        if let Some(Token::Keyword(Keyword::Base)) = w.first();
+                        if let Some(Token::Word(prefix)) = w.get(1);
+                        if let Some(Token::TripleSep) = w.get(2);
                         then {
                             base_prefix = Some(prefix.replace(|c| { "<>".contains(c) }, ""));
                         }
@@ -584,11 +836,8 @@ impl BaseParser for TurtleParser {
                 });
         }
 
-        let mut prefixes: HashMap<String, String> = HashMap::new();
-
-        // default prefixes
-        prefixes.insert("rdf:".into(), "http://www.w3.org/1999/02/22-rdf-syntax-ns#".into());
-        prefixes.insert("xsd:".into(), "http://www.w3.org/2001/XMLSchema#".into());
+        // predefined prefixes
+        let mut prefixes: HashMap<String, String> = options.prefixes.clone();
 
         // If the graph contains prefixes, parse them
         if tokens.contains(&Token::Keyword(Keyword::Prefix)) {
@@ -598,10 +847,10 @@ impl BaseParser for TurtleParser {
                 .split_inclusive(|&t| t == &Token::TripleSep)
                 .for_each(|w| {
                     if_chain! {
-                        if let Token::Keyword(Keyword::Prefix) = &w[0];
-                        if let Token::Word(prefix) = &w[1];
-                        if let Token::Word(expanded) = &w[2];
-                        if let Token::TripleSep = &w[3];
+                        if let Some(Token::Keyword(Keyword::Prefix)) = w.first();
+                        if let Some(Token::Word(prefix)) = w.get(1);
+                        if let Some(Token::Word(expanded)) = w.get(2);
+                        if let Some(Token::TripleSep) = w.get(3);
                         then {
                             prefixes.insert(prefix.to_string(), expanded.replace(|c| { "<>".contains(c) }, ""));
                         }
@@ -609,21 +858,59 @@ impl BaseParser for TurtleParser {
                 });
         }
 
-        let full_triples: Vec<String> = tokens.as_slice()
-            .split_inclusive(|t| t == &Token::TripleSep)
-            .map(|tokens| Token::vec_to_string(tokens.to_vec()))
-            .filter(|triple| !(triple.starts_with("@base") || triple.starts_with("@prefix") || triple.is_empty()))
+        let full_triples: Vec<(String, Span)> = spanned_tokens.as_slice()
+            .split_inclusive(|(t, _)| t == &Token::TripleSep)
+            .map(|tokens| {
+                // The statement spans from its first non-whitespace token to its terminator
+                let start = tokens.iter().find(|(t, _)| t != &Token::Whitespace).map(|(_, s)| s.0);
+                let end = tokens.last().map(|(_, s)| s.1);
+                let span = (start.unwrap_or_default(), end.unwrap_or_default());
+
+                (Token::vec_to_string(tokens.iter().map(|(t, _)| t.clone()).collect()), span)
+            })
+            .filter(|(triple, _)| !(triple.starts_with("@base") || triple.starts_with("@prefix") || triple.is_empty()))
             .collect();
 
-        let triples: Vec<Triple> = full_triples.into_iter().flat_map(|t| {
+        // Blank node labels are unique across the whole graph
+        let mut blank_nodes = BlankNodeLabels::new(&options.blank_node_prefix);
+        let mut triples: Vec<Triple> = Vec::new();
+        let mut locations = SourceMap::new();
+
+        for (t, (start, end)) in full_triples {
+            let location = SourceLocation { source: options.source_name.clone(), start, end };
+
             // Trim leading and trailing whitespace
-            Self::triple(t.trim()).unwrap()
-        }).collect();
+            match Self::statement(t.trim(), &mut blank_nodes) {
+                Ok(mut statement) => {
+                    if options.track_locations {
+                        for triple in &statement {
+                            locations.insert(triple.clone(), location.clone());
+                        }
+                    }
+
+                    triples.append(&mut statement)
+                },
+                Err(_) if options.skip_invalid_statements => { },
+                Err(ParserError(e)) if options.track_locations => {
+                    return Err(ParserError(format!("{}: {}", location, e)))
+                },
+                Err(e) => return Err(e)
+            }
+        }
+
+        if options.require_declared_prefixes {
+            Self::check_prefixes(&triples, &prefixes)?;
+        }
+
+        if options.validate_literals {
+            Self::check_literals(&triples)?;
+        }
 
         Ok(Graph {
             base_prefix,
-            prefixes,
-            triples
+            prefixes: prefixes.into(),
+            triples: triples.into(),
+            locations
         })
     }
 }
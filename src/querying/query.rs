@@ -1,11 +1,11 @@
-use crate::core::{ Object, Triple };
+use crate::core::{ Object, Triple, LiteralValue };
 
 pub struct QueryBuilder {
     triples: Vec<Triple>
 }
 
 impl QueryBuilder {
-    pub fn start(triples: Vec<Triple>) -> Self {
+    pub(crate) fn start(triples: Vec<Triple>) -> Self {
         Self {
             triples
         }
@@ -43,8 +43,43 @@ impl QueryBuilder {
         self
     }
 
+    /// Keeps the triples whose object is a literal with a valid value for which `condition` is
+    /// true.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::LiteralValue;
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/ns/> .
+    ///     ex:John ex:age 42 .
+    ///     ex:Jane ex:age "017"^^xsd:integer .
+    /// "#)?;
+    ///
+    /// let adults = graph.normalize()?.start_query(0)
+    ///     .object_value(|v| v >= &LiteralValue::Integer(18))
+    ///     .query();
+    /// assert_eq!(adults.len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn object_value<F>(mut self, condition: F) -> Self
+        where F: Fn(&LiteralValue) -> bool {
+
+        self.triples.retain(|t| {
+            t.object.literal()
+                .and_then(|l| l.value().ok())
+                .map(|v| condition(&v))
+                .unwrap_or(false)
+        });
+
+        self
+    }
+
     pub fn value(self) -> Option<Object> {
-        if self.triples.len() > 0 {
+        if !self.triples.is_empty() {
             Some(self.triples[0].clone().object)
         } else {
             None
@@ -52,7 +87,7 @@ impl QueryBuilder {
     }
 
     pub fn values(self) -> Option<Vec<Object>> {
-        if self.triples.len() > 0 {
+        if !self.triples.is_empty() {
             Some(self.triples.into_iter().map(|t| t.object).collect())
         } else {
             None
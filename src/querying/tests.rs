@@ -3,33 +3,37 @@ use std::collections::HashSet;
 
 use crate::parsing::{ ParserError, TurtleParser, BaseParser };
 use crate::querying::QueryBuilder;
-use crate::core::Triple;
+use crate::core::{ Graph, Triple };
+use crate::assert_isomorphic;
 
 type TestReturn = Result<(), ParserError>;
 
 #[test]
 fn can_query_simple_graph() -> TestReturn {
-    let graph = TurtleParser::from_file("test_data/simple.ttl")?;
+    let graph = TurtleParser::from_file("test_data/simple.ttl")?.normalize()?;
 
-    let mut spiderman = graph.start_query(2)
-        .subject(|s| s == "ex:spiderman")
+    let spiderman = graph.start_query(2)
+        .subject(|s| s == "http://example.com/spiderman")
         .query();
-    spiderman.sort();
 
-    let mut expected_triples = TurtleParser::graph(r#"
+    let expected = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/> .
+        @prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix foaf: <http://xmlns.com/foaf/0.1/> .
+        @prefix rel: <http://www.perceive.net/schemas/relationship/> .
+
         ex:spiderman 
             foaf:name 
-                "Spiderman", "Человек-паук"@ru, _:blank1 ;
+                "Spiderman", "Человек-паук"@ru, _:spiderman, _:человек-паук ;
             rel:enemyOf ex:green-goblin ;
             rdf:type rdfs:Resource, foaf:Person .
-    "#)?.triples;
-    expected_triples.sort();
+    "#)?.normalize()?.into_inner();
 
-    assert_eq!(spiderman, expected_triples);
+    assert_isomorphic!(Graph { triples: spiderman.into(), ..expected.clone() }, expected);
 
     let name = graph.start_query(2)
-        .subject(|s| s == "ex:spiderman")
-        .predicate(|p| p == "foaf:name")
+        .subject(|s| s == "http://example.com/spiderman")
+        .predicate(|p| p == "http://xmlns.com/foaf/0.1/name")
         .value().unwrap();
     let name = name.literal().unwrap();
 
@@ -37,3 +41,25 @@ fn can_query_simple_graph() -> TestReturn {
 
     Ok(())
 }
+
+#[test]
+fn can_filter_by_temporal_values() -> TestReturn {
+    use crate::core::{ Date, LiteralValue };
+
+    let graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/ns/> .
+        ex:John ex:born "1990-04-01"^^xsd:date .
+        ex:Jane ex:born "2010-11-23Z"^^xsd:date .
+        ex:Jack ex:born "2005-06-15"^^xsd:date .
+        ex:Jill ex:born "soon"^^xsd:date .
+    "#)?.normalize()?;
+
+    let cutoff = LiteralValue::Date("2006-01-01".parse::<Date>()?);
+    let born_before = graph.start_query(0)
+        .object_value(|v| v < &cutoff)
+        .query();
+
+    assert_eq!(born_before.len(), 2);
+
+    Ok(())
+}
@@ -1,14 +1,30 @@
+use std::collections::HashSet;
+
 use crate::reasoning::Entailment;
-use crate::core::Triple;
+use crate::core::{ Triple, NormalizedGraph };
 
 pub trait BaseReasoner {
-    fn get_entailment_patterns() -> Vec<Entailment>;
-    fn get_inferred_triples(triples: Vec<Triple>, depth: usize) -> Vec<Triple> {
-        let rules = Self::get_entailment_patterns();
+    /// Returns the rules to infer triples from those of `graph` with. New blank nodes the rules
+    /// introduce get labels `graph` does not use.
+    fn get_entailment_patterns(graph: &NormalizedGraph) -> Vec<Entailment>;
+
+    /// Returns the axiomatic triples that hold for `graph`, which are inferred along with
+    /// those the rules derive from them. There are none by default.
+    fn get_axiomatic_triples(_graph: &NormalizedGraph) -> Vec<Triple> {
+        Vec::new()
+    }
+
+    /// Returns the triples inferred from those of `graph` in `depth` steps, along with the
+    /// axiomatic ones, sorted and without the triples `graph` already has.
+    ///
+    /// The graph is normalized, so the rules only ever compare absolute IRIs.
+    fn get_inferred_triples(graph: &NormalizedGraph, depth: usize) -> Vec<Triple> {
+        let triples = graph.triples.to_vec();
+        let rules = Self::get_entailment_patterns(graph);
 
-        let mut new_triples: Vec<Triple> = Vec::new();
+        let mut new_triples: Vec<Triple> = Self::get_axiomatic_triples(graph);
 
-        let mut buckets: Vec<Vec<Triple>> = vec![triples.clone()];
+        let mut buckets: Vec<Vec<Triple>> = vec![triples.iter().chain(new_triples.iter()).cloned().collect()];
 
         for d in 0..depth {
             let mut triple_bucket = Vec::new();
@@ -23,8 +39,7 @@ pub trait BaseReasoner {
                     }
 
                     // Double-input rule pass
-                    for j in i..buckets[d].len() {
-                        let triple_2 = &bucket_clone[j];
+                    for triple_2 in &bucket_clone[i..] {
                         if rule.verify(&vec![triple.clone(), triple_2.clone()]) {
                             triple_bucket.append(&mut rule.apply(&vec![triple.clone(), triple_2.clone()]));
                         }
@@ -35,15 +50,15 @@ pub trait BaseReasoner {
             buckets.push(triple_bucket);
         }
 
-        // The first bucket is the input triples
+        // The first bucket is the input triples and the axiomatic ones
         let buckets = buckets[1..].to_vec();
         new_triples.append(&mut buckets.into_iter().flatten().collect());
 
         // Sort and deduplicate
-        let triples_as_strings: Vec<String> = triples.into_iter().map(|t| t.to_string()).collect();
-        new_triples.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
-        new_triples.dedup_by(|a, b| a.to_string() == b.to_string());
-        new_triples.retain(|t| !triples_as_strings.contains(&t.to_string()));
+        let triples: HashSet<Triple> = triples.into_iter().collect();
+        new_triples.sort();
+        new_triples.dedup();
+        new_triples.retain(|t| !triples.contains(t));
 
         new_triples
     }
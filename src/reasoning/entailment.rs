@@ -1,10 +1,13 @@
 use crate::core::Triple;
 
+pub type InputPattern = Box<dyn Fn(&Vec<Triple>) -> bool>;
+pub type OutputPattern = Box<dyn Fn(&Vec<Triple>) -> Vec<Triple>>;
+
 pub struct Entailment {
     pub input_length: usize,
     pub output_length: usize,
-    pub input_pattern: Box<dyn Fn(&Vec<Triple>) -> bool>,
-    pub output_pattern: Box<dyn Fn(&Vec<Triple>) -> Vec<Triple>>
+    pub input_pattern: InputPattern,
+    pub output_pattern: OutputPattern
 }
 
 impl Entailment {
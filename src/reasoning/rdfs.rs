@@ -1,13 +1,115 @@
+use std::cell::{ Cell, RefCell };
+use std::collections::{ HashMap, HashSet };
+
 use crate::reasoning::{ BaseReasoner, Entailment };
-use crate::core::Triple;
-use crate::parsing::{ BaseParser, TurtleParser };
+use crate::core::merge::blank_labels;
+use crate::core::{ Triple, Resource, Relationship, Object, Literal, Uri, NormalizedGraph, uri::UriType, container::membership_index };
+use crate::vocab::{ rdf, rdfs };
+
+// The triple `subject predicate object`, as the output of a rule. The terms come from a
+// normalized graph or the vocabulary, so the triple is normalized as well.
+fn triple(subject: &Uri, predicate: &Uri, object: Object) -> Vec<Triple> {
+    vec![(Resource(subject.clone()), Relationship(predicate.clone()), object).into()]
+}
+
+// The blank nodes that stand for literals in rdfs1, the same for equal literals. The rules are
+// built for each inference, so the nodes are numbered in the order the literals are met,
+// skipping the labels the graph already uses.
+struct LiteralNodes {
+    used: HashSet<String>,
+    nodes: RefCell<HashMap<Literal, Uri>>,
+    next: Cell<usize>
+}
+
+impl LiteralNodes {
+    fn new(graph: &NormalizedGraph) -> Self {
+        let mut used = HashSet::new();
+        graph.triples.iter().for_each(|t| blank_labels(t, &mut used));
+
+        Self {
+            used: used.into_iter().map(str::to_string).collect(),
+            nodes: RefCell::new(HashMap::new()),
+            next: Cell::new(1)
+        }
+    }
+
+    fn node(&self, l: &Literal) -> Uri {
+        self.nodes.borrow_mut()
+            .entry(l.clone())
+            .or_insert_with(|| {
+                let (n, label) = (self.next.get()..)
+                    .map(|n| (n, format!("literal{}", n)))
+                    .find(|(_, label)| !self.used.contains(label))
+                    .unwrap();
+                self.next.set(n + 1);
+                Uri::new("_:", &label, UriType::BlankNode)
+            })
+            .clone()
+    }
+}
 
 pub struct RDFSReasoner;
+impl RDFSReasoner {
+    /// Returns the axiomatic triples of the container membership properties (`rdf:_1`, `rdf:_2`,
+    /// ...) that `graph` uses. RDF 1.1 Semantics states them for every `rdf:_n`, so they are
+    /// only generated for the properties in use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+    /// # use rdf_rs::reasoning::RDFSReasoner;
+    /// # fn main() -> Result<(), ParserError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/ns/> .
+    ///     @prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+    ///     ex:fruits a rdf:Bag ; rdf:_1 ex:apple ; rdf:_2 ex:pear .
+    /// "#)?;
+    /// let axioms = RDFSReasoner::container_membership_axioms(&graph.normalize()?);
+    ///
+    /// assert_eq!(axioms.len(), 8);
+    /// assert_eq!(axioms[1].to_string(), "http://www.w3.org/1999/02/22-rdf-syntax-ns#_1 \
+    ///     http://www.w3.org/1999/02/22-rdf-syntax-ns#type \
+    ///     http://www.w3.org/2000/01/rdf-schema#ContainerMembershipProperty .");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn container_membership_axioms(graph: &NormalizedGraph) -> Vec<Triple> {
+        let mut properties: Vec<&Uri> = Vec::new();
+        let mut seen = HashSet::new();
+
+        for t in graph.triples.iter() {
+            let uris = [Some(&t.subject.0), Some(&t.predicate.0), t.object.resource()];
+            for u in uris.iter().flatten() {
+                if let Some(n) = membership_index(u) {
+                    if seen.insert(n) {
+                        properties.push(u);
+                    }
+                }
+            }
+        }
+
+        properties.into_iter()
+            .flat_map(|p| vec![
+                (Resource(p.clone()), Relationship(rdf::TYPE.uri()), Object::Resource(rdf::PROPERTY.uri())).into(),
+                (Resource(p.clone()), Relationship(rdf::TYPE.uri()), Object::Resource(rdfs::CONTAINER_MEMBERSHIP_PROPERTY.uri())).into(),
+                (Resource(p.clone()), Relationship(rdfs::DOMAIN.uri()), Object::Resource(rdfs::RESOURCE.uri())).into(),
+                (Resource(p.clone()), Relationship(rdfs::RANGE.uri()), Object::Resource(rdfs::RESOURCE.uri())).into()
+            ])
+            .collect()
+    }
+}
+
 impl BaseReasoner for RDFSReasoner {
-    fn get_entailment_patterns() -> Vec<Entailment> {
+    fn get_axiomatic_triples(graph: &NormalizedGraph) -> Vec<Triple> {
+        Self::container_membership_axioms(graph)
+    }
+
+    fn get_entailment_patterns(graph: &NormalizedGraph) -> Vec<Entailment> {
         // https://www.w3.org/TR/rdf11-mt
         // Section 9.2.1
 
+        let literal_nodes = LiteralNodes::new(graph);
         let rdfs1 = Entailment {
             input_length: 1,
             output_length: 2,
@@ -17,14 +119,15 @@ impl BaseReasoner for RDFSReasoner {
                 }
             ),
             output_pattern: Box::new(
-                |triples: &Vec<Triple>| {
+                move |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
                     let predicate = &triples[0].predicate;
                     let object = &triples[0].object.literal().unwrap();
+                    let node = literal_nodes.node(object);
 
-                    TurtleParser::triple(&format!("{} {} [ rdf:type {} ] .",
-                        subject.to_string(), predicate.to_string(), object.datatype.to_string()
-                    )).unwrap()
+                    let mut new_triples = triple(&subject.0, &predicate.0, Object::Resource(node.clone()));
+                    new_triples.append(&mut triple(&node, &rdf::TYPE.uri(), Object::Resource(object.datatype.clone())));
+                    new_triples
                 }
             )
         };
@@ -37,17 +140,17 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate_a = &triples[0].predicate;
                     let predicate_b = &triples[1].predicate;
 
-                    if predicate_a.to_string() == "rdfs:domain" {
+                    if rdfs::DOMAIN.matches(&predicate_a.0) {
                         let subject_a = &triples[0].subject;
                         let object_a = &triples[0].object;
 
-                        predicate_b.to_string() == subject_a.to_string() &&
+                        predicate_b.0 == subject_a.0 &&
                             object_a.is_resource()
-                    } else if predicate_b.to_string() == "rdfs:domain" {
+                    } else if rdfs::DOMAIN.matches(&predicate_b.0) {
                         let subject_b = &triples[1].subject;
                         let object_b = &triples[1].object;
 
-                        predicate_a.to_string() == subject_b.to_string() &&
+                        predicate_a.0 == subject_b.0 &&
                             object_b.is_resource()
                     } else {
                         false
@@ -59,20 +162,16 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate_a = &triples[0].predicate;
                     let predicate_b = &triples[1].predicate;
 
-                    if predicate_a.to_string() == "rdfs:domain" {
+                    if rdfs::DOMAIN.matches(&predicate_a.0) {
                         let subject_b = &triples[1].subject;
-                        let object_a = &triples[0].object.resource().unwrap();
+                        let object_a = &triples[0].object;
 
-                        TurtleParser::triple(&format!("{} rdf:type {} .", 
-                            subject_b.to_string(), object_a.to_string()
-                        )).unwrap()
-                    } else if predicate_b.to_string() == "rdfs:domain" {
+                        triple(&subject_b.0, &rdf::TYPE.uri(), object_a.clone())
+                    } else if rdfs::DOMAIN.matches(&predicate_b.0) {
                         let subject_a = &triples[0].subject;
-                        let object_b = &triples[1].object.resource().unwrap();
+                        let object_b = &triples[1].object;
 
-                        TurtleParser::triple(&format!("{} rdf:type {} .", 
-                            subject_a.to_string(), object_b.to_string()
-                        )).unwrap()
+                        triple(&subject_a.0, &rdf::TYPE.uri(), object_b.clone())
                     } else {
                         panic!("Invalid entailment.")
                     }
@@ -91,15 +190,15 @@ impl BaseReasoner for RDFSReasoner {
                     let object_a = &triples[0].object;
                     let object_b = &triples[1].object;
                     
-                    if predicate_a.to_string() == "rdfs:range" {
+                    if rdfs::RANGE.matches(&predicate_a.0) {
                         let subject_a = &triples[0].subject;
 
-                        predicate_b.to_string() == subject_a.to_string() &&
+                        predicate_b.0 == subject_a.0 &&
                             object_a.is_resource() && object_b.is_resource()
-                    } else if predicate_b.to_string() == "rdfs:range" {
+                    } else if rdfs::RANGE.matches(&predicate_b.0) {
                         let subject_b = &triples[1].subject;
 
-                        predicate_a.to_string() == subject_b.to_string() &&
+                        predicate_a.0 == subject_b.0 &&
                             object_a.is_resource() && object_b.is_resource()
                     } else {
                         false
@@ -111,20 +210,16 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate_a = &triples[0].predicate;
                     let predicate_b = &triples[1].predicate;
 
-                    if predicate_a.to_string() == "rdfs:range" {
-                        let object_b = &triples[1].object.resource().unwrap();
-                        let object_a = &triples[0].object.resource().unwrap();
+                    if rdfs::RANGE.matches(&predicate_a.0) {
+                        let object_b = triples[1].object.resource().unwrap();
+                        let object_a = &triples[0].object;
 
-                        TurtleParser::triple(&format!("{} rdf:type {} .", 
-                            object_b.to_string(), object_a.to_string()
-                        )).unwrap()
-                    } else if predicate_b.to_string() == "rdfs:range" {
-                        let object_a = &triples[0].object.resource().unwrap();
-                        let object_b = &triples[1].object.resource().unwrap();
+                        triple(object_b, &rdf::TYPE.uri(), object_a.clone())
+                    } else if rdfs::RANGE.matches(&predicate_b.0) {
+                        let object_a = triples[0].object.resource().unwrap();
+                        let object_b = &triples[1].object;
 
-                        TurtleParser::triple(&format!("{} rdf:type {} .", 
-                            object_a.to_string(), object_b.to_string()
-                        )).unwrap()
+                        triple(object_a, &rdf::TYPE.uri(), object_b.clone())
                     } else {
                         panic!("Invalid entailment.")
                     }
@@ -139,14 +234,14 @@ impl BaseReasoner for RDFSReasoner {
                 |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
 
-                    subject.to_string() != "rdfs:Resource"
+                    !rdfs::RESOURCE.matches(&subject.0)
                 }
             ),
             output_pattern: Box::new(
                 |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
                     
-                    TurtleParser::triple(&format!("{} rdf:type rdfs:Resource .", subject.to_string())).unwrap()
+                    triple(&subject.0, &rdf::TYPE.uri(), Object::Resource(rdfs::RESOURCE.uri()))
                 }
             )
         };
@@ -158,14 +253,14 @@ impl BaseReasoner for RDFSReasoner {
                 |triples: &Vec<Triple>| {
                     let object = &triples[0].object;
 
-                    object.is_resource() && object.to_string() != "rdfs:Resource"
+                    object.is_resource() && !rdfs::RESOURCE.matches_object(object)
                 }
             ),
             output_pattern: Box::new(
                 |triples: &Vec<Triple>| {
-                    let object = &triples[0].object;
+                    let object = triples[0].object.resource().unwrap();
                     
-                    TurtleParser::triple(&format!("{} rdf:type rdfs:Resource .", object.to_string())).unwrap()
+                    triple(object, &rdf::TYPE.uri(), Object::Resource(rdfs::RESOURCE.uri()))
                 }
             )
         };
@@ -184,8 +279,8 @@ impl BaseReasoner for RDFSReasoner {
                     let object_a = &triples[0].object;
                     let object_b = &triples[1].object;
                     
-                    (predicate_a.to_string() == "rdfs:subPropertyOf" && predicate_b.to_string() == "rdfs:subPropertyOf") &&
-                        (object_a.to_string() == subject_b.to_string() || object_b.to_string() == subject_a.to_string())
+                    (rdfs::SUB_PROPERTY_OF.matches(&predicate_a.0) && rdfs::SUB_PROPERTY_OF.matches(&predicate_b.0)) &&
+                        (object_a.resource() == Some(&subject_b.0) || object_b.resource() == Some(&subject_a.0))
                 }
             ),
             output_pattern: Box::new(
@@ -196,14 +291,10 @@ impl BaseReasoner for RDFSReasoner {
                     let subject_a = &triples[0].subject;
                     let object_b = &triples[1].object;
 
-                    if subject_b.to_string() == object_a.to_string() {
-                        TurtleParser::triple(&format!("{} rdfs:subPropertyOf {} .", 
-                            subject_a.to_string(), object_b.to_string()
-                        )).unwrap()
-                    } else if subject_a.to_string() == object_b.to_string() {
-                        TurtleParser::triple(&format!("{} rdfs:subPropertyOf {} .",
-                            subject_b.to_string(), object_a.to_string()
-                        )).unwrap()
+                    if Some(&subject_b.0) == object_a.resource() {
+                        triple(&subject_a.0, &rdfs::SUB_PROPERTY_OF.uri(), object_b.clone())
+                    } else if Some(&subject_a.0) == object_b.resource() {
+                        triple(&subject_b.0, &rdfs::SUB_PROPERTY_OF.uri(), object_a.clone())
                     } else {
                         panic!("Invalid entailment.")
                     }
@@ -219,17 +310,15 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate = &triples[0].predicate;
                     let object = &triples[0].object;
 
-                    predicate.to_string() == "rdf:type" &&
-                        object.to_string() == "rdf:Property"
+                    rdf::TYPE.matches(&predicate.0) &&
+                        rdf::PROPERTY.matches_object(object)
                 }
             ),
             output_pattern: Box::new(
                 |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
 
-                    TurtleParser::triple(&format!("{} rdfs:subPropertyOf {0} .", 
-                        subject.to_string()
-                    )).unwrap()
+                    triple(&subject.0, &rdfs::SUB_PROPERTY_OF.uri(), Object::Resource(subject.0.clone()))
                 }
             )
         };
@@ -245,15 +334,15 @@ impl BaseReasoner for RDFSReasoner {
                     let object_a = &triples[0].object;
                     let object_b = &triples[1].object;
 
-                    if predicate_a.to_string() == "rdfs:subPropertyOf" {
+                    if rdfs::SUB_PROPERTY_OF.matches(&predicate_a.0) {
                         let subject_a = &triples[0].subject;
 
-                        subject_a.to_string() == predicate_b.to_string() &&
+                        subject_a.0 == predicate_b.0 &&
                             object_a.is_resource() && object_b.is_resource()
-                    } else if predicate_b.to_string() == "rdfs:subPropertyOf" {
+                    } else if rdfs::SUB_PROPERTY_OF.matches(&predicate_b.0) {
                         let subject_b = &triples[1].subject;
 
-                        subject_b.to_string() == predicate_a.to_string() &&
+                        subject_b.0 == predicate_a.0 &&
                             object_a.is_resource() && object_b.is_resource()
                     } else {
                         false
@@ -268,18 +357,14 @@ impl BaseReasoner for RDFSReasoner {
                     let object_a = &triples[0].object;
                     let object_b = &triples[1].object;
 
-                    if predicate_a.to_string() == "rdfs:subPropertyOf" {
+                    if rdfs::SUB_PROPERTY_OF.matches(&predicate_a.0) {
                         let subject_b = &triples[1].subject;
 
-                        TurtleParser::triple(&format!("{} {} {} .", 
-                            subject_b.to_string(), object_a.to_string(), object_b.to_string()
-                        )).unwrap()
-                    } else if predicate_b.to_string() == "rdfs:subProperyOf" {
+                        triple(&subject_b.0, object_a.resource().unwrap(), object_b.clone())
+                    } else if rdfs::SUB_PROPERTY_OF.matches(&predicate_b.0) {
                         let subject_a = &triples[0].subject;
 
-                        TurtleParser::triple(&format!("{} {} {} .", 
-                            subject_a.to_string(), object_b.to_string(), object_a.to_string()
-                        )).unwrap()
+                        triple(&subject_a.0, object_b.resource().unwrap(), object_a.clone())
                     } else {
                         panic!("Invalid entailment.")
                     }
@@ -295,17 +380,15 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate = &triples[0].predicate;
                     let object = &triples[0].object;
 
-                    predicate.to_string() == "rdf:type" &&
-                        object.to_string() == "rdfs:Class"
+                    rdf::TYPE.matches(&predicate.0) &&
+                        rdfs::CLASS.matches_object(object)
                 }
             ),
             output_pattern: Box::new(
                 |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
 
-                    TurtleParser::triple(&format!("{} rdfs:subClassOf rdfs:Resource .", 
-                        subject.to_string()
-                    )).unwrap()
+                    triple(&subject.0, &rdfs::SUB_CLASS_OF.uri(), Object::Resource(rdfs::RESOURCE.uri()))
                 }
             )
         };
@@ -318,16 +401,16 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate_a = &triples[0].predicate;
                     let predicate_b = &triples[1].predicate;
 
-                    if predicate_a.to_string() == "rdfs:subClassOf" && predicate_b.to_string() == "rdf:type" {
+                    if rdfs::SUB_CLASS_OF.matches(&predicate_a.0) && rdf::TYPE.matches(&predicate_b.0) {
                         let subject_a = &triples[0].subject;
                         let object_b = &triples[1].object;
 
-                        subject_a.to_string() == object_b.to_string()
-                    } else if predicate_b.to_string() == "rdfs:subClassOf" && predicate_a.to_string() == "rdf:type" {
+                        Some(&subject_a.0) == object_b.resource()
+                    } else if rdfs::SUB_CLASS_OF.matches(&predicate_b.0) && rdf::TYPE.matches(&predicate_a.0) {
                         let subject_b = &triples[1].subject;
                         let object_a = &triples[0].object;
 
-                        subject_b.to_string() == object_a.to_string()
+                        Some(&subject_b.0) == object_a.resource()
                     } else {
                         false
                     }
@@ -338,20 +421,16 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate_a = &triples[0].predicate;
                     let predicate_b = &triples[1].predicate;
 
-                    if predicate_a.to_string() == "rdfs:subClassOf" {
+                    if rdfs::SUB_CLASS_OF.matches(&predicate_a.0) {
                         let object_a = &triples[0].object;
                         let subject_b = &triples[1].subject;
 
-                        TurtleParser::triple(&format!("{} rdf:type {} .", 
-                            subject_b.to_string(), object_a.to_string()
-                        )).unwrap()
-                    } else if predicate_b.to_string() == "rdfs:subClassOf" {
+                        triple(&subject_b.0, &rdf::TYPE.uri(), object_a.clone())
+                    } else if rdfs::SUB_CLASS_OF.matches(&predicate_b.0) {
                         let object_b = &triples[1].object;
                         let subject_a = &triples[0].subject;
 
-                        TurtleParser::triple(&format!("{} rdf:type {} .", 
-                            subject_a.to_string(), object_b.to_string()
-                        )).unwrap()
+                        triple(&subject_a.0, &rdf::TYPE.uri(), object_b.clone())
                     } else {
                         panic!("Invalid entailment.")
                     }
@@ -367,17 +446,15 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate = &triples[0].predicate;
                     let object = &triples[0].object;
 
-                    predicate.to_string() == "rdf:type" &&
-                        object.to_string() == "rdfs:Class"
+                    rdf::TYPE.matches(&predicate.0) &&
+                        rdfs::CLASS.matches_object(object)
                 }
             ),
             output_pattern: Box::new(
                 |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
 
-                    TurtleParser::triple(&format!("{} rdfs:subClassOf {0} .", 
-                        subject.to_string()
-                    )).unwrap()
+                    triple(&subject.0, &rdfs::SUB_CLASS_OF.uri(), Object::Resource(subject.0.clone()))
                 }
             )
         };
@@ -396,8 +473,8 @@ impl BaseReasoner for RDFSReasoner {
                     let object_a = &triples[0].object;
                     let object_b = &triples[1].object;
                     
-                    (predicate_a.to_string() == "rdfs:subClassOf" && predicate_a.to_string() == predicate_b.to_string()) &&
-                        (object_a.to_string() == subject_b.to_string() || object_b.to_string() == subject_a.to_string())
+                    (rdfs::SUB_CLASS_OF.matches(&predicate_a.0) && rdfs::SUB_CLASS_OF.matches(&predicate_b.0)) &&
+                        (object_a.resource() == Some(&subject_b.0) || object_b.resource() == Some(&subject_a.0))
                 }
             ),
             output_pattern: Box::new(
@@ -408,14 +485,10 @@ impl BaseReasoner for RDFSReasoner {
                     let subject_a = &triples[0].subject;
                     let object_b = &triples[1].object;
 
-                    if subject_b.to_string() == object_a.to_string() {
-                        TurtleParser::triple(&format!("{} rdfs:subClassOf {} .", 
-                            subject_a.to_string(), object_b.to_string()
-                        )).unwrap()
-                    } else if subject_a.to_string() == object_b.to_string() {
-                        TurtleParser::triple(&format!("{} rdfs:subClassOf {} .", 
-                            subject_b.to_string(), object_a.to_string()
-                        )).unwrap()
+                    if Some(&subject_b.0) == object_a.resource() {
+                        triple(&subject_a.0, &rdfs::SUB_CLASS_OF.uri(), object_b.clone())
+                    } else if Some(&subject_a.0) == object_b.resource() {
+                        triple(&subject_b.0, &rdfs::SUB_CLASS_OF.uri(), object_a.clone())
                     } else {
                         panic!("Invalid entailment.")
                     }
@@ -431,17 +504,15 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate = &triples[0].predicate;
                     let object = &triples[0].object;
 
-                    predicate.to_string() == "rdf:type" &&
-                        object.to_string() == "rdfs:ContainerMembershipProperty"
+                    rdf::TYPE.matches(&predicate.0) &&
+                        rdfs::CONTAINER_MEMBERSHIP_PROPERTY.matches_object(object)
                 }
             ),
             output_pattern: Box::new(
                 |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
 
-                    TurtleParser::triple(&format!("{} rdfs:subPropertyOf rdfs:member .", 
-                        subject.to_string()
-                    )).unwrap()
+                    triple(&subject.0, &rdfs::SUB_PROPERTY_OF.uri(), Object::Resource(rdfs::MEMBER.uri()))
                 }
             )
         };
@@ -454,17 +525,15 @@ impl BaseReasoner for RDFSReasoner {
                     let predicate = &triples[0].predicate;
                     let object = &triples[0].object;
 
-                    predicate.to_string() == "rdf:type" &&
-                        object.to_string() == "rdfs:Datatype"
+                    rdf::TYPE.matches(&predicate.0) &&
+                        rdfs::DATATYPE.matches_object(object)
                 }
             ),
             output_pattern: Box::new(
                 |triples: &Vec<Triple>| {
                     let subject = &triples[0].subject;
 
-                    TurtleParser::triple(&format!("{} rdfs:subClassOf rdfs:Literal .", 
-                        subject.to_string()
-                    )).unwrap()
+                    triple(&subject.0, &rdfs::SUB_CLASS_OF.uri(), Object::Resource(rdfs::LITERAL.uri()))
                 }
             )
         };
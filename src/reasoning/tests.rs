@@ -1,51 +1,73 @@
 #![allow(dead_code, unused_imports)]
-use crate::parsing::{ BaseParser, TurtleParser, ParserError };
+use crate::parsing::{ BaseParser, TurtleParser, ParserError, ParserOptions };
 use crate::reasoning::{ RDFSReasoner, BaseReasoner };
+use crate::core::{ Graph, NormalizedGraph, Triple };
+use crate::assert_isomorphic;
 
 type TestReturn = Result<(), ParserError>;
 
+// Parses a document with the well-known prefixes and `ex:`, and normalizes it
+fn normalized(document: &str) -> Result<NormalizedGraph, ParserError> {
+    let options = ParserOptions::default()
+        .with_well_known_prefixes()
+        .with_prefix("ex", "http://example.com/ns/");
+
+    Ok(TurtleParser::graph_with_options(document, &options)?.normalize()?)
+}
+
+fn normalized_triples(document: &str) -> Result<Vec<Triple>, ParserError> {
+    Ok(normalized(document)?.triples.to_vec())
+}
+
 #[test]
 fn rdfs_reasoning() -> TestReturn {
-    let entailment_rules = &RDFSReasoner::get_entailment_patterns();
+    let entailment_rules = &RDFSReasoner::get_entailment_patterns(&normalized("")?);
 
     // rdfs1
     {
-        let triple = TurtleParser::triple("_:a _:b \"5.0\"^^xsd:string .")?;
+        let triple = normalized_triples("_:a _:b \"5.0\"^^xsd:string .")?;
         let rdfs1 = &entailment_rules[0];
         assert!(rdfs1.verify(&triple));
 
         let new_triples = rdfs1.apply(&triple);
-        let expected_triples = TurtleParser::graph("_:blank1 rdf:type xsd:string . _:a _:b _:blank1 .")?.triples;
-        assert_eq!(new_triples, expected_triples);
+        let expected = normalized("_:node rdf:type xsd:string . _:a _:b _:node .")?.into_inner();
+        assert_isomorphic!(Graph { triples: new_triples.clone().into(), ..expected.clone() }, expected);
+
+        // Equal literals stand for the same node and other literals for new ones
+        let same = rdfs1.apply(&normalized_triples("_:c _:d \"5.0\"^^xsd:string .")?);
+        let other = rdfs1.apply(&normalized_triples("_:c _:d \"6.0\"^^xsd:string .")?);
+        assert_eq!(same[0].object, new_triples[0].object);
+        assert_ne!(other[0].object, new_triples[0].object);
+        assert_eq!(other[1].subject.to_string(), "_:literal2");
     }
 
     // rdfs2
     {
-        let graph = TurtleParser::graph("_:a rdfs:domain _:x . _:y _:a _:z .")?;
-        let triples = &graph.triples;
+        let graph = normalized("_:a rdfs:domain _:x . _:y _:a _:z .")?;
+        let triples = &graph.triples.to_vec();
         let rdfs2 = &entailment_rules[1];
-        assert!(rdfs2.verify(&triples));
+        assert!(rdfs2.verify(triples));
 
-        let new_triples = rdfs2.apply(&triples);
-        let expected_triple = TurtleParser::triple("_:y rdf:type _:x .")?;
+        let new_triples = rdfs2.apply(triples);
+        let expected_triple = normalized_triples("_:y rdf:type _:x .")?;
         assert_eq!(new_triples, expected_triple);
     }
 
     // rdfs3
     {
-        let graph = TurtleParser::graph("_:a rdfs:range _:x . _:y _:a _:z .")?;
-        let triples = &graph.triples;
+        let graph = normalized("_:a rdfs:range _:x . _:y _:a _:z .")?;
+        let triples = &graph.triples.to_vec();
         let rdfs3 = &entailment_rules[2];
-        assert!(rdfs3.verify(&triples));
+        assert!(rdfs3.verify(triples));
 
-        let new_triples = rdfs3.apply(&triples);
-        let expected_triple = TurtleParser::triple("_:z rdf:type _:x .")?;
+        let new_triples = rdfs3.apply(triples);
+        let expected_triple = normalized_triples("_:z rdf:type _:x .")?;
         assert_eq!(new_triples, expected_triple);
     }
     
     // rdfs4a and rdfs4b
     {
-        let triple = TurtleParser::triple("_:x _:a _:y .")?;
+        let triple = normalized_triples("_:x _:a _:y .")?;
 
         let rdfs4a = &entailment_rules[3];
         assert!(rdfs4a.verify(&triple));
@@ -55,106 +77,106 @@ fn rdfs_reasoning() -> TestReturn {
 
         let mut new_triples = rdfs4a.apply(&triple);
         new_triples.append(&mut rdfs4b.apply(&triple));
-        let expected_triples = TurtleParser::graph("_:x rdf:type rdfs:Resource . _:y rdf:type rdfs:Resource .")?.triples;
+        let expected_triples = normalized("_:x rdf:type rdfs:Resource . _:y rdf:type rdfs:Resource .")?.triples.to_vec();
         assert_eq!(new_triples, expected_triples);
     }
 
     // rdfs5
     {
-        let triples = TurtleParser::graph("_:x rdfs:subPropertyOf _:y . _:y rdfs:subPropertyOf _:z .")?.triples;
+        let triples = normalized("_:x rdfs:subPropertyOf _:y . _:y rdfs:subPropertyOf _:z .")?.triples.to_vec();
         let rdfs5 = &entailment_rules[5];
         assert!(rdfs5.verify(&triples));
 
         let new_triple = rdfs5.apply(&triples);
-        let expected_triple = TurtleParser::triple("_:x rdfs:subPropertyOf _:z .")?;
+        let expected_triple = normalized_triples("_:x rdfs:subPropertyOf _:z .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs6
     {
-        let triple = TurtleParser::triple("_:x rdf:type rdf:Property .")?;
+        let triple = normalized_triples("_:x rdf:type rdf:Property .")?;
         let rdfs6 = &entailment_rules[6];
         assert!(rdfs6.verify(&triple));
 
         let new_triple = rdfs6.apply(&triple);
-        let expected_triple = TurtleParser::triple("_:x rdfs:subPropertyOf _:x .")?;
+        let expected_triple = normalized_triples("_:x rdfs:subPropertyOf _:x .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs7
     {
-        let triples = TurtleParser::graph("_:a rdfs:subPropertyOf _:b . _:x _:a _:y .")?.triples;
+        let triples = normalized("_:a rdfs:subPropertyOf _:b . _:x _:a _:y .")?.triples.to_vec();
         let rdfs7 = &entailment_rules[7];
         assert!(rdfs7.verify(&triples));
 
         let new_triple = rdfs7.apply(&triples);
-        let expected_triple = TurtleParser::triple("_:x _:b _:y .")?;
+        let expected_triple = normalized_triples("_:x _:b _:y .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs8
     {
-        let triple = TurtleParser::triple("_:x rdf:type rdfs:Class .")?;
+        let triple = normalized_triples("_:x rdf:type rdfs:Class .")?;
         let rdfs8 = &entailment_rules[8];
         assert!(rdfs8.verify(&triple));
 
         let new_triple = rdfs8.apply(&triple);
-        let expected_triple = TurtleParser::triple("_:x rdfs:subClassOf rdfs:Resource .")?;
+        let expected_triple = normalized_triples("_:x rdfs:subClassOf rdfs:Resource .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs9
     {
-        let triples = TurtleParser::graph("_:x rdfs:subClassOf _:y . _:z rdf:type _:x .")?.triples;
+        let triples = normalized("_:x rdfs:subClassOf _:y . _:z rdf:type _:x .")?.triples.to_vec();
         let rdfs9 = &entailment_rules[9];
         assert!(rdfs9.verify(&triples));
 
         let new_triple = rdfs9.apply(&triples);
-        let expected_triple = TurtleParser::triple("_:z rdf:type _:y .")?;
+        let expected_triple = normalized_triples("_:z rdf:type _:y .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs10
     {
-        let triple = TurtleParser::triple("_:x rdf:type rdfs:Class .")?;
+        let triple = normalized_triples("_:x rdf:type rdfs:Class .")?;
         let rdfs10 = &entailment_rules[10];
         assert!(rdfs10.verify(&triple));
 
         let new_triple = rdfs10.apply(&triple);
-        let expected_triple = TurtleParser::triple("_:x rdfs:subClassOf _:x .")?;
+        let expected_triple = normalized_triples("_:x rdfs:subClassOf _:x .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs11
     {
-        let triples = TurtleParser::graph("_:x rdfs:subClassOf _:y . _:y rdfs:subClassOf _:z .")?.triples;
+        let triples = normalized("_:x rdfs:subClassOf _:y . _:y rdfs:subClassOf _:z .")?.triples.to_vec();
         let rdfs11 = &entailment_rules[11];
         assert!(rdfs11.verify(&triples));
 
         let new_triple = rdfs11.apply(&triples);
-        let expected_triple = TurtleParser::triple("_:x rdfs:subClassOf _:z .")?;
+        let expected_triple = normalized_triples("_:x rdfs:subClassOf _:z .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs12
     {
-        let triple = TurtleParser::triple("_:x rdf:type rdfs:ContainerMembershipProperty .")?;
+        let triple = normalized_triples("_:x rdf:type rdfs:ContainerMembershipProperty .")?;
         let rdfs12 = &entailment_rules[12];
         assert!(rdfs12.verify(&triple));
 
         let new_triple = rdfs12.apply(&triple);
-        let expected_triple = TurtleParser::triple("_:x rdfs:subPropertyOf rdfs:member .")?;
+        let expected_triple = normalized_triples("_:x rdfs:subPropertyOf rdfs:member .")?;
         assert_eq!(new_triple, expected_triple);
     }
     
     // rdfs13
     {
-        let triple = TurtleParser::triple("_:x rdf:type rdfs:Datatype .")?;
+        let triple = normalized_triples("_:x rdf:type rdfs:Datatype .")?;
         let rdfs13 = &entailment_rules[13];
         assert!(rdfs13.verify(&triple));
 
         let new_triple = rdfs13.apply(&triple);
-        let expected_triple = TurtleParser::triple("_:x rdfs:subClassOf rdfs:Literal .")?;
+        let expected_triple = normalized_triples("_:x rdfs:subClassOf rdfs:Literal .")?;
         assert_eq!(new_triple, expected_triple);
     }
 
@@ -163,22 +185,87 @@ fn rdfs_reasoning() -> TestReturn {
 
 #[test]
 fn can_apply_entailment_to_graph() -> TestReturn {
-    let graph = TurtleParser::graph("ex:employer rdfs:domain foaf:Person ;\
+    let graph = normalized("ex:employer rdfs:domain foaf:Person ;\
                                         rdfs:range foaf:Organization .
                                     ex:John ex:employer ex:Company .")?;
 
-    let inferred = RDFSReasoner::get_inferred_triples(graph.triples, 2);
+    let inferred = RDFSReasoner::get_inferred_triples(&graph, 2);
 
-    let expected_triples = TurtleParser::graph("ex:Company rdf:type foaf:Organization ;\
+    let expected_triples = normalized("ex:Company rdf:type foaf:Organization ;\
                                                     rdf:type rdfs:Resource .\
                                                 ex:John rdf:type foaf:Person ;\
                                                     rdf:type rdfs:Resource .\
                                                 ex:employer rdf:type rdfs:Resource .\
                                                 foaf:Organization rdf:type rdfs:Resource .\
                                                 foaf:Person rdf:type rdfs:Resource .\
-                                               ")?.triples;
+                                               ")?.triples.to_vec();
+    let mut expected_triples = expected_triples;
+    expected_triples.sort();
+    assert_eq!(inferred, expected_triples);
+
+    Ok(())
+}
+
+
+#[test]
+fn can_infer_container_membership_axioms() -> TestReturn {
+    let graph = normalized("ex:steps rdf:_1 ex:mix ; rdf:_3 ex:bake .")?;
+
+    let inferred = RDFSReasoner::get_inferred_triples(&graph, 0);
+
+    let expected_triples = normalized("rdf:_1 rdf:type rdf:Property , rdfs:ContainerMembershipProperty ;\
+                                                    rdfs:domain rdfs:Resource ;\
+                                                    rdfs:range rdfs:Resource .\
+                                                rdf:_3 rdf:type rdf:Property , rdfs:ContainerMembershipProperty ;\
+                                                    rdfs:domain rdfs:Resource ;\
+                                                    rdfs:range rdfs:Resource .\
+                                               ")?.triples.to_vec();
+    let mut expected_triples = expected_triples;
+    expected_triples.sort();
     assert_eq!(inferred, expected_triples);
 
+    // The rules apply to the axioms as well
+    let graph = normalized("ex:steps rdf:_1 ex:mix .")?;
+    let inferred = RDFSReasoner::get_inferred_triples(&graph, 1);
+    let expected = normalized_triples("ex:mix rdf:type rdfs:Resource .")?.remove(0);
+    assert!(inferred.contains(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn can_reason_on_expanded_vocabulary() -> TestReturn {
+    let graph = normalized("<http://example.com/ns/Cat> <http://www.w3.org/2000/01/rdf-schema#subClassOf> ex:Animal .\
+                                    ex:Tom <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.com/ns/Cat> .")?;
+
+    let inferred = RDFSReasoner::get_inferred_triples(&graph, 1);
+
+    let expected = normalized_triples("ex:Tom rdf:type ex:Animal .")?.remove(0);
+    assert!(inferred.contains(&expected));
+
+    // rdfs7 applies whichever of the two triples comes first
+    let graph = normalized("ex:John ex:mother ex:Mary . ex:mother rdfs:subPropertyOf ex:parent .")?;
+    let inferred = RDFSReasoner::get_inferred_triples(&graph, 1);
+
+    let expected = normalized_triples("ex:John ex:parent ex:Mary .")?.remove(0);
+    assert!(inferred.contains(&expected));
+
     Ok(())
 }
 
+#[test]
+fn literal_nodes_do_not_take_the_labels_of_the_graph() -> TestReturn {
+    let graph = normalized("_:literal0 ex:name \"Zero\" . _:literal1 ex:name \"One\" .")?;
+
+    let inferred = RDFSReasoner::get_inferred_triples(&graph, 1);
+
+    let string = normalized_triples("ex:x ex:y xsd:string .")?.remove(0).object;
+    let mut nodes: Vec<String> = inferred.iter()
+        .filter(|t| t.object == string)
+        .map(|t| t.subject.to_string())
+        .collect();
+    nodes.sort();
+    assert_eq!(nodes, ["_:literal2", "_:literal3"]);
+
+    Ok(())
+}
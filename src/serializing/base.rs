@@ -0,0 +1,115 @@
+use std::error::Error;
+use std::io::Write;
+
+use crate::compression::Compression;
+use crate::core::*;
+use crate::parsing::ParserError;
+
+#[derive(Debug)]
+pub struct SerializerError(pub String);
+impl std::fmt::Display for SerializerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl<T: Error> From<T> for SerializerError {
+    fn from(err: T) -> Self {
+        SerializerError(err.to_string())
+    }
+}
+
+impl From<ParserError> for SerializerError {
+    fn from(err: ParserError) -> Self {
+        SerializerError(err.0)
+    }
+}
+
+pub type Serialized<T> = Result<T, SerializerError>;
+
+pub trait BaseSerializer {
+    /// Serializes what precedes the triples of a [`Graph`], such as its prefix declarations.
+    fn header(g: &Graph) -> Serialized<String>;
+
+    /// Serializes a single [`Triple`] of a [`Graph`] as a line, including its line break. The
+    /// graph provides the prefixes and base the triple's URIs are relative to.
+    fn triple(t: &Triple, g: &Graph) -> Serialized<String>;
+
+    /// Serializes each triple of a [`Graph`], in order. Serializers that look at the rest of the
+    /// graph to write a triple override this to do that work once for the whole graph, rather
+    /// than in every call of [`BaseSerializer::triple()`].
+    fn triples<'a>(g: &'a Graph) -> Box<dyn Iterator<Item = Serialized<String>> + Send + 'a> {
+        Box::new(g.triples.iter().map(move |t| Self::triple(t, g)))
+    }
+
+    /// Serializes a whole [`Graph`] to a string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SerializerError`] if a triple cannot be represented in the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser };
+    /// # use rdf_rs::serializing::{ TurtleSerializer, BaseSerializer, SerializerError };
+    /// # fn main() -> Result<(), SerializerError> {
+    /// let graph = TurtleParser::from_file("./test_data/simple.ttl")?;
+    /// let turtle = TurtleSerializer::graph(&graph)?;
+    ///
+    /// assert_eq!(TurtleParser::graph(&turtle)?.triples, graph.triples);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn graph(g: &Graph) -> Serialized<String> {
+        let mut s = Self::header(g)?;
+
+        for t in Self::triples(g) {
+            s += &t?;
+        }
+
+        Ok(s)
+    }
+
+    /// Writes a [`Graph`] to `writer`, one triple at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SerializerError`] if a triple cannot be represented in the format or if
+    /// writing fails.
+    fn write<W: Write>(g: &Graph, mut writer: W) -> Serialized<()> {
+        writer.write_all(Self::header(g)?.as_bytes())?;
+
+        for t in Self::triples(g) {
+            writer.write_all(t?.as_bytes())?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Acts as a wrapper around [`BaseSerializer::write()`] that compresses the output.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SerializerError`] if the graph cannot be serialized, if writing fails or if
+    /// the feature for the compression format is disabled.
+    fn write_compressed<W: Write>(g: &Graph, writer: W, compression: Compression) -> Serialized<()> {
+        let mut encoder = compression.encoder(writer)?;
+        Self::write(g, &mut encoder)?;
+        encoder.finish()?.flush()?;
+
+        Ok(())
+    }
+
+    /// Acts as a wrapper around [`BaseSerializer::write()`] that creates (or truncates) a file.
+    /// The output is compressed if the path ends with `.gz`, `.bz2` or `.zst`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SerializerError`] if the graph cannot be serialized or the file written.
+    fn to_file(g: &Graph, path: &str) -> Serialized<()> {
+        let file = std::fs::File::create(path)?;
+        Self::write_compressed(g, std::io::BufWriter::new(file), Compression::from_path(path))
+    }
+}
@@ -0,0 +1,110 @@
+use crate::core::*;
+use crate::core::uri::UriType;
+use crate::serializing::base::{
+    Serialized,
+    BaseSerializer,
+    SerializerError
+};
+
+pub struct NTriplesSerializer;
+impl NTriplesSerializer {
+    // Writes a URI as an absolute IRI (or blank node label), expanding it with the graph's
+    // prefixes and base
+    fn uri(u: &Uri, g: &Graph) -> Serialized<String> {
+        match u.uri_type {
+            UriType::Full => Ok(format!("<{}{}>", u.prefix, u.name)),
+            UriType::BlankNode => Ok(format!("_:{}", u.name)),
+            UriType::Prefixed if u.prefix == "_:" => Ok(format!("_:{}", u.name)),
+            UriType::Prefixed => {
+                g.prefixes.expand_uri(u)
+                    .map(|u| format!("<{}>", u))
+                    .map_err(|e| SerializerError(e.0))
+            },
+            UriType::Relative | UriType::PrefixedWithBase => {
+                g.base_prefix.as_ref()
+                    .map(|base| format!("<{}{}>", base, u.name))
+                    .ok_or_else(|| SerializerError(format!("Relative IRI without a base: {}", u.name)))
+            }
+        }
+    }
+
+    // Escapes the line breaks and unescaped quotes of a lexical form
+    pub(crate) fn escape(lexical_form: &str) -> String {
+        let mut escaped = String::new();
+        let mut backslash = false;
+
+        for c in lexical_form.chars() {
+            match c {
+                '"' if !backslash => escaped += "\\\"",
+                '\n' => escaped += "\\n",
+                '\r' => escaped += "\\r",
+                c => escaped.push(c)
+            }
+
+            backslash = c == '\\' && !backslash;
+        }
+
+        escaped
+    }
+
+    fn literal(l: &Literal, g: &Graph) -> Serialized<String> {
+        let value = format!("\"{}\"", Self::escape(l.lexical_form()));
+
+        if let Some(language) = &l.language {
+            Ok(format!("{}@{}", value, language))
+        } else if l.is_string() {
+            Ok(value)
+        } else {
+            Ok(format!("{}^^{}", value, Self::uri(&l.datatype, g)?))
+        }
+    }
+
+    // Writes a triple term in the N-Triples-star form `<<( s p o )>>` of RDF 1.2
+    fn object(o: &Object, g: &Graph) -> Serialized<String> {
+        match o {
+            Object::Literal(literal) => Self::literal(literal, g),
+            Object::Resource(resource) => Self::uri(resource, g),
+            Object::Triple(t) => {
+                Ok(format!("<<( {} {} {} )>>", Self::uri(&t.subject.0, g)?, Self::uri(&t.predicate.0, g)?, Self::object(&t.object, g)?))
+            }
+        }
+    }
+}
+
+impl BaseSerializer for NTriplesSerializer {
+    /// N-Triples has no directives, so the header is always empty.
+    fn header(_g: &Graph) -> Serialized<String> {
+        Ok(String::new())
+    }
+
+    /// Serializes a [`Triple`] as an N-Triples line with absolute IRIs.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`SerializerError`] if a prefix of the triple is not defined in the graph, or if
+    /// it contains a relative IRI while the graph has no base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser };
+    /// # use rdf_rs::serializing::{ NTriplesSerializer, BaseSerializer, SerializerError };
+    /// # fn main() -> Result<(), SerializerError> {
+    /// let graph = TurtleParser::graph(r#"
+    ///     @prefix ex: <http://example.com/> .
+    ///     ex:John a ex:Person .
+    /// "#)?;
+    /// let triple = NTriplesSerializer::triple(&graph.triples[0], &graph)?;
+    ///
+    /// assert_eq!(triple, "<http://example.com/John> \
+    ///     <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.com/Person> .\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn triple(t: &Triple, g: &Graph) -> Serialized<String> {
+        let Resource(subject) = &t.subject;
+        let Relationship(predicate) = &t.predicate;
+
+        Ok(format!("{} {} {} .\n", Self::uri(subject, g)?, Self::uri(predicate, g)?, Self::object(&t.object, g)?))
+    }
+}
@@ -0,0 +1,184 @@
+#![allow(dead_code, unused_imports)]
+use crate::core::*;
+use crate::core::uri::UriType;
+use crate::parsing::{ BaseParser, TurtleParser };
+use crate::serializing::{ BaseSerializer, TurtleSerializer, NTriplesSerializer, SerializerError };
+
+type TestReturn = Result<(), SerializerError>;
+
+#[test]
+fn can_round_trip_turtle() -> TestReturn {
+    let graph = TurtleParser::from_file("./test_data/simple.ttl")?;
+    let turtle = TurtleSerializer::graph(&graph)?;
+
+    assert!(turtle.starts_with("@prefix ex: <http://example.com/> .\n"));
+    assert!(turtle.contains("ex:spiderman foaf:name \"Человек-паук\"@ru .\n"));
+
+    let reparsed = TurtleParser::graph(&turtle)?;
+    assert_eq!(reparsed.triples, graph.triples);
+    assert_eq!(reparsed.prefixes, graph.prefixes);
+
+    Ok(())
+}
+
+#[test]
+fn can_serialize_ntriples() -> TestReturn {
+    let mut graph = TurtleParser::graph(r#"
+        @base <http://example.com/base/> .
+        @prefix ex: <http://example.com/> .
+
+        ex:John ex:age "42"^^xsd:integer ;
+            ex:knows [ ex:name "Jane"@en ] ;
+            ex:self <John> .
+    "#)?;
+    let mut motto = TurtleParser::triple("ex:John ex:motto \"\" .")?.remove(0);
+    if let Object::Literal(literal) = &mut motto.object {
+        literal.value = "\"\"\"Say \"hi\"\ntwice\"\"\"".to_string();
+    }
+
+    let mut triples = graph.triples.into_vec();
+    triples.insert(1, motto);
+    graph.triples = triples.into();
+
+    let expected = [
+        "<http://example.com/John> <http://example.com/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .",
+        "<http://example.com/John> <http://example.com/motto> \"Say \\\"hi\\\"\\ntwice\" .",
+        "_:blank1 <http://example.com/name> \"Jane\"@en .",
+        "<http://example.com/John> <http://example.com/knows> _:blank1 .",
+        "<http://example.com/John> <http://example.com/self> <http://example.com/base/John> .",
+    ];
+    assert_eq!(NTriplesSerializer::graph(&graph)?, expected.join("\n") + "\n");
+
+    let graph = TurtleParser::graph("foo:a foo:b foo:c .")?;
+    assert!(NTriplesSerializer::graph(&graph).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_serialize_triple_terms() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/> .
+
+        ex:Alice ex:claims <<( ex:Bob ex:age 23 )>> .
+        ex:Bob ex:age 23 {| ex:source ex:Census ; ex:confidence 0.9 |} .
+        << ex:Bob ex:name "Bob" >> ex:source ex:Survey .
+    "#)?;
+
+    let turtle = TurtleSerializer::graph(&graph)?;
+    assert!(turtle.contains("ex:Alice ex:claims <<( ex:Bob ex:age 23 )>> .\n"));
+    assert!(turtle.contains("ex:Bob ex:age 23 {| ex:source ex:Census ; ex:confidence 0.9 |} .\n"));
+    // The reified triple is not asserted, so its reifier cannot be an annotation
+    assert!(turtle.contains("_:blank2 rdf:reifies <<( ex:Bob ex:name \"Bob\" )>> .\n"));
+    assert_eq!(TurtleParser::graph(&turtle)?.triples, graph.triples);
+
+    let graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/> .
+        ex:Alice ex:claims <<( ex:Bob ex:knows <<( ex:Carol ex:age 40 )>> )>> .
+    "#)?;
+    assert_eq!(NTriplesSerializer::graph(&graph)?, "<http://example.com/Alice> <http://example.com/claims> \
+        <<( <http://example.com/Bob> <http://example.com/knows> \
+        <<( <http://example.com/Carol> <http://example.com/age> \"40\"^^<http://www.w3.org/2001/XMLSchema#integer> )>> )>> .\n");
+
+    Ok(())
+}
+
+#[test]
+fn can_compact_full_iris_in_turtle() -> TestReturn {
+    let graph = TurtleParser::graph(r#"
+        @prefix ex: <http://example.com/ns/> .
+        @prefix people: <http://example.com/ns/people/> .
+
+        <http://example.com/ns/people/John> <http://example.com/ns/knows> <http://example.org/ns/Jane> .
+    "#)?;
+
+    let turtle = TurtleSerializer::graph(&graph)?;
+    assert!(turtle.contains("people:John ex:knows <http://example.org/ns/Jane> .\n"));
+
+    let reparsed = TurtleParser::graph(&turtle)?;
+    assert_eq!(reparsed.triples.len(), 1);
+
+    // N-Triples reports undefined prefixes instead of panicking
+    let mut undefined = graph.clone();
+    undefined.prefixes.remove("ex");
+    undefined.triples = TurtleParser::triple("ex:John ex:knows ex:Jane .")?.into();
+    assert!(NTriplesSerializer::graph(&undefined).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn can_round_trip_constructed_literals() -> TestReturn {
+    let typed = |value: &str, datatype: &str| Literal {
+        value: value.into(),
+        datatype: Uri::new(if datatype == "langString" { "rdf:" } else { "xsd:" }, datatype, UriType::Prefixed),
+        language: None
+    };
+
+    let literals = vec![
+        Literal::from("hello world"),
+        Literal::from("42"),
+        Literal::from("true"),
+        Literal::from("Say \"hi\"\ntwice"),
+        Literal { language: Some("fr".into()), ..typed("chat", "langString") },
+        Literal::from(42),
+        Literal::from(false),
+        typed("42", "integer"),
+        typed("-1.5", "decimal"),
+        typed("1.", "decimal"),
+        typed("1E3", "double"),
+        typed("1.5", "double"),
+        typed("true", "boolean"),
+        typed("yes", "boolean"),
+        typed("2002-05-30", "date")
+    ];
+
+    let mut graph = TurtleParser::graph("@prefix ex: <http://example.com/> .")?;
+    graph.triples = literals.iter().enumerate()
+        .map(|(i, l)| Triple::from((
+            Resource(Uri::new("ex:", &format!("s{}", i), UriType::Prefixed)),
+            Relationship(Uri::new("ex:", "value", UriType::Prefixed)),
+            Object::Literal(l.clone())
+        )))
+        .collect::<Vec<Triple>>()
+        .into();
+
+    let turtle = TurtleSerializer::graph(&graph)?;
+    assert!(turtle.contains("ex:s0 ex:value \"hello world\" .\n"));
+    assert!(turtle.contains("ex:s1 ex:value \"42\" .\n"));
+    assert!(turtle.contains("ex:s3 ex:value \"Say \\\"hi\\\"\\ntwice\" .\n"));
+    assert!(turtle.contains("ex:s7 ex:value 42 .\n"));
+    assert!(turtle.contains("ex:s9 ex:value \"1.\"^^xsd:decimal .\n"));
+    assert!(turtle.contains("ex:s11 ex:value \"1.5\"^^xsd:double .\n"));
+
+    // Lexical forms keep their escapes when parsed
+    let mut expected = literals.clone();
+    expected[3].value = "Say \\\"hi\\\"\\ntwice".into();
+
+    let reparsed = TurtleParser::graph(&turtle)?;
+    let objects: Vec<Literal> = reparsed.triples.iter()
+        .filter_map(|t| t.object.literal().cloned())
+        .collect();
+    assert_eq!(objects, expected);
+
+    Ok(())
+}
+
+#[test]
+fn can_serialize_many_annotations() -> TestReturn {
+    let mut document = String::from("@prefix ex: <http://example.com/> .\n");
+    for i in 0..1000 {
+        document += &format!("ex:s{} ex:p {} {{| ex:source ex:Census |}} .\n", i, i);
+    }
+    // Used as an object, so it cannot be written as an annotation
+    document += "_:used rdf:reifies <<( ex:s0 ex:p 0 )>> ; ex:source ex:Survey .\nex:Bob ex:saw _:used .\n";
+
+    let graph = TurtleParser::graph(&document)?;
+    let turtle = TurtleSerializer::graph(&graph)?;
+
+    assert_eq!(turtle.matches("{|").count(), 1000);
+    assert!(turtle.contains("ex:Bob ex:saw _:used .\n"));
+    crate::assert_isomorphic!(TurtleParser::graph(&turtle)?, graph);
+
+    Ok(())
+}
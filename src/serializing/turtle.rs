@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+
+use crate::core::*;
+use crate::core::uri::UriType;
+use crate::core::merge::is_blank_node;
+use crate::parsing::TurtleParser;
+use crate::vocab::{ rdf, xsd };
+use crate::serializing::NTriplesSerializer;
+use crate::serializing::base::{
+    Serialized,
+    BaseSerializer
+};
+
+pub struct TurtleSerializer;
+impl TurtleSerializer {
+    // Writes a full IRI as a prefixed name where one of the graph's prefixes can compact it
+    fn uri(u: &Uri, g: &Graph) -> String {
+        match u.uri_type {
+            UriType::Full => g.prefixes.compact(&u.to_string()).unwrap_or_else(|| format!("<{}{}>", u.prefix, u.name)),
+            UriType::Relative => format!("<{}>", u.name),
+            UriType::PrefixedWithBase => format!(":{}", u.name),
+            UriType::Prefixed | UriType::BlankNode => u.to_string()
+        }
+    }
+
+    // Returns true if a literal can be written bare (`true`, `42`, `1.5`, `1E3`): a boolean or a
+    // number in the Turtle grammar of its datatype, whose datatype is then implied
+    fn is_bare(l: &Literal) -> bool {
+        let form = l.lexical_form();
+        let datatype = match form {
+            "true" | "false" => Some(xsd::BOOLEAN),
+            _ if form.ends_with('.') => None,
+            _ => TurtleParser::numeric_datatype(form)
+        };
+
+        l.language.is_none() && datatype.is_some_and(|d| d.matches(&l.datatype))
+    }
+
+    fn literal(l: &Literal, g: &Graph) -> String {
+        let quoted = l.lexical_form() != l.value;
+
+        if !quoted && Self::is_bare(l) {
+            return l.value.clone();
+        }
+
+        let value = if quoted {
+            l.value.clone()
+        } else {
+            format!("\"{}\"", NTriplesSerializer::escape(&l.value))
+        };
+
+        if let Some(language) = &l.language {
+            format!("{}@{}", value, language)
+        } else if l.is_string() {
+            value
+        } else {
+            format!("{}^^{}", value, Self::uri(&l.datatype, g))
+        }
+    }
+
+    fn object(o: &Object, g: &Graph) -> String {
+        match o {
+            Object::Literal(literal) => Self::literal(literal, g),
+            Object::Resource(resource) => Self::uri(resource, g),
+            Object::Triple(t) => format!("<<( {} {} {} )>>", Self::uri(&t.subject.0, g), Self::uri(&t.predicate.0, g), Self::object(&t.object, g))
+        }
+    }
+
+    fn is_reifies(u: &Uri) -> bool {
+        rdf::REIFIES.matches(u)
+    }
+
+    // The resources used as an object in the graph, including the subjects and objects of triple
+    // terms, which cannot be written as annotations
+    fn objects(g: &Graph) -> HashSet<&Uri> {
+        let mut objects = HashSet::new();
+
+        for t in g.triples.iter() {
+            for o in std::iter::successors(Some(&t.object), |o| o.triple().map(|t| &t.object)) {
+                objects.extend(o.resource());
+                objects.extend(o.triple().map(|t| &t.subject.0));
+            }
+        }
+
+        objects
+    }
+
+    // The triple that `reifier` is written as an annotation of: `reifier` must be a blank node
+    // that reifies a single triple of the graph, describes it with at least one other triple, and
+    // is not used anywhere else
+    fn annotated<'a>(reifier: &Uri, g: &'a Graph, objects: &HashSet<&Uri>) -> Option<&'a Triple> {
+        if !is_blank_node(reifier) {
+            return None;
+        }
+
+        let resource = Resource(reifier.clone());
+        let (reifications, descriptions): (Vec<&Triple>, Vec<&Triple>) = g.triples_matching(Some(&resource), None, None)
+            .partition(|t| Self::is_reifies(&t.predicate.0));
+
+        let reified = match (reifications.as_slice(), descriptions.is_empty()) {
+            ([reification], false) => reification.object.triple()?,
+            _ => return None
+        };
+
+        if g.triples.contains(reified) && !objects.contains(reifier) {
+            Some(reified)
+        } else {
+            None
+        }
+    }
+
+    // The annotations of a triple (` {| p o ; ... |}`), one for each reifier that is written as an
+    // annotation
+    fn annotations(t: &Triple, g: &Graph, objects: &HashSet<&Uri>) -> String {
+        let term = Object::Triple(Box::new(t.clone()));
+
+        g.triples_matching(None, None, Some(&term))
+            .filter(|r| Self::is_reifies(&r.predicate.0) && Self::annotated(&r.subject.0, g, objects) == Some(t))
+            .map(|r| {
+                let descriptions: Vec<String> = g.triples_matching(Some(&r.subject), None, None)
+                    .filter(|d| !Self::is_reifies(&d.predicate.0))
+                    .map(|d| format!("{} {}{}", Self::uri(&d.predicate.0, g), Self::object(&d.object, g), Self::annotations(d, g, objects)))
+                    .collect();
+
+                format!(" {{| {} |}}", descriptions.join(" ; "))
+            })
+            .collect()
+    }
+
+    // A triple as a statement, given the objects of its graph (see `TurtleSerializer::objects()`)
+    fn statement(t: &Triple, g: &Graph, objects: &HashSet<&Uri>) -> String {
+        let Resource(subject) = &t.subject;
+        let Relationship(predicate) = &t.predicate;
+
+        if Self::annotated(subject, g, objects).is_some() {
+            return String::new();
+        }
+
+        format!("{} {} {}{} .\n", Self::uri(subject, g), Self::uri(predicate, g), Self::object(&t.object, g), Self::annotations(t, g, objects))
+    }
+}
+
+impl BaseSerializer for TurtleSerializer {
+    /// Serializes the `@base` and `@prefix` directives of a [`Graph`], followed by an empty line.
+    fn header(g: &Graph) -> Serialized<String> {
+        let mut header = String::new();
+
+        if let Some(base) = &g.base_prefix {
+            header += &format!("@base <{}> .\n", base);
+        }
+
+        for (prefix, namespace) in g.prefixes.sorted() {
+            header += &format!("@prefix {} <{}> .\n", prefix, namespace);
+        }
+
+        if !header.is_empty() {
+            header += "\n";
+        }
+
+        Ok(header)
+    }
+
+    /// Serializes a [`Triple`] as a Turtle statement. Prefixed names and relative IRIs are kept as
+    /// they are, relying on the directives from [`TurtleSerializer::header()`], and full IRIs are
+    /// compacted with the prefixes of the graph where they can be (see
+    /// [`PrefixMap::compact()`](crate::core::PrefixMap::compact)).
+    ///
+    /// A blank node that only reifies (`rdf:reifies`) a triple of the graph and describes it is
+    /// written as an annotation of that triple (`s p o {| ... |}`), so the triples of the blank
+    /// node serialize to an empty string. Finding those looks at every triple of the graph, so
+    /// serialize whole graphs with [`BaseSerializer::graph()`] or [`BaseSerializer::write()`],
+    /// which only do it once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use rdf_rs::core::Graph;
+    /// # use rdf_rs::parsing::{ TurtleParser, BaseParser };
+    /// # use rdf_rs::serializing::{ TurtleSerializer, BaseSerializer, SerializerError };
+    /// # fn main() -> Result<(), SerializerError> {
+    /// let graph = TurtleParser::graph(r#"ex:John foaf:age "42"^^xsd:integer ."#)?;
+    /// let triple = TurtleSerializer::triple(&graph.triples[0], &graph)?;
+    ///
+    /// assert_eq!(triple, "ex:John foaf:age \"42\"^^xsd:integer .\n");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn triple(t: &Triple, g: &Graph) -> Serialized<String> {
+        Ok(Self::statement(t, g, &Self::objects(g)))
+    }
+
+    fn triples<'a>(g: &'a Graph) -> Box<dyn Iterator<Item = Serialized<String>> + Send + 'a> {
+        let objects = Self::objects(g);
+        Box::new(g.triples.iter().map(move |t| Ok(Self::statement(t, g, &objects))))
+    }
+}
@@ -0,0 +1,384 @@
+use crate::vocab::term::vocabulary;
+
+/// [RDF](https://www.w3.org/TR/rdf11-schema/) terms.
+pub mod rdf {
+    super::vocabulary!("rdf:", "http://www.w3.org/1999/02/22-rdf-syntax-ns#", {
+        TYPE = "type",
+        PROPERTY = "Property",
+        STATEMENT = "Statement",
+        SUBJECT = "subject",
+        PREDICATE = "predicate",
+        OBJECT = "object",
+        REIFIES = "reifies",
+        BAG = "Bag",
+        SEQ = "Seq",
+        ALT = "Alt",
+        VALUE = "value",
+        LIST = "List",
+        FIRST = "first",
+        REST = "rest",
+        NIL = "nil",
+        LANG_STRING = "langString",
+        DIR_LANG_STRING = "dirLangString",
+        HTML = "HTML",
+        XML_LITERAL = "XMLLiteral",
+        JSON = "JSON",
+        PLAIN_LITERAL = "PlainLiteral"
+    });
+}
+
+/// [RDF Schema](https://www.w3.org/TR/rdf11-schema/) terms.
+pub mod rdfs {
+    super::vocabulary!("rdfs:", "http://www.w3.org/2000/01/rdf-schema#", {
+        RESOURCE = "Resource",
+        CLASS = "Class",
+        LITERAL = "Literal",
+        DATATYPE = "Datatype",
+        CONTAINER = "Container",
+        CONTAINER_MEMBERSHIP_PROPERTY = "ContainerMembershipProperty",
+        SUB_CLASS_OF = "subClassOf",
+        SUB_PROPERTY_OF = "subPropertyOf",
+        DOMAIN = "domain",
+        RANGE = "range",
+        LABEL = "label",
+        COMMENT = "comment",
+        MEMBER = "member",
+        SEE_ALSO = "seeAlso",
+        IS_DEFINED_BY = "isDefinedBy"
+    });
+}
+
+/// [XML Schema](https://www.w3.org/TR/xmlschema11-2/) datatypes.
+pub mod xsd {
+    super::vocabulary!("xsd:", "http://www.w3.org/2001/XMLSchema#", {
+        STRING = "string",
+        NORMALIZED_STRING = "normalizedString",
+        TOKEN = "token",
+        LANGUAGE = "language",
+        NAME = "Name",
+        NC_NAME = "NCName",
+        NMTOKEN = "NMTOKEN",
+        BOOLEAN = "boolean",
+        DECIMAL = "decimal",
+        INTEGER = "integer",
+        NON_POSITIVE_INTEGER = "nonPositiveInteger",
+        NEGATIVE_INTEGER = "negativeInteger",
+        NON_NEGATIVE_INTEGER = "nonNegativeInteger",
+        POSITIVE_INTEGER = "positiveInteger",
+        LONG = "long",
+        INT = "int",
+        SHORT = "short",
+        BYTE = "byte",
+        UNSIGNED_LONG = "unsignedLong",
+        UNSIGNED_INT = "unsignedInt",
+        UNSIGNED_SHORT = "unsignedShort",
+        UNSIGNED_BYTE = "unsignedByte",
+        DOUBLE = "double",
+        FLOAT = "float",
+        DATE = "date",
+        TIME = "time",
+        DATE_TIME = "dateTime",
+        DATE_TIME_STAMP = "dateTimeStamp",
+        G_YEAR = "gYear",
+        G_YEAR_MONTH = "gYearMonth",
+        G_MONTH = "gMonth",
+        G_MONTH_DAY = "gMonthDay",
+        G_DAY = "gDay",
+        DURATION = "duration",
+        YEAR_MONTH_DURATION = "yearMonthDuration",
+        DAY_TIME_DURATION = "dayTimeDuration",
+        HEX_BINARY = "hexBinary",
+        BASE64_BINARY = "base64Binary",
+        ANY_URI = "anyURI"
+    });
+}
+
+/// [OWL 2](https://www.w3.org/TR/owl2-rdf-based-semantics/) terms.
+pub mod owl {
+    super::vocabulary!("owl:", "http://www.w3.org/2002/07/owl#", {
+        ONTOLOGY = "Ontology",
+        IMPORTS = "imports",
+        VERSION_INFO = "versionInfo",
+        VERSION_IRI = "versionIRI",
+        CLASS = "Class",
+        THING = "Thing",
+        NOTHING = "Nothing",
+        NAMED_INDIVIDUAL = "NamedIndividual",
+        OBJECT_PROPERTY = "ObjectProperty",
+        DATATYPE_PROPERTY = "DatatypeProperty",
+        ANNOTATION_PROPERTY = "AnnotationProperty",
+        FUNCTIONAL_PROPERTY = "FunctionalProperty",
+        INVERSE_FUNCTIONAL_PROPERTY = "InverseFunctionalProperty",
+        TRANSITIVE_PROPERTY = "TransitiveProperty",
+        SYMMETRIC_PROPERTY = "SymmetricProperty",
+        ASYMMETRIC_PROPERTY = "AsymmetricProperty",
+        REFLEXIVE_PROPERTY = "ReflexiveProperty",
+        IRREFLEXIVE_PROPERTY = "IrreflexiveProperty",
+        RESTRICTION = "Restriction",
+        ON_PROPERTY = "onProperty",
+        SOME_VALUES_FROM = "someValuesFrom",
+        ALL_VALUES_FROM = "allValuesFrom",
+        HAS_VALUE = "hasValue",
+        CARDINALITY = "cardinality",
+        MIN_CARDINALITY = "minCardinality",
+        MAX_CARDINALITY = "maxCardinality",
+        EQUIVALENT_CLASS = "equivalentClass",
+        EQUIVALENT_PROPERTY = "equivalentProperty",
+        DISJOINT_WITH = "disjointWith",
+        INVERSE_OF = "inverseOf",
+        SAME_AS = "sameAs",
+        DIFFERENT_FROM = "differentFrom",
+        UNION_OF = "unionOf",
+        INTERSECTION_OF = "intersectionOf",
+        COMPLEMENT_OF = "complementOf",
+        ONE_OF = "oneOf",
+        DEPRECATED = "deprecated"
+    });
+}
+
+/// [SKOS](https://www.w3.org/TR/skos-reference/) terms.
+pub mod skos {
+    super::vocabulary!("skos:", "http://www.w3.org/2004/02/skos/core#", {
+        CONCEPT = "Concept",
+        CONCEPT_SCHEME = "ConceptScheme",
+        COLLECTION = "Collection",
+        ORDERED_COLLECTION = "OrderedCollection",
+        IN_SCHEME = "inScheme",
+        HAS_TOP_CONCEPT = "hasTopConcept",
+        TOP_CONCEPT_OF = "topConceptOf",
+        PREF_LABEL = "prefLabel",
+        ALT_LABEL = "altLabel",
+        HIDDEN_LABEL = "hiddenLabel",
+        NOTATION = "notation",
+        NOTE = "note",
+        DEFINITION = "definition",
+        EXAMPLE = "example",
+        SCOPE_NOTE = "scopeNote",
+        HISTORY_NOTE = "historyNote",
+        EDITORIAL_NOTE = "editorialNote",
+        CHANGE_NOTE = "changeNote",
+        SEMANTIC_RELATION = "semanticRelation",
+        BROADER = "broader",
+        NARROWER = "narrower",
+        RELATED = "related",
+        BROADER_TRANSITIVE = "broaderTransitive",
+        NARROWER_TRANSITIVE = "narrowerTransitive",
+        MEMBER = "member",
+        MEMBER_LIST = "memberList",
+        MAPPING_RELATION = "mappingRelation",
+        EXACT_MATCH = "exactMatch",
+        CLOSE_MATCH = "closeMatch",
+        BROAD_MATCH = "broadMatch",
+        NARROW_MATCH = "narrowMatch",
+        RELATED_MATCH = "relatedMatch"
+    });
+}
+
+/// [FOAF](http://xmlns.com/foaf/spec/) terms.
+pub mod foaf {
+    super::vocabulary!("foaf:", "http://xmlns.com/foaf/0.1/", {
+        AGENT = "Agent",
+        PERSON = "Person",
+        ORGANIZATION = "Organization",
+        GROUP = "Group",
+        DOCUMENT = "Document",
+        IMAGE = "Image",
+        PROJECT = "Project",
+        ONLINE_ACCOUNT = "OnlineAccount",
+        NAME = "name",
+        GIVEN_NAME = "givenName",
+        FAMILY_NAME = "familyName",
+        NICK = "nick",
+        TITLE = "title",
+        MBOX = "mbox",
+        PHONE = "phone",
+        HOMEPAGE = "homepage",
+        WEBLOG = "weblog",
+        PAGE = "page",
+        KNOWS = "knows",
+        AGE = "age",
+        BIRTHDAY = "birthday",
+        INTEREST = "interest",
+        TOPIC = "topic",
+        PRIMARY_TOPIC = "primaryTopic",
+        DEPICTION = "depiction",
+        IMG = "img",
+        MADE = "made",
+        MAKER = "maker",
+        MEMBER = "member",
+        ACCOUNT = "account",
+        ACCOUNT_NAME = "accountName",
+        BASED_NEAR = "based_near"
+    });
+}
+
+/// [Dublin Core](https://www.dublincore.org/specifications/dublin-core/dcmi-terms/) elements.
+pub mod dc {
+    super::vocabulary!("dc:", "http://purl.org/dc/elements/1.1/", {
+        CONTRIBUTOR = "contributor",
+        COVERAGE = "coverage",
+        CREATOR = "creator",
+        DATE = "date",
+        DESCRIPTION = "description",
+        FORMAT = "format",
+        IDENTIFIER = "identifier",
+        LANGUAGE = "language",
+        PUBLISHER = "publisher",
+        RELATION = "relation",
+        RIGHTS = "rights",
+        SOURCE = "source",
+        SUBJECT = "subject",
+        TITLE = "title",
+        TYPE = "type"
+    });
+}
+
+/// [DCMI Metadata Terms](https://www.dublincore.org/specifications/dublin-core/dcmi-terms/).
+pub mod dcterms {
+    super::vocabulary!("dcterms:", "http://purl.org/dc/terms/", {
+        AGENT = "Agent",
+        LICENSE_DOCUMENT = "LicenseDocument",
+        LOCATION = "Location",
+        PERIOD_OF_TIME = "PeriodOfTime",
+        ABSTRACT = "abstract",
+        ACCESS_RIGHTS = "accessRights",
+        ALTERNATIVE = "alternative",
+        AUDIENCE = "audience",
+        AVAILABLE = "available",
+        BIBLIOGRAPHIC_CITATION = "bibliographicCitation",
+        CONFORMS_TO = "conformsTo",
+        CONTRIBUTOR = "contributor",
+        COVERAGE = "coverage",
+        CREATED = "created",
+        CREATOR = "creator",
+        DATE = "date",
+        DESCRIPTION = "description",
+        EXTENT = "extent",
+        FORMAT = "format",
+        HAS_PART = "hasPart",
+        HAS_VERSION = "hasVersion",
+        IDENTIFIER = "identifier",
+        IS_PART_OF = "isPartOf",
+        IS_REPLACED_BY = "isReplacedBy",
+        IS_VERSION_OF = "isVersionOf",
+        ISSUED = "issued",
+        LANGUAGE = "language",
+        LICENSE = "license",
+        MODIFIED = "modified",
+        PUBLISHER = "publisher",
+        REFERENCES = "references",
+        RELATION = "relation",
+        REPLACES = "replaces",
+        REQUIRES = "requires",
+        RIGHTS = "rights",
+        SOURCE = "source",
+        SPATIAL = "spatial",
+        SUBJECT = "subject",
+        TEMPORAL = "temporal",
+        TITLE = "title",
+        TYPE = "type",
+        VALID = "valid"
+    });
+}
+
+/// [SHACL](https://www.w3.org/TR/shacl/) terms.
+pub mod sh {
+    super::vocabulary!("sh:", "http://www.w3.org/ns/shacl#", {
+        SHAPE = "Shape",
+        NODE_SHAPE = "NodeShape",
+        PROPERTY_SHAPE = "PropertyShape",
+        VALIDATION_REPORT = "ValidationReport",
+        VALIDATION_RESULT = "ValidationResult",
+        VIOLATION = "Violation",
+        WARNING = "Warning",
+        INFO = "Info",
+        IRI = "IRI",
+        BLANK_NODE = "BlankNode",
+        LITERAL = "Literal",
+        BLANK_NODE_OR_IRI = "BlankNodeOrIRI",
+        BLANK_NODE_OR_LITERAL = "BlankNodeOrLiteral",
+        IRI_OR_LITERAL = "IRIOrLiteral",
+        TARGET_CLASS = "targetClass",
+        TARGET_NODE = "targetNode",
+        TARGET_SUBJECTS_OF = "targetSubjectsOf",
+        TARGET_OBJECTS_OF = "targetObjectsOf",
+        PROPERTY = "property",
+        PATH = "path",
+        NODE = "node",
+        CLASS = "class",
+        DATATYPE = "datatype",
+        NODE_KIND = "nodeKind",
+        MIN_COUNT = "minCount",
+        MAX_COUNT = "maxCount",
+        MIN_INCLUSIVE = "minInclusive",
+        MAX_INCLUSIVE = "maxInclusive",
+        MIN_EXCLUSIVE = "minExclusive",
+        MAX_EXCLUSIVE = "maxExclusive",
+        MIN_LENGTH = "minLength",
+        MAX_LENGTH = "maxLength",
+        PATTERN = "pattern",
+        FLAGS = "flags",
+        LANGUAGE_IN = "languageIn",
+        UNIQUE_LANG = "uniqueLang",
+        IN = "in",
+        HAS_VALUE = "hasValue",
+        NOT = "not",
+        AND = "and",
+        OR = "or",
+        XONE = "xone",
+        CLOSED = "closed",
+        IGNORED_PROPERTIES = "ignoredProperties",
+        DEACTIVATED = "deactivated",
+        SEVERITY = "severity",
+        MESSAGE = "message",
+        NAME = "name",
+        DESCRIPTION = "description",
+        ORDER = "order",
+        GROUP = "group",
+        CONFORMS = "conforms",
+        RESULT = "result",
+        FOCUS_NODE = "focusNode",
+        RESULT_PATH = "resultPath",
+        RESULT_SEVERITY = "resultSeverity",
+        RESULT_MESSAGE = "resultMessage",
+        VALUE = "value",
+        SOURCE_SHAPE = "sourceShape",
+        SOURCE_CONSTRAINT_COMPONENT = "sourceConstraintComponent"
+    });
+}
+
+/// [PROV-O](https://www.w3.org/TR/prov-o/) terms.
+pub mod prov {
+    super::vocabulary!("prov:", "http://www.w3.org/ns/prov#", {
+        ENTITY = "Entity",
+        ACTIVITY = "Activity",
+        AGENT = "Agent",
+        PERSON = "Person",
+        ORGANIZATION = "Organization",
+        SOFTWARE_AGENT = "SoftwareAgent",
+        PLAN = "Plan",
+        BUNDLE = "Bundle",
+        COLLECTION = "Collection",
+        LOCATION = "Location",
+        WAS_GENERATED_BY = "wasGeneratedBy",
+        WAS_DERIVED_FROM = "wasDerivedFrom",
+        WAS_REVISION_OF = "wasRevisionOf",
+        WAS_QUOTED_FROM = "wasQuotedFrom",
+        HAD_PRIMARY_SOURCE = "hadPrimarySource",
+        WAS_ATTRIBUTED_TO = "wasAttributedTo",
+        WAS_ASSOCIATED_WITH = "wasAssociatedWith",
+        ACTED_ON_BEHALF_OF = "actedOnBehalfOf",
+        USED = "used",
+        WAS_INFORMED_BY = "wasInformedBy",
+        WAS_STARTED_BY = "wasStartedBy",
+        WAS_ENDED_BY = "wasEndedBy",
+        WAS_INVALIDATED_BY = "wasInvalidatedBy",
+        STARTED_AT_TIME = "startedAtTime",
+        ENDED_AT_TIME = "endedAtTime",
+        GENERATED_AT_TIME = "generatedAtTime",
+        INVALIDATED_AT_TIME = "invalidatedAtTime",
+        HAD_MEMBER = "hadMember",
+        AT_LOCATION = "atLocation",
+        VALUE = "value"
+    });
+}
@@ -0,0 +1,104 @@
+use crate::core::{ Uri, Object, uri::UriType };
+
+/// A term of a well-known vocabulary, such as [`rdfs::SUB_CLASS_OF`](crate::vocab::rdfs::SUB_CLASS_OF).
+///
+/// A term knows both its absolute IRI and the prefixed name it is usually written with, and
+/// matches a [`Uri`] written either way.
+///
+/// # Examples
+///
+/// ```
+/// # use rdf_rs::parsing::{ TurtleParser, BaseParser, ParserError };
+/// # use rdf_rs::vocab::rdfs;
+/// # fn main() -> Result<(), ParserError> {
+/// let prefixed = TurtleParser::uri("rdfs:subClassOf")?;
+/// let full = TurtleParser::uri("<http://www.w3.org/2000/01/rdf-schema#subClassOf>")?;
+///
+/// assert!(rdfs::SUB_CLASS_OF.matches(&prefixed));
+/// assert!(rdfs::SUB_CLASS_OF.matches(&full));
+/// assert_eq!(rdfs::SUB_CLASS_OF.to_string(), "rdfs:subClassOf");
+/// assert_eq!(rdfs::SUB_CLASS_OF.iri(), "http://www.w3.org/2000/01/rdf-schema#subClassOf");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VocabTerm {
+    /// The usual label of the namespace, including the colon (`"rdfs:"`).
+    pub prefix: &'static str,
+    pub namespace: &'static str,
+    pub name: &'static str
+}
+
+impl VocabTerm {
+    pub const fn new(prefix: &'static str, namespace: &'static str, name: &'static str) -> Self {
+        Self { prefix, namespace, name }
+    }
+
+    /// The absolute IRI of the term.
+    pub fn iri(&self) -> String {
+        format!("{}{}", self.namespace, self.name)
+    }
+
+    /// The term as an absolute [`Uri`].
+    pub fn uri(&self) -> Uri {
+        Uri::new(self.namespace, self.name, UriType::Full)
+    }
+
+    /// The term as a prefixed name, such as `rdfs:subClassOf`.
+    pub fn prefixed(&self) -> Uri {
+        Uri::new(self.prefix, self.name, UriType::Prefixed)
+    }
+
+    /// Returns true if `u` is this term, written as its absolute IRI or with the usual prefix of
+    /// its namespace.
+    pub fn matches(&self, u: &Uri) -> bool {
+        let expected = match u.uri_type {
+            UriType::Full => self.namespace,
+            UriType::Prefixed => self.prefix,
+            _ => return false
+        };
+
+        // The parser may split an IRI anywhere, so the parts are compared as a whole
+        u.prefix.len() + u.name.len() == expected.len() + self.name.len() &&
+            u.prefix.bytes().chain(u.name.bytes()).eq(expected.bytes().chain(self.name.bytes()))
+    }
+
+    /// Returns true if `o` is a resource that [`VocabTerm::matches()`] this term.
+    pub fn matches_object(&self, o: &Object) -> bool {
+        o.resource().is_some_and(|u| self.matches(u))
+    }
+}
+
+impl std::fmt::Display for VocabTerm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.prefix, self.name)
+    }
+}
+
+impl From<VocabTerm> for Uri {
+    fn from(term: VocabTerm) -> Self {
+        term.uri()
+    }
+}
+
+// Declares the namespace, its usual prefix, a constant for each term and the list of the terms
+macro_rules! vocabulary {
+    ($prefix:literal, $namespace:literal, { $( $constant:ident = $name:literal ),* $(,)? }) => {
+        use crate::vocab::VocabTerm;
+
+        /// The namespace IRI.
+        pub const NAMESPACE: &str = $namespace;
+        /// The usual label of the namespace, including the colon.
+        pub const PREFIX: &str = $prefix;
+
+        $(
+            #[doc = concat!("`", $prefix, $name, "`")]
+            pub const $constant: VocabTerm = VocabTerm::new(PREFIX, NAMESPACE, $name);
+        )*
+
+        /// Every term of the vocabulary declared here.
+        pub const TERMS: &[VocabTerm] = &[ $( $constant ),* ];
+    };
+}
+
+pub(crate) use vocabulary;
@@ -0,0 +1,48 @@
+#![allow(dead_code, unused_imports)]
+use crate::vocab::*;
+use crate::core::{ Uri, Object };
+use crate::parsing::{ BaseParser, TurtleParser, ParserError };
+
+type TestReturn = Result<(), ParserError>;
+
+#[test]
+fn can_match_vocabulary_terms() -> TestReturn {
+    assert!(rdf::TYPE.matches(&TurtleParser::uri("rdf:type")?));
+    assert!(rdf::TYPE.matches(&TurtleParser::relationship("a")?.0));
+    assert!(rdf::TYPE.matches(&TurtleParser::uri("<http://www.w3.org/1999/02/22-rdf-syntax-ns#type>")?));
+    assert!(rdf::TYPE.matches(&rdf::TYPE.uri()));
+    assert!(rdf::TYPE.matches(&rdf::TYPE.prefixed()));
+
+    assert!(!rdf::TYPE.matches(&TurtleParser::uri("rdfs:type")?));
+    assert!(!rdf::TYPE.matches(&TurtleParser::uri("rdf:typeOf")?));
+    assert!(!rdf::TYPE.matches(&TurtleParser::uri(":type")?));
+    assert!(!rdfs::CLASS.matches(&owl::CLASS.uri()));
+
+    assert!(rdfs::CLASS.matches_object(&TurtleParser::object("rdfs:Class")?));
+    assert!(!xsd::STRING.matches_object(&TurtleParser::object("\"xsd:string\"")?));
+
+    assert_eq!(Uri::from(skos::PREF_LABEL).to_string(), "http://www.w3.org/2004/02/skos/core#prefLabel");
+    assert_eq!(dcterms::MODIFIED.to_string(), "dcterms:modified");
+
+    Ok(())
+}
+
+#[test]
+fn vocabularies_are_consistent() {
+    let vocabularies = [
+        rdf::TERMS, rdfs::TERMS, xsd::TERMS, owl::TERMS, skos::TERMS,
+        foaf::TERMS, dc::TERMS, dcterms::TERMS, sh::TERMS, prov::TERMS
+    ];
+
+    for terms in vocabularies.iter() {
+        let mut names: Vec<&str> = terms.iter().map(|t| t.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), terms.len());
+
+        assert!(terms.iter().all(|t| t.namespace == terms[0].namespace && t.prefix == terms[0].prefix));
+        assert!(terms[0].namespace.ends_with(['#', '/']));
+    }
+
+    assert_eq!(crate::parsing::ParserOptions::default().prefixes[rdf::PREFIX], rdf::NAMESPACE);
+}